@@ -38,6 +38,36 @@ pub enum SolmatesError {
     #[msg("Asset gate token account required")]
     AssetGateRequired,
 
+    #[msg("Asset gate has too many rules")]
+    TooManyGateRules,
+
+    #[msg("Asset gate account is not a token account")]
+    GateAccountInvalid,
+
+    #[msg("Asset gate token account is not owned by the holder")]
+    GateAccountOwnerMismatch,
+
+    #[msg("Asset gate token account is frozen")]
+    GateAccountFrozen,
+
+    #[msg("No NFT from the required collection is held")]
+    CollectionNotHeld,
+
+    #[msg("Minimum profile age cannot be negative")]
+    InvalidProfileAge,
+
+    #[msg("Bidder allowlist has too many entries")]
+    AllowlistTooLong,
+
+    #[msg("Bidder is not on the auction allowlist")]
+    BidderNotAllowlisted,
+
+    #[msg("Bidder profile required for this auction")]
+    BidderProfileRequired,
+
+    #[msg("Bidder profile is too new for this auction")]
+    ProfileTooNew,
+
     #[msg("DM deposit amount is below recipient's required price")]
     InsufficientDmDeposit,
 
@@ -49,4 +79,274 @@ pub enum SolmatesError {
 
     #[msg("Invalid treasury account")]
     InvalidTreasury,
+
+    #[msg("Mints with a permanent delegate are not supported")]
+    PermanentDelegateNotSupported,
+
+    #[msg("Non-transferable mints are not supported")]
+    NonTransferableMint,
+
+    #[msg("Mints with a transfer hook are not supported")]
+    TransferHookNotSupported,
+
+    #[msg("Profile still hosts live auctions")]
+    ProfileHasOpenAuctions,
+
+    #[msg("Profile still has open bounties")]
+    ProfileHasOpenBounties,
+
+    #[msg("Profile still has pending DM escrows")]
+    ProfileHasPendingEscrows,
+
+    #[msg("Account layout is outdated - migrate it first")]
+    AccountVersionMismatch,
+
+    #[msg("Account is already on the current layout")]
+    AccountAlreadyMigrated,
+
+    #[msg("Account is not a legacy account of the expected type")]
+    InvalidLegacyAccount,
+
+    #[msg("Session scopes are empty or unknown")]
+    InvalidSessionScope,
+
+    #[msg("Session duration is out of range")]
+    InvalidSessionDuration,
+
+    #[msg("Session delegate cannot be the authority itself")]
+    InvalidSessionDelegate,
+
+    #[msg("Session has expired")]
+    SessionExpired,
+
+    #[msg("Session does not grant this action")]
+    SessionScopeNotGranted,
+
+    #[msg("Guardian list is empty, too long, has duplicates or includes the authority")]
+    InvalidGuardians,
+
+    #[msg("Guardian threshold must be between 1 and the number of guardians")]
+    InvalidGuardianThreshold,
+
+    #[msg("Signer is not a guardian of this profile")]
+    NotAGuardian,
+
+    #[msg("Guardian has already approved this recovery")]
+    RecoveryAlreadyApproved,
+
+    #[msg("New authority must differ from the current one")]
+    InvalidNewAuthority,
+
+    #[msg("Recovery does not have enough guardian approvals")]
+    RecoveryThresholdNotMet,
+
+    #[msg("Recovery timelock has not elapsed")]
+    RecoveryTimelockActive,
+
+    #[msg("Handle length is out of range")]
+    InvalidHandleLength,
+
+    #[msg("Handle must be lowercase with no surrounding whitespace")]
+    HandleNotNormalized,
+
+    #[msg("Handle may only contain a-z, 0-9 and underscores")]
+    InvalidHandleCharacters,
+
+    #[msg("Profile already has a handle")]
+    ProfileAlreadyHasHandle,
+
+    #[msg("Handle is not held by this profile")]
+    HandleNotOwned,
+
+    #[msg("Profile still holds a handle - release it first")]
+    ProfileHasHandle,
+
+    #[msg("Verifier name is too long")]
+    VerifierNameTooLong,
+
+    #[msg("Attestation expiry must be in the future")]
+    InvalidAttestationExpiry,
+
+    #[msg("A live attestation from a registered verifier is required")]
+    AttestationRequired,
+
+    #[msg("Encryption key is not a valid X25519 public key")]
+    InvalidEncryptionKey,
+
+    #[msg("Recipient has not published an encryption key")]
+    NoEncryptionKey,
+
+    #[msg("Sealed envelope is malformed or uses an unknown version")]
+    InvalidEnvelope,
+
+    #[msg("Recipient is away and not taking DMs")]
+    RecipientAway,
+
+    #[msg("Host is away and not running auctions")]
+    HostAway,
+
+    #[msg("Back-at time must be in the future")]
+    InvalidBackAt,
+
+    #[msg("Batch accounts are missing or don't match the escrow")]
+    InvalidBatchAccounts,
+
+    #[msg("Recipient's inbox is full")]
+    InboxFull,
+
+    #[msg("Inbox page does not match the escrow")]
+    InvalidInboxPage,
+
+    #[msg("Recipient's inbox and inbox page are required for this escrow")]
+    InboxRequired,
+
+    #[msg("Dynamic DM pricing cap must be at least the base price")]
+    InvalidDmPricing,
+
+    #[msg("Candidate cannot be the bounty issuer or the matchmaker")]
+    InvalidCandidate,
+
+    #[msg("Matchmaker has no live submission for this bounty")]
+    SubmissionNotLive,
+
+    #[msg("Bounty pays out through two-sided consent")]
+    ConsentRequired,
+
+    #[msg("Bounty is paid out by the issuer, not through consent")]
+    ConsentNotRequired,
+
+    #[msg("A claim on this bounty is awaiting consent")]
+    BountyClaimPending,
+
+    #[msg("Submission has no claim awaiting consent")]
+    ReferralNotClaimed,
+
+    #[msg("Issuer and candidate have not both confirmed and the objection period is still running")]
+    ConsentNotReached,
+
+    #[msg("Objection period for this claim has ended")]
+    ObjectionPeriodOver,
+
+    #[msg("Dispute window is still open")]
+    DisputeWindowActive,
+
+    #[msg("Dispute window has closed")]
+    DisputeWindowOver,
+
+    #[msg("Bounty expiry must be in the future")]
+    InvalidBountyExpiry,
+
+    #[msg("Bounty has expired")]
+    BountyExpired,
+
+    #[msg("Bounty has not expired yet")]
+    BountyNotExpired,
+
+    #[msg("Submission belongs to a different bounty")]
+    SubmissionBountyMismatch,
+
+    #[msg("Bounty already has the maximum number of contributors")]
+    TooManyContributors,
+
+    #[msg("Contribution accounts are missing or don't match the bounty")]
+    ContributionMismatch,
+
+    #[msg("Contribution is still tied to a live bounty")]
+    ContributionStillLive,
+
+    #[msg("Bounty reward cannot drop below what contributors put in")]
+    BountyBelowContributions,
+
+    #[msg("Split shares must be non-zero basis points adding up to 10000, for at most 5 matchmakers")]
+    InvalidSplitShares,
+
+    #[msg("Split recipient accounts are missing or don't match")]
+    InvalidSplitAccounts,
+
+    #[msg("Milestones must be non-zero, at most 3, add up to the reward and use issuer payouts")]
+    InvalidMilestones,
+
+    #[msg("Bounty pays out by milestones")]
+    MilestoneBounty,
+
+    #[msg("Bounty has no milestones")]
+    NotMilestoneBounty,
+
+    #[msg("Milestones must be released in order, each once")]
+    InvalidMilestoneIndex,
+
+    #[msg("Milestone is still time-locked")]
+    MilestoneLocked,
+
+    #[msg("This bounty only accepts registered matchmakers")]
+    MatchmakerNotRegistered,
+
+    #[msg("Matchmaker doesn't have the track record this bounty requires")]
+    InsufficientTrackRecord,
+
+    #[msg("Profiles can't like themselves")]
+    InvalidLike,
+
+    #[msg("The likee hasn't liked back, so there's no match to create")]
+    NotLikedBack,
+
+    #[msg("The pair's match account must be passed")]
+    MatchAccountRequired,
+
+    #[msg("Match rent must be refunded to the wallet that paid it")]
+    MatchPayerMismatch,
+
+    #[msg("Super-like amount must be greater than zero")]
+    InvalidSuperLikeAmount,
+
+    #[msg("The likee already liked back")]
+    AlreadyLikedBack,
+
+    #[msg("Like back came after the super-like window")]
+    SuperLikeExpired,
+
+    #[msg("Super-like window has not closed yet")]
+    SuperLikeNotExpired,
+
+    #[msg("Super-like was earned by a like back within the window")]
+    SuperLikeLikedBack,
+
+    #[msg("Date stake must be greater than zero")]
+    InvalidDateStake,
+
+    #[msg("Date must be scheduled in the future")]
+    InvalidDateTime,
+
+    #[msg("Signer's profile isn't a party to this match")]
+    NotDateParty,
+
+    #[msg("This side of the date has already staked")]
+    AlreadyStaked,
+
+    #[msg("Stakes close at the scheduled time")]
+    DateStakingClosed,
+
+    #[msg("Both sides must stake first")]
+    DateNotStaked,
+
+    #[msg("A date both sides staked for can't be cancelled")]
+    DateFullyStaked,
+
+    #[msg("Attendance can only be confirmed within the window after the date")]
+    AttendanceNotOpen,
+
+    #[msg("Attendance window is still open")]
+    AttendanceWindowOpen,
+
+    #[msg("Obligation accounts don't match the profile being migrated")]
+    ObligationAccountsMismatch,
+
+    #[msg("Collection gate proof must be an NFT mint - zero decimals and a supply of one")]
+    CollectionMintNotNft,
+
+    #[msg("Bounty reward is below the matchmaker's minimum fee")]
+    RewardBelowMatchmakerFee,
+
+    #[msg("The match has a date booking - settle or cancel it first")]
+    DateBookingActive,
 }
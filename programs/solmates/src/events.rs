@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+use crate::states::{Availability, ClaimType, ContributorCredit, DmPricing};
+
 #[event]
 pub struct ProfileCreated {
     pub authority: Pubkey,
@@ -12,6 +14,135 @@ pub struct ProfileUpdated {
     pub dm_price: u64,
 }
 
+#[event]
+pub struct AccountMigrated {
+    pub account: Pubkey,
+    pub version: u8,
+}
+
+#[event]
+pub struct ProfileClosed {
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct SessionCreated {
+    pub authority: Pubkey,
+    pub delegate: Pubkey,
+    pub scopes: u8,
+    pub expires_at: i64,
+}
+
+#[event]
+pub struct SessionRevoked {
+    pub authority: Pubkey,
+    pub delegate: Pubkey,
+}
+
+#[event]
+pub struct DmPricingSet {
+    pub profile: Pubkey,
+    pub pricing: Option<DmPricing>,
+}
+
+#[event]
+pub struct AvailabilityChanged {
+    pub profile: Pubkey,
+    pub availability: Availability,
+}
+
+#[event]
+pub struct InboxLimitSet {
+    pub recipient: Pubkey,
+    pub max_pending: u32,
+}
+
+#[event]
+pub struct KeyRotated {
+    pub profile: Pubkey,
+    pub encryption_key: Option<[u8; 32]>,
+    pub key_version: u32,
+}
+
+#[event]
+pub struct HandleClaimed {
+    pub handle: String,
+    pub profile: Pubkey,
+}
+
+#[event]
+pub struct HandleReleased {
+    pub handle: String,
+    pub profile: Pubkey,
+}
+
+#[event]
+pub struct HandleTransferred {
+    pub handle: String,
+    pub from_profile: Pubkey,
+    pub to_profile: Pubkey,
+}
+
+#[event]
+pub struct VerifierAdded {
+    pub verifier: Pubkey,
+    pub name: String,
+}
+
+#[event]
+pub struct VerifierRemoved {
+    pub verifier: Pubkey,
+}
+
+#[event]
+pub struct AttestationIssued {
+    pub profile: Pubkey,
+    pub verifier: Pubkey,
+    pub claim_type: ClaimType,
+    pub expires_at: i64,
+}
+
+#[event]
+pub struct AttestationRevoked {
+    pub profile: Pubkey,
+    pub verifier: Pubkey,
+    pub claim_type: ClaimType,
+}
+
+#[event]
+pub struct GuardiansUpdated {
+    pub profile: Pubkey,
+    pub guardians: Vec<Pubkey>,
+    pub threshold: u8,
+}
+
+#[event]
+pub struct RecoveryInitiated {
+    pub profile: Pubkey,
+    pub proposer: Pubkey,
+    pub new_authority: Pubkey,
+    pub executable_at: i64,
+}
+
+#[event]
+pub struct RecoveryApproved {
+    pub profile: Pubkey,
+    pub guardian: Pubkey,
+    pub approvals: u8,
+}
+
+#[event]
+pub struct RecoveryCancelled {
+    pub profile: Pubkey,
+}
+
+#[event]
+pub struct AuthorityRotated {
+    pub profile: Pubkey,
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
+}
+
 #[event]
 pub struct EscrowCreated {
     pub sender: Pubkey,
@@ -67,28 +198,212 @@ pub struct AuctionClaimed {
     pub fee: u64,
 }
 
+#[event]
+pub struct ProfileLiked {
+    pub liker: Pubkey,
+    pub likee: Pubkey,
+}
+
+#[event]
+pub struct LikeRemoved {
+    pub liker: Pubkey,
+    pub likee: Pubkey,
+}
+
+#[event]
+pub struct MatchCreated {
+    pub user_a: Pubkey,
+    pub user_b: Pubkey,
+}
+
+#[event]
+pub struct MatchClosed {
+    pub user_a: Pubkey,
+    pub user_b: Pubkey,
+    pub closed_by: Pubkey,
+}
+
+#[event]
+pub struct SuperLikeSent {
+    pub liker: Pubkey,
+    pub likee: Pubkey,
+    pub amount: u64,
+    pub expiry: i64,
+}
+
+#[event]
+pub struct SuperLikeClaimed {
+    pub liker: Pubkey,
+    pub likee: Pubkey,
+    pub amount: u64,
+    pub fee: u64,
+}
+
+#[event]
+pub struct SuperLikeRefunded {
+    pub liker: Pubkey,
+    pub likee: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct DateBooked {
+    pub pair_match: Pubkey,
+    pub booked_by: Pubkey,
+    pub stake: u64,
+    pub scheduled_at: i64,
+}
+
+#[event]
+pub struct DateStaked {
+    pub pair_match: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct DateCancelled {
+    pub pair_match: Pubkey,
+    pub cancelled_by: Pubkey,
+    pub refunded: u64,
+}
+
+#[event]
+pub struct AttendanceConfirmed {
+    pub pair_match: Pubkey,
+    pub user: Pubkey,
+}
+
+#[event]
+pub struct DateSettled {
+    pub pair_match: Pubkey,
+    pub user_a: Pubkey,
+    pub user_b: Pubkey,
+    pub a_amount: u64,
+    pub b_amount: u64,
+}
+
+#[event]
+pub struct MatchmakerRegistered {
+    pub matchmaker: Pubkey,
+    pub min_fee: u64,
+    pub specialties_hash: [u8; 32],
+}
+
+#[event]
+pub struct MatchmakerUpdated {
+    pub matchmaker: Pubkey,
+    pub min_fee: u64,
+    pub specialties_hash: [u8; 32],
+}
+
 #[event]
 pub struct BountyCreated {
     pub issuer: Pubkey,
     pub reward_amount: u64,
+    pub expires_at: Option<i64>,
+    pub min_matchmaker_payouts: Option<u32>,
 }
 
 #[event]
 pub struct BountyUpdated {
     pub issuer: Pubkey,
     pub new_amount: u64,
+    pub expires_at: Option<i64>,
 }
 
 #[event]
 pub struct BountyPaid {
     pub issuer: Pubkey,
     pub matchmaker: Pubkey,
+    pub candidate: Pubkey,
     pub amount: u64,
     pub fee: u64,
+    pub contributors: Vec<ContributorCredit>, // Wallets that crowdfunded the reward
 }
 
 #[event]
-pub struct BountyCancelled {
+pub struct MilestoneReleased {
+    pub issuer: Pubkey,
+    pub matchmaker: Pubkey,
+    pub index: u8,
+    pub amount: u64,
+    pub fee: u64,
+}
+
+#[event]
+pub struct BountyContributed {
     pub issuer: Pubkey,
+    pub contributor: Pubkey,
     pub amount: u64,
+    pub reward_amount: u64,
+}
+
+#[event]
+pub struct ContributionRefunded {
+    pub issuer: Pubkey,
+    pub contributor: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct SubmissionCreated {
+    pub bounty: Pubkey,
+    pub matchmaker: Pubkey,
+    pub candidate: Pubkey,
+    pub note_hash: [u8; 32],
+}
+
+#[event]
+pub struct SubmissionWithdrawn {
+    pub bounty: Pubkey,
+    pub matchmaker: Pubkey,
+}
+
+#[event]
+pub struct SubmissionRejected {
+    pub bounty: Pubkey,
+    pub matchmaker: Pubkey,
+}
+
+#[event]
+pub struct ReferralClaimed {
+    pub bounty: Pubkey,
+    pub matchmaker: Pubkey,
+    pub candidate: Pubkey,
+    pub objection_ends_at: i64,
+}
+
+#[event]
+pub struct ReferralConfirmed {
+    pub bounty: Pubkey,
+    pub confirmer: Pubkey,
+}
+
+#[event]
+pub struct ReferralDisputed {
+    pub bounty: Pubkey,
+    pub disputer: Pubkey,
+    pub dispute_ends_at: i64,
+}
+
+#[event]
+pub struct ReferralDisputeExpired {
+    pub bounty: Pubkey,
+    pub matchmaker: Pubkey,
+}
+
+#[event]
+pub struct BountyExpired {
+    pub issuer: Pubkey,
+    pub amount: u64, // Refunded to the issuer
+    pub voided_submissions: u32,
+    pub refunding_contributors: u32,
+}
+
+#[event]
+pub struct BountyCancelled {
+    pub issuer: Pubkey,
+    pub amount: u64, // Refunded to the issuer
+    pub refunding_contributors: u32,
 }
@@ -1,12 +1,15 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{self, Mint, Token, TokenAccount, Transfer},
+    token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked},
 };
 
 use crate::errors::SolmatesError;
 use crate::events::EscrowAccepted;
-use crate::states::{EscrowStatus, MessageEscrow};
+use crate::states::{
+    EscrowStatus, Inbox, InboxPage, MessageEscrow, SessionDelegate, UserProfile,
+};
+use crate::utils::{authorize_session, unindex_escrow};
 use crate::{PLATFORM_FEE_BPS, TREASURY};
 
 #[derive(Accounts)]
@@ -15,10 +18,35 @@ pub struct AcceptDm<'info> {
     #[account(mut)]
     pub sender: UncheckedAccount<'info>,
 
+    /// CHECK: Recipient profile identity - validated against the escrow
+    pub recipient: UncheckedAccount<'info>,
+
+    /// CHECK: Recipient profile's current authority - receives the payment
+    #[account(address = recipient_profile.authority @ SolmatesError::Unauthorized)]
+    pub recipient_authority: UncheckedAccount<'info>,
+
+    /// Recipient profile's current authority, or a session delegate acting for them
     #[account(mut)]
-    pub recipient: Signer<'info>,
+    pub signer: Signer<'info>,
+
+    /// Optional: session the recipient's authority granted to `signer`, when `signer` is
+    /// a delegate
+    #[account(
+        seeds = [b"session", recipient_profile.authority.as_ref(), signer.key().as_ref()],
+        bump = session.bump,
+        constraint = session.version == SessionDelegate::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub session: Option<Account<'info, SessionDelegate>>,
+
+    #[account(
+        mut,
+        seeds = [b"profile", recipient.key().as_ref()],
+        bump = recipient_profile.bump,
+        constraint = recipient_profile.version == UserProfile::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub recipient_profile: Account<'info, UserProfile>,
 
-    pub mint: Account<'info, Mint>,
+    pub mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         mut,
@@ -27,24 +55,49 @@ pub struct AcceptDm<'info> {
         has_one = sender,
         has_one = recipient,
         has_one = mint,
-        close = sender
+        close = sender,
+        constraint = escrow.version == MessageEscrow::VERSION @ SolmatesError::AccountVersionMismatch
     )]
     pub escrow: Account<'info, MessageEscrow>,
 
+    /// Recipient's inbox - required when the escrow was indexed on deposit
+    #[account(
+        mut,
+        seeds = [b"inbox", recipient.key().as_ref()],
+        bump = inbox.bump,
+        constraint = inbox.version == Inbox::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub inbox: Option<Account<'info, Inbox>>,
+
+    /// Inbox page indexing the escrow - required when the escrow was indexed on deposit
+    #[account(
+        mut,
+        seeds = [
+            b"inbox_page",
+            recipient.key().as_ref(),
+            escrow.inbox_page.unwrap_or_default().to_le_bytes().as_ref()
+        ],
+        bump = inbox_page.bump,
+        constraint = inbox_page.version == InboxPage::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub inbox_page: Option<Account<'info, InboxPage>>,
+
     #[account(
         mut,
         associated_token::mint = mint,
-        associated_token::authority = escrow
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program
     )]
-    pub escrow_vault: Account<'info, TokenAccount>,
+    pub escrow_vault: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         init_if_needed,
-        payer = recipient,
+        payer = signer,
         associated_token::mint = mint,
-        associated_token::authority = recipient
+        associated_token::authority = recipient_authority,
+        associated_token::token_program = token_program
     )]
-    pub recipient_token_account: Account<'info, TokenAccount>,
+    pub recipient_token_account: InterfaceAccount<'info, TokenAccount>,
 
     /// CHECK: Platform treasury for fees - validated against constant
     #[account(
@@ -55,18 +108,26 @@ pub struct AcceptDm<'info> {
 
     #[account(
         init_if_needed,
-        payer = recipient,
+        payer = signer,
         associated_token::mint = mint,
-        associated_token::authority = treasury
+        associated_token::authority = treasury,
+        associated_token::token_program = token_program
     )]
-    pub treasury_token_account: Account<'info, TokenAccount>,
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
 pub fn handler(ctx: Context<AcceptDm>) -> Result<()> {
+    authorize_session(
+        &ctx.accounts.recipient_profile.authority,
+        &ctx.accounts.signer.key(),
+        ctx.accounts.session.as_deref(),
+        SessionDelegate::SCOPE_ACCEPT_DM,
+    )?;
+
     let escrow = &mut ctx.accounts.escrow;
 
     require!(
@@ -100,29 +161,49 @@ pub fn handler(ctx: Context<AcceptDm>) -> Result<()> {
     // Transfer to recipient
     let transfer_ctx = CpiContext::new_with_signer(
         ctx.accounts.token_program.to_account_info(),
-        Transfer {
+        TransferChecked {
             from: ctx.accounts.escrow_vault.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
             to: ctx.accounts.recipient_token_account.to_account_info(),
             authority: ctx.accounts.escrow.to_account_info(),
         },
         signer_seeds,
     );
-    token::transfer(transfer_ctx, recipient_amount)?;
+    token_interface::transfer_checked(transfer_ctx, recipient_amount, ctx.accounts.mint.decimals)?;
 
     // Transfer fee to treasury
     if fee > 0 {
         let fee_ctx = CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
-            Transfer {
+            TransferChecked {
                 from: ctx.accounts.escrow_vault.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
                 to: ctx.accounts.treasury_token_account.to_account_info(),
                 authority: ctx.accounts.escrow.to_account_info(),
             },
             signer_seeds,
         );
-        token::transfer(fee_ctx, fee)?;
+        token_interface::transfer_checked(fee_ctx, fee, ctx.accounts.mint.decimals)?;
     }
 
+    unindex_escrow(
+        &ctx.accounts.escrow.key(),
+        &ctx.accounts.escrow,
+        ctx.accounts.inbox.as_deref_mut(),
+        ctx.accounts.inbox_page.as_deref_mut(),
+    )?;
+
+    // Escrow is settled - release the recipient's pending obligation
+    let recipient_profile = &mut ctx.accounts.recipient_profile;
+    recipient_profile.pending_escrows = recipient_profile.pending_escrows.saturating_sub(1);
+    recipient_profile.reputation.dms_accepted =
+        recipient_profile.reputation.dms_accepted.saturating_add(1);
+    recipient_profile.reputation.dm_earned = recipient_profile
+        .reputation
+        .dm_earned
+        .saturating_add(recipient_amount);
+    recipient_profile.record_accept(Clock::get()?.unix_timestamp);
+
     emit!(EscrowAccepted {
         sender,
         recipient,
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::SolmatesError;
+use crate::events::VerifierAdded;
+use crate::states::Verifier;
+use crate::{ADMIN, MAX_VERIFIER_NAME_LEN};
+
+/// Registers a verifier whose attestations can satisfy access gates. Admin only.
+#[derive(Accounts)]
+#[instruction(verifier: Pubkey)]
+pub struct AddVerifier<'info> {
+    #[account(
+        mut,
+        constraint = admin.key() == ADMIN @ SolmatesError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Verifier::INIT_SPACE,
+        seeds = [b"verifier", verifier.as_ref()],
+        bump
+    )]
+    pub verifier_record: Account<'info, Verifier>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<AddVerifier>, verifier: Pubkey, name: String) -> Result<()> {
+    require!(
+        name.len() <= MAX_VERIFIER_NAME_LEN,
+        SolmatesError::VerifierNameTooLong
+    );
+
+    let record = &mut ctx.accounts.verifier_record;
+    record.version = Verifier::VERSION;
+    record.verifier = verifier;
+    record.name = name.clone();
+    record.added_at = Clock::get()?.unix_timestamp;
+    record.bump = ctx.bumps.verifier_record;
+
+    emit!(VerifierAdded { verifier, name });
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::SolmatesError;
+use crate::events::RecoveryApproved;
+use crate::states::{GuardianSet, RecoveryRequest};
+
+#[derive(Accounts)]
+pub struct ApproveRecovery<'info> {
+    pub guardian: Signer<'info>,
+
+    #[account(
+        seeds = [b"guardians", recovery.profile.as_ref()],
+        bump = guardian_set.bump,
+        constraint = guardian_set.is_guardian(&guardian.key()) @ SolmatesError::NotAGuardian,
+        constraint = guardian_set.version == GuardianSet::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub guardian_set: Account<'info, GuardianSet>,
+
+    #[account(
+        mut,
+        seeds = [b"recovery", recovery.profile.as_ref()],
+        bump = recovery.bump,
+        constraint = recovery.version == RecoveryRequest::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub recovery: Account<'info, RecoveryRequest>,
+}
+
+pub fn handler(ctx: Context<ApproveRecovery>) -> Result<()> {
+    let guardian = ctx.accounts.guardian.key();
+    let guardian_set = &ctx.accounts.guardian_set;
+    let recovery = &mut ctx.accounts.recovery;
+
+    // Drop approvals from guardians removed since - they'd otherwise fill the vector
+    // and keep current guardians from approving
+    recovery
+        .approvals
+        .retain(|approval| guardian_set.is_guardian(approval));
+
+    require!(
+        !recovery.approvals.contains(&guardian),
+        SolmatesError::RecoveryAlreadyApproved
+    );
+    recovery.approvals.push(guardian);
+
+    emit!(RecoveryApproved {
+        profile: recovery.profile,
+        guardian,
+        approvals: recovery.approvals.len() as u8,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,139 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::errors::SolmatesError;
+use crate::events::{DateBooked, DateStaked};
+use crate::states::{DateBooking, DateParty, Match, UserProfile};
+use crate::utils::validate_mint;
+
+/// One side of a match books a date and stakes first. The other side matches the stake
+/// with `stake_date` before the scheduled time.
+#[derive(Accounts)]
+pub struct BookDate<'info> {
+    #[account(mut)]
+    pub booker: Signer<'info>,
+
+    #[account(
+        seeds = [b"profile", booker_profile.identity.as_ref()],
+        bump = booker_profile.bump,
+        constraint = booker_profile.authority == booker.key() @ SolmatesError::Unauthorized,
+        constraint = booker_profile.version == UserProfile::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub booker_profile: Account<'info, UserProfile>,
+
+    #[account(
+        seeds = [b"match", pair_match.user_a.as_ref(), pair_match.user_b.as_ref()],
+        bump = pair_match.bump,
+        constraint = pair_match.user_a == booker_profile.identity
+            || pair_match.user_b == booker_profile.identity @ SolmatesError::NotDateParty,
+        constraint = pair_match.version == Match::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub pair_match: Account<'info, Match>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = booker,
+        associated_token::token_program = token_program
+    )]
+    pub booker_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = booker,
+        space = 8 + DateBooking::INIT_SPACE,
+        seeds = [b"date", pair_match.key().as_ref()],
+        bump
+    )]
+    pub booking: Account<'info, DateBooking>,
+
+    #[account(
+        init_if_needed,
+        payer = booker,
+        associated_token::mint = mint,
+        associated_token::authority = booking,
+        associated_token::token_program = token_program
+    )]
+    pub booking_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<BookDate>, stake: u64, scheduled_at: i64) -> Result<()> {
+    validate_mint(&ctx.accounts.mint)?;
+    require!(stake > 0, SolmatesError::InvalidDateStake);
+    require!(
+        scheduled_at > Clock::get()?.unix_timestamp,
+        SolmatesError::InvalidDateTime
+    );
+
+    // Transfer USDC from booker to the booking vault
+    let vault_balance_before = ctx.accounts.booking_vault.amount;
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.booker_token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            to: ctx.accounts.booking_vault.to_account_info(),
+            authority: ctx.accounts.booker.to_account_info(),
+        },
+    );
+    token_interface::transfer_checked(transfer_ctx, stake, ctx.accounts.mint.decimals)?;
+
+    // Credit only what actually arrived - transfer-fee mints withhold part of the amount
+    ctx.accounts.booking_vault.reload()?;
+    let received = ctx
+        .accounts
+        .booking_vault
+        .amount
+        .checked_sub(vault_balance_before)
+        .unwrap();
+
+    let pair_match = &ctx.accounts.pair_match;
+    let booking = &mut ctx.accounts.booking;
+    booking.version = DateBooking::VERSION;
+    booking.pair_match = pair_match.key();
+    booking.mint = ctx.accounts.mint.key();
+    booking.stake = stake;
+    booking.scheduled_at = scheduled_at;
+    booking.party_a = DateParty {
+        identity: pair_match.user_a,
+        staker: None,
+        deposit: 0,
+        confirmed: false,
+    };
+    booking.party_b = DateParty {
+        identity: pair_match.user_b,
+        staker: None,
+        deposit: 0,
+        confirmed: false,
+    };
+    booking.payer = ctx.accounts.booker.key();
+    booking.bump = ctx.bumps.booking;
+
+    let booker = ctx.accounts.booker_profile.identity;
+    let party = booking.party_mut(&booker)?;
+    party.staker = Some(ctx.accounts.booker.key());
+    party.deposit = received;
+
+    emit!(DateBooked {
+        pair_match: booking.pair_match,
+        booked_by: booker,
+        stake,
+        scheduled_at,
+    });
+    emit!(DateStaked {
+        pair_match: booking.pair_match,
+        user: booker,
+        amount: received,
+    });
+
+    Ok(())
+}
@@ -1,35 +1,46 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::errors::SolmatesError;
 use crate::events::AuctionCancelled;
-use crate::states::DateAuction;
+use crate::states::{DateAuction, UserProfile};
 
 #[derive(Accounts)]
 pub struct CancelAuction<'info> {
     #[account(mut)]
     pub host: Signer<'info>,
 
-    pub mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [b"profile", host_profile.identity.as_ref()],
+        bump = host_profile.bump,
+        constraint = host_profile.authority == host.key() @ SolmatesError::Unauthorized,
+        constraint = host_profile.version == UserProfile::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub host_profile: Account<'info, UserProfile>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         mut,
-        seeds = [b"auction", host.key().as_ref(), auction.auction_id.to_le_bytes().as_ref()],
+        seeds = [b"auction", auction.host.as_ref(), auction.auction_id.to_le_bytes().as_ref()],
         bump = auction.bump,
-        has_one = host,
         has_one = mint,
-        close = host
+        constraint = auction.host == host_profile.identity @ SolmatesError::Unauthorized,
+        close = host,
+        constraint = auction.version == DateAuction::VERSION @ SolmatesError::AccountVersionMismatch
     )]
     pub auction: Account<'info, DateAuction>,
 
     #[account(
         mut,
         associated_token::mint = mint,
-        associated_token::authority = auction
+        associated_token::authority = auction,
+        associated_token::token_program = token_program
     )]
-    pub auction_vault: Account<'info, TokenAccount>,
+    pub auction_vault: InterfaceAccount<'info, TokenAccount>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 pub fn handler(ctx: Context<CancelAuction>) -> Result<()> {
@@ -41,6 +52,10 @@ pub fn handler(ctx: Context<CancelAuction>) -> Result<()> {
         SolmatesError::AuctionHasBids
     );
 
+    // Release the host's open-auction obligation
+    let host_profile = &mut ctx.accounts.host_profile;
+    host_profile.open_auctions = host_profile.open_auctions.saturating_sub(1);
+
     emit!(AuctionCancelled {
         host: auction.host,
         auction_id: auction.auction_id,
@@ -1,42 +1,53 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::errors::SolmatesError;
 use crate::events::BountyCancelled;
-use crate::states::{BountyStatus, BountyVault};
+use crate::states::{BountyStatus, BountyVault, UserProfile};
+use crate::utils::refund_issuer;
 
 #[derive(Accounts)]
 pub struct CancelBounty<'info> {
     #[account(mut)]
     pub issuer: Signer<'info>,
 
-    pub mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [b"profile", bounty.issuer.as_ref()],
+        bump = issuer_profile.bump,
+        constraint = issuer_profile.authority == issuer.key() @ SolmatesError::Unauthorized,
+        constraint = issuer_profile.version == UserProfile::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub issuer_profile: Account<'info, UserProfile>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         mut,
-        seeds = [b"bounty", issuer.key().as_ref()],
+        seeds = [b"bounty", bounty.issuer.as_ref()],
         bump = bounty.bump,
-        has_one = issuer,
         has_one = mint,
-        close = issuer
+        constraint = bounty.version == BountyVault::VERSION @ SolmatesError::AccountVersionMismatch
     )]
     pub bounty: Account<'info, BountyVault>,
 
     #[account(
         mut,
         associated_token::mint = mint,
-        associated_token::authority = bounty
+        associated_token::authority = bounty,
+        associated_token::token_program = token_program
     )]
-    pub bounty_vault: Account<'info, TokenAccount>,
+    pub bounty_vault: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         mut,
         associated_token::mint = mint,
-        associated_token::authority = issuer
+        associated_token::authority = issuer,
+        associated_token::token_program = token_program
     )]
-    pub issuer_token_account: Account<'info, TokenAccount>,
+    pub issuer_token_account: InterfaceAccount<'info, TokenAccount>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 pub fn handler(ctx: Context<CancelBounty>) -> Result<()> {
@@ -46,29 +57,29 @@ pub fn handler(ctx: Context<CancelBounty>) -> Result<()> {
         bounty.status == BountyStatus::Open,
         SolmatesError::BountyNotOpen
     );
+    require!(
+        !bounty.is_claim_locked(),
+        SolmatesError::BountyClaimPending
+    );
 
-    // Transfer USDC from bounty vault back to issuer
+    // Return the issuer's share - contributors reclaim theirs with refund_contribution
     let issuer_key = bounty.issuer;
-    let seeds = &[b"bounty", issuer_key.as_ref(), &[bounty.bump]];
-    let signer_seeds = &[&seeds[..]];
-
-    let transfer_ctx = CpiContext::new_with_signer(
-        ctx.accounts.token_program.to_account_info(),
-        Transfer {
-            from: ctx.accounts.bounty_vault.to_account_info(),
-            to: ctx.accounts.issuer_token_account.to_account_info(),
-            authority: ctx.accounts.bounty.to_account_info(),
-        },
-        signer_seeds,
-    );
-    token::transfer(transfer_ctx, bounty.reward_amount)?;
+    let refunding_contributors = bounty.contributor_count;
+    let amount = refund_issuer(
+        &mut ctx.accounts.bounty,
+        &ctx.accounts.bounty_vault,
+        &ctx.accounts.mint,
+        &ctx.accounts.issuer_token_account,
+        &ctx.accounts.issuer.to_account_info(),
+        &mut ctx.accounts.issuer_profile,
+        &ctx.accounts.token_program,
+    )?;
 
     emit!(BountyCancelled {
-        issuer: bounty.issuer,
-        amount: bounty.reward_amount,
+        issuer: issuer_key,
+        amount,
+        refunding_contributors,
     });
 
-    // Account will be closed, rent returned to issuer
-
     Ok(())
 }
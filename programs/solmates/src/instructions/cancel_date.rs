@@ -0,0 +1,99 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::errors::SolmatesError;
+use crate::events::DateCancelled;
+use crate::states::{DateBooking, UserProfile};
+use crate::utils::pay_from_booking;
+
+/// Either party calls off a date the other side hasn't staked for yet. The one stake in
+/// the vault goes back to whoever made it.
+#[derive(Accounts)]
+pub struct CancelDate<'info> {
+    pub canceller: Signer<'info>,
+
+    #[account(
+        seeds = [b"profile", canceller_profile.identity.as_ref()],
+        bump = canceller_profile.bump,
+        constraint = canceller_profile.authority == canceller.key() @ SolmatesError::Unauthorized,
+        constraint = canceller_profile.version == UserProfile::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub canceller_profile: Account<'info, UserProfile>,
+
+    /// CHECK: Wallet that paid the booking's rent - validated against the booking
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+
+    /// CHECK: Wallet that staked - validated against the booking
+    pub staker: UncheckedAccount<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"date", booking.pair_match.as_ref()],
+        bump = booking.bump,
+        has_one = payer,
+        has_one = mint,
+        close = payer,
+        constraint = booking.version == DateBooking::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub booking: Account<'info, DateBooking>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = booking,
+        associated_token::token_program = token_program
+    )]
+    pub booking_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = staker,
+        associated_token::token_program = token_program
+    )]
+    pub staker_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler(ctx: Context<CancelDate>) -> Result<()> {
+    let booking = &ctx.accounts.booking;
+    require!(!booking.is_staked(), SolmatesError::DateFullyStaked);
+
+    let canceller = ctx.accounts.canceller_profile.identity;
+    require!(booking.has_party(&canceller), SolmatesError::NotDateParty);
+
+    // Only one side staked - that side gets it back
+    let staked = if booking.party_a.staker.is_some() {
+        booking.party_a
+    } else {
+        booking.party_b
+    };
+    require_keys_eq!(
+        ctx.accounts.staker.key(),
+        staked.staker.unwrap_or_default(),
+        SolmatesError::Unauthorized
+    );
+
+    pay_from_booking(
+        &ctx.accounts.booking,
+        &ctx.accounts.booking_vault,
+        &ctx.accounts.mint,
+        &ctx.accounts.staker_token_account,
+        &ctx.accounts.token_program,
+        staked.deposit,
+    )?;
+
+    emit!(DateCancelled {
+        pair_match: ctx.accounts.booking.pair_match,
+        cancelled_by: canceller,
+        refunded: staked.deposit,
+    });
+
+    // Account will be closed, rent returned to payer
+
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::SolmatesError;
+use crate::events::RecoveryCancelled;
+use crate::states::{RecoveryRequest, UserProfile};
+
+/// The current authority vetoes a pending recovery. Rent goes back to the guardian who
+/// opened it.
+#[derive(Accounts)]
+pub struct CancelRecovery<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"profile", profile.identity.as_ref()],
+        bump = profile.bump,
+        has_one = authority,
+        constraint = profile.version == UserProfile::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub profile: Account<'info, UserProfile>,
+
+    /// CHECK: Guardian who opened the request - validated against it, receives the rent
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"recovery", profile.key().as_ref()],
+        bump = recovery.bump,
+        has_one = proposer,
+        close = proposer,
+        constraint = recovery.version == RecoveryRequest::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub recovery: Account<'info, RecoveryRequest>,
+}
+
+pub fn handler(ctx: Context<CancelRecovery>) -> Result<()> {
+    emit!(RecoveryCancelled {
+        profile: ctx.accounts.profile.key(),
+    });
+
+    // Account will be closed, rent returned to proposer
+
+    Ok(())
+}
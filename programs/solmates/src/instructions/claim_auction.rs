@@ -1,12 +1,13 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{self, Mint, Token, TokenAccount, Transfer},
+    token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked},
 };
 
 use crate::errors::SolmatesError;
 use crate::events::AuctionClaimed;
-use crate::states::DateAuction;
+use crate::states::{DateAuction, UserProfile};
+use crate::utils::update_profile_if_exists;
 use crate::{PLATFORM_FEE_BPS, TREASURY};
 
 #[derive(Accounts)]
@@ -14,32 +15,53 @@ pub struct ClaimAuction<'info> {
     #[account(mut)]
     pub host: Signer<'info>,
 
-    pub mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [b"profile", host_profile.identity.as_ref()],
+        bump = host_profile.bump,
+        constraint = host_profile.authority == host.key() @ SolmatesError::Unauthorized,
+        constraint = host_profile.version == UserProfile::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub host_profile: Account<'info, UserProfile>,
 
+    /// CHECK: Winner's profile PDA - always passed, and credited with the win whenever
+    /// the winning wallet has a profile
     #[account(
         mut,
-        seeds = [b"auction", host.key().as_ref(), auction.auction_id.to_le_bytes().as_ref()],
+        seeds = [b"profile", auction.highest_bidder.as_ref()],
+        bump
+    )]
+    pub winner_profile: UncheckedAccount<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"auction", auction.host.as_ref(), auction.auction_id.to_le_bytes().as_ref()],
         bump = auction.bump,
-        has_one = host,
         has_one = mint,
-        close = host
+        constraint = auction.host == host_profile.identity @ SolmatesError::Unauthorized,
+        close = host,
+        constraint = auction.version == DateAuction::VERSION @ SolmatesError::AccountVersionMismatch
     )]
     pub auction: Account<'info, DateAuction>,
 
     #[account(
         mut,
         associated_token::mint = mint,
-        associated_token::authority = auction
+        associated_token::authority = auction,
+        associated_token::token_program = token_program
     )]
-    pub auction_vault: Account<'info, TokenAccount>,
+    pub auction_vault: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         init_if_needed,
         payer = host,
         associated_token::mint = mint,
-        associated_token::authority = host
+        associated_token::authority = host,
+        associated_token::token_program = token_program
     )]
-    pub host_token_account: Account<'info, TokenAccount>,
+    pub host_token_account: InterfaceAccount<'info, TokenAccount>,
 
     /// CHECK: Platform treasury for fees - validated against constant
     #[account(
@@ -52,11 +74,12 @@ pub struct ClaimAuction<'info> {
         init_if_needed,
         payer = host,
         associated_token::mint = mint,
-        associated_token::authority = treasury
+        associated_token::authority = treasury,
+        associated_token::token_program = token_program
     )]
-    pub treasury_token_account: Account<'info, TokenAccount>,
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
@@ -94,29 +117,46 @@ pub fn handler(ctx: Context<ClaimAuction>) -> Result<()> {
 
     let transfer_ctx = CpiContext::new_with_signer(
         ctx.accounts.token_program.to_account_info(),
-        Transfer {
+        TransferChecked {
             from: ctx.accounts.auction_vault.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
             to: ctx.accounts.host_token_account.to_account_info(),
             authority: ctx.accounts.auction.to_account_info(),
         },
         signer_seeds,
     );
-    token::transfer(transfer_ctx, host_amount)?;
+    token_interface::transfer_checked(transfer_ctx, host_amount, ctx.accounts.mint.decimals)?;
 
     // Transfer fee to treasury
     if fee > 0 {
         let fee_ctx = CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
-            Transfer {
+            TransferChecked {
                 from: ctx.accounts.auction_vault.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
                 to: ctx.accounts.treasury_token_account.to_account_info(),
                 authority: ctx.accounts.auction.to_account_info(),
             },
             signer_seeds,
         );
-        token::transfer(fee_ctx, fee)?;
+        token_interface::transfer_checked(fee_ctx, fee, ctx.accounts.mint.decimals)?;
     }
 
+    // Auction is settled - release the host's open-auction obligation
+    let host_profile = &mut ctx.accounts.host_profile;
+    host_profile.open_auctions = host_profile.open_auctions.saturating_sub(1);
+    host_profile.reputation.auctions_hosted =
+        host_profile.reputation.auctions_hosted.saturating_add(1);
+    host_profile.reputation.auction_earned = host_profile
+        .reputation
+        .auction_earned
+        .saturating_add(host_amount);
+
+    update_profile_if_exists(&ctx.accounts.winner_profile, |winner_profile| {
+        winner_profile.reputation.auctions_won =
+            winner_profile.reputation.auctions_won.saturating_add(1);
+    })?;
+
     emit!(AuctionClaimed {
         auction_id: auction.auction_id,
         host: auction.host,
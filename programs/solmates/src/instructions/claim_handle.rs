@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+
+use crate::errors::SolmatesError;
+use crate::events::HandleClaimed;
+use crate::states::{HandleRecord, UserProfile};
+use crate::utils::validate_handle;
+use crate::{HANDLE_FEE_LAMPORTS, TREASURY};
+
+/// Claims a unique handle for the profile. The handle must already be normalized; the
+/// authority pays the record's rent plus a flat fee to the treasury.
+#[derive(Accounts)]
+#[instruction(handle: String)]
+pub struct ClaimHandle<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"profile", profile.identity.as_ref()],
+        bump = profile.bump,
+        has_one = authority,
+        constraint = profile.version == UserProfile::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub profile: Account<'info, UserProfile>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + HandleRecord::INIT_SPACE,
+        seeds = [b"handle", handle.as_bytes()],
+        bump
+    )]
+    pub handle_record: Account<'info, HandleRecord>,
+
+    /// CHECK: Platform treasury - receives the handle fee
+    #[account(
+        mut,
+        constraint = treasury.key() == TREASURY @ SolmatesError::InvalidTreasury
+    )]
+    pub treasury: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ClaimHandle>, handle: String) -> Result<()> {
+    validate_handle(&handle)?;
+    require!(
+        ctx.accounts.profile.handle.is_none(),
+        SolmatesError::ProfileAlreadyHasHandle
+    );
+
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.system_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.authority.to_account_info(),
+            to: ctx.accounts.treasury.to_account_info(),
+        },
+    );
+    system_program::transfer(transfer_ctx, HANDLE_FEE_LAMPORTS)?;
+
+    let record = &mut ctx.accounts.handle_record;
+    record.version = HandleRecord::VERSION;
+    record.handle = handle.clone();
+    record.profile = ctx.accounts.profile.key();
+    record.claimed_at = Clock::get()?.unix_timestamp;
+    record.bump = ctx.bumps.handle_record;
+
+    ctx.accounts.profile.handle = Some(handle.clone());
+
+    emit!(HandleClaimed {
+        handle,
+        profile: record.profile,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::SolmatesError;
+use crate::events::ReferralClaimed;
+use crate::states::{BountyStatus, BountyVault, PayoutMode, Submission, SubmissionStatus};
+use crate::CONSENT_TIMEOUT;
+
+/// Matchmaker claims a consent-mode bounty for their submission. The reward is locked to
+/// the claim until it is released or a dispute over it runs out.
+#[derive(Accounts)]
+pub struct ClaimReferral<'info> {
+    pub matchmaker: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"bounty", bounty.issuer.as_ref()],
+        bump = bounty.bump,
+        constraint = bounty.version == BountyVault::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub bounty: Account<'info, BountyVault>,
+
+    #[account(
+        mut,
+        seeds = [b"submission", bounty.key().as_ref(), matchmaker.key().as_ref()],
+        bump = submission.bump,
+        has_one = matchmaker,
+        constraint = submission.version == Submission::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub submission: Account<'info, Submission>,
+}
+
+pub fn handler(ctx: Context<ClaimReferral>) -> Result<()> {
+    let bounty = &mut ctx.accounts.bounty;
+    require!(
+        bounty.status == BountyStatus::Open,
+        SolmatesError::BountyNotOpen
+    );
+    require!(
+        bounty.payout_mode == PayoutMode::Consent,
+        SolmatesError::ConsentNotRequired
+    );
+    require!(
+        bounty.active_claim.is_none(),
+        SolmatesError::BountyClaimPending
+    );
+
+    let submission = &mut ctx.accounts.submission;
+    require!(
+        submission.is_live_for(bounty.created_at),
+        SolmatesError::SubmissionNotLive
+    );
+
+    let current_time = Clock::get()?.unix_timestamp;
+    require!(
+        !bounty.is_expired(current_time),
+        SolmatesError::BountyExpired
+    );
+    submission.status = SubmissionStatus::Claimed;
+    submission.claimed_at = current_time;
+    submission.issuer_confirmed = false;
+    submission.candidate_confirmed = false;
+    bounty.active_claim = Some(submission.key());
+
+    emit!(ReferralClaimed {
+        bounty: bounty.key(),
+        matchmaker: submission.matchmaker,
+        candidate: submission.candidate,
+        objection_ends_at: current_time.checked_add(CONSENT_TIMEOUT).unwrap(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,166 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{self, CloseAccount, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::errors::SolmatesError;
+use crate::events::SuperLikeClaimed;
+use crate::states::{Like, SuperLike, UserProfile};
+use crate::{PLATFORM_FEE_BPS, TREASURY};
+
+/// Likee collects a super-like's escrow, minus the platform fee, after liking back within
+/// the window.
+#[derive(Accounts)]
+pub struct ClaimSuperLike<'info> {
+    #[account(mut)]
+    pub likee: Signer<'info>,
+
+    #[account(
+        seeds = [b"profile", likee_profile.identity.as_ref()],
+        bump = likee_profile.bump,
+        constraint = likee_profile.authority == likee.key() @ SolmatesError::Unauthorized,
+        constraint = likee_profile.version == UserProfile::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub likee_profile: Account<'info, UserProfile>,
+
+    /// CHECK: Wallet that escrowed the tokens - receives rent refund
+    #[account(mut)]
+    pub sender: UncheckedAccount<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"super_like", super_like.liker.as_ref(), likee_profile.identity.as_ref()],
+        bump = super_like.bump,
+        has_one = sender,
+        has_one = mint,
+        close = sender,
+        constraint = super_like.version == SuperLike::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub super_like: Account<'info, SuperLike>,
+
+    /// The likee's like of the liker - must have come within the window
+    #[account(
+        seeds = [b"like", likee_profile.identity.as_ref(), super_like.liker.as_ref()],
+        bump = like_back.bump,
+        constraint = like_back.liked_at <= super_like.expiry @ SolmatesError::SuperLikeExpired,
+        constraint = like_back.version == Like::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub like_back: Account<'info, Like>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = super_like,
+        associated_token::token_program = token_program
+    )]
+    pub super_like_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = likee,
+        associated_token::mint = mint,
+        associated_token::authority = likee,
+        associated_token::token_program = token_program
+    )]
+    pub likee_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Platform treasury for fees - validated against constant
+    #[account(
+        mut,
+        constraint = treasury.key() == TREASURY @ SolmatesError::InvalidTreasury
+    )]
+    pub treasury: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = likee,
+        associated_token::mint = mint,
+        associated_token::authority = treasury,
+        associated_token::token_program = token_program
+    )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ClaimSuperLike>) -> Result<()> {
+    let super_like = &ctx.accounts.super_like;
+    let amount = super_like.amount;
+    let liker = super_like.liker;
+    let likee = super_like.likee;
+
+    // Calculate platform fee (1%)
+    let fee = amount
+        .checked_mul(PLATFORM_FEE_BPS)
+        .unwrap()
+        .checked_div(10000)
+        .unwrap();
+    // Anything sent to the vault on top of the escrow goes to the likee, so the vault
+    // always empties and can be closed
+    let vault_amount = ctx.accounts.super_like_vault.amount;
+    let likee_amount = vault_amount.checked_sub(fee).unwrap();
+
+    let seeds = &[
+        b"super_like",
+        liker.as_ref(),
+        likee.as_ref(),
+        &[super_like.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    // Transfer to likee
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.super_like_vault.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            to: ctx.accounts.likee_token_account.to_account_info(),
+            authority: ctx.accounts.super_like.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token_interface::transfer_checked(transfer_ctx, likee_amount, ctx.accounts.mint.decimals)?;
+
+    // Transfer fee to treasury
+    if fee > 0 {
+        let fee_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.super_like_vault.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.treasury_token_account.to_account_info(),
+                authority: ctx.accounts.super_like.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token_interface::transfer_checked(fee_ctx, fee, ctx.accounts.mint.decimals)?;
+    }
+
+    // Vault is empty - rent returned to sender
+    let close_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.super_like_vault.to_account_info(),
+            destination: ctx.accounts.sender.to_account_info(),
+            authority: ctx.accounts.super_like.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token_interface::close_account(close_ctx)?;
+
+    emit!(SuperLikeClaimed {
+        liker,
+        likee,
+        amount: likee_amount,
+        fee,
+    });
+
+    // Account will be closed, rent returned to sender
+
+    Ok(())
+}
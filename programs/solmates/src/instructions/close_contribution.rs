@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::SolmatesError;
+use crate::states::{BountyVault, Contribution};
+
+/// Contributor reclaims the rent of a contribution whose bounty was paid out and closed.
+#[derive(Accounts)]
+pub struct CloseContribution<'info> {
+    #[account(mut)]
+    pub contributor: Signer<'info>,
+
+    /// CHECK: The contributed bounty's address - must no longer hold that bounty
+    #[account(address = contribution.bounty @ SolmatesError::ContributionMismatch)]
+    pub bounty: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"contribution", contribution.bounty.as_ref(), contributor.key().as_ref()],
+        bump = contribution.bump,
+        has_one = contributor,
+        close = contributor,
+        constraint = contribution.version == Contribution::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub contribution: Account<'info, Contribution>,
+}
+
+pub fn handler(ctx: Context<CloseContribution>) -> Result<()> {
+    // A bounty recreated at the same address is a different instance
+    let bounty_info = &ctx.accounts.bounty;
+    if bounty_info.owner == &crate::ID && !bounty_info.data_is_empty() {
+        let bounty = BountyVault::try_deserialize(&mut &bounty_info.try_borrow_data()?[..])?;
+        require!(
+            bounty.created_at != ctx.accounts.contribution.bounty_created_at,
+            SolmatesError::ContributionStillLive
+        );
+    }
+
+    // Account will be closed, rent returned to contributor
+
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::SolmatesError;
+use crate::events::ProfileClosed;
+use crate::states::UserProfile;
+
+/// Closes a profile and returns its rent to the authority. Refused while the profile
+/// still hosts live auctions, has open bounties or has pending DM escrows to settle, or
+/// holds a handle.
+#[derive(Accounts)]
+pub struct CloseProfile<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"profile", profile.identity.as_ref()],
+        bump = profile.bump,
+        has_one = authority,
+        close = authority,
+        constraint = profile.version == UserProfile::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub profile: Account<'info, UserProfile>,
+}
+
+pub fn handler(ctx: Context<CloseProfile>) -> Result<()> {
+    let profile = &ctx.accounts.profile;
+
+    require!(profile.open_auctions == 0, SolmatesError::ProfileHasOpenAuctions);
+    require!(profile.open_bounties == 0, SolmatesError::ProfileHasOpenBounties);
+    require!(
+        profile.pending_escrows == 0,
+        SolmatesError::ProfileHasPendingEscrows
+    );
+    require!(profile.handle.is_none(), SolmatesError::ProfileHasHandle);
+
+    emit!(ProfileClosed {
+        authority: profile.authority,
+    });
+
+    // Account will be closed, rent returned to authority
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::SolmatesError;
+use crate::events::AttendanceConfirmed;
+use crate::states::{DateBooking, UserProfile};
+
+/// A party confirms they attended. Open from the scheduled time until
+/// `ATTENDANCE_WINDOW` after it, once both sides have staked.
+#[derive(Accounts)]
+pub struct ConfirmAttendance<'info> {
+    pub attendee: Signer<'info>,
+
+    #[account(
+        seeds = [b"profile", attendee_profile.identity.as_ref()],
+        bump = attendee_profile.bump,
+        constraint = attendee_profile.authority == attendee.key() @ SolmatesError::Unauthorized,
+        constraint = attendee_profile.version == UserProfile::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub attendee_profile: Account<'info, UserProfile>,
+
+    #[account(
+        mut,
+        seeds = [b"date", booking.pair_match.as_ref()],
+        bump = booking.bump,
+        constraint = booking.version == DateBooking::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub booking: Account<'info, DateBooking>,
+}
+
+pub fn handler(ctx: Context<ConfirmAttendance>) -> Result<()> {
+    let booking = &mut ctx.accounts.booking;
+    require!(booking.is_staked(), SolmatesError::DateNotStaked);
+
+    let current_time = Clock::get()?.unix_timestamp;
+    require!(
+        current_time >= booking.scheduled_at && current_time <= booking.attendance_closes_at(),
+        SolmatesError::AttendanceNotOpen
+    );
+
+    let attendee = ctx.accounts.attendee_profile.identity;
+    booking.party_mut(&attendee)?.confirmed = true;
+
+    emit!(AttendanceConfirmed {
+        pair_match: booking.pair_match,
+        user: attendee,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::SolmatesError;
+use crate::events::ReferralConfirmed;
+use crate::states::{BountyVault, Submission, SubmissionStatus, UserProfile};
+use crate::DISPUTE_WINDOW;
+
+/// The issuer or the introduced candidate confirms a claimed match. Confirming also
+/// withdraws an earlier objection from the same party.
+#[derive(Accounts)]
+pub struct ConfirmReferral<'info> {
+    /// Issuer's authority or the candidate
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [b"profile", bounty.issuer.as_ref()],
+        bump = issuer_profile.bump,
+        constraint = issuer_profile.version == UserProfile::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub issuer_profile: Account<'info, UserProfile>,
+
+    #[account(
+        seeds = [b"bounty", bounty.issuer.as_ref()],
+        bump = bounty.bump,
+        constraint = bounty.version == BountyVault::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub bounty: Account<'info, BountyVault>,
+
+    #[account(
+        mut,
+        seeds = [b"submission", bounty.key().as_ref(), submission.matchmaker.as_ref()],
+        bump = submission.bump,
+        constraint = bounty.active_claim == Some(submission.key()) @ SolmatesError::ReferralNotClaimed,
+        constraint = submission.version == Submission::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub submission: Account<'info, Submission>,
+}
+
+pub fn handler(ctx: Context<ConfirmReferral>) -> Result<()> {
+    let submission = &mut ctx.accounts.submission;
+    if submission.status == SubmissionStatus::Disputed {
+        require!(
+            Clock::get()?.unix_timestamp < submission.disputed_at.saturating_add(DISPUTE_WINDOW),
+            SolmatesError::DisputeWindowOver
+        );
+    }
+
+    let signer = ctx.accounts.signer.key();
+    let is_issuer = signer == ctx.accounts.issuer_profile.authority;
+    let is_candidate = signer == submission.candidate;
+    require!(is_issuer || is_candidate, SolmatesError::Unauthorized);
+
+    if is_issuer {
+        submission.issuer_confirmed = true;
+    }
+    if is_candidate {
+        submission.candidate_confirmed = true;
+    }
+
+    emit!(ReferralConfirmed {
+        bounty: submission.bounty,
+        confirmer: signer,
+    });
+
+    Ok(())
+}
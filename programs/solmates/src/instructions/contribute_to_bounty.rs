@@ -0,0 +1,136 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::errors::SolmatesError;
+use crate::events::BountyContributed;
+use crate::states::{BountyStatus, BountyVault, Contribution};
+use crate::utils::validate_mint;
+use crate::MAX_BOUNTY_CONTRIBUTORS;
+
+/// Any wallet other than the issuer chips in to an open bounty's reward. Repeat
+/// contributions add to the same ledger entry.
+#[derive(Accounts)]
+pub struct ContributeToBounty<'info> {
+    #[account(mut)]
+    pub contributor: Signer<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"bounty", bounty.issuer.as_ref()],
+        bump = bounty.bump,
+        has_one = mint,
+        constraint = bounty.version == BountyVault::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub bounty: Account<'info, BountyVault>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = bounty,
+        associated_token::token_program = token_program
+    )]
+    pub bounty_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = contributor,
+        associated_token::token_program = token_program
+    )]
+    pub contributor_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = contributor,
+        space = 8 + Contribution::INIT_SPACE,
+        seeds = [b"contribution", bounty.key().as_ref(), contributor.key().as_ref()],
+        bump
+    )]
+    pub contribution: Account<'info, Contribution>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ContributeToBounty>, amount: u64) -> Result<()> {
+    validate_mint(&ctx.accounts.mint)?;
+
+    let bounty = &ctx.accounts.bounty;
+    require!(
+        bounty.status == BountyStatus::Open,
+        SolmatesError::BountyNotOpen
+    );
+    require!(
+        !bounty.is_expired(Clock::get()?.unix_timestamp),
+        SolmatesError::BountyExpired
+    );
+    require!(
+        bounty.milestones.is_empty(),
+        SolmatesError::MilestoneBounty
+    );
+    require!(
+        ctx.accounts.contributor.key() != bounty.issuer,
+        SolmatesError::Unauthorized
+    );
+
+    // Transfer from contributor to bounty vault
+    let vault_balance_before = ctx.accounts.bounty_vault.amount;
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.contributor_token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            to: ctx.accounts.bounty_vault.to_account_info(),
+            authority: ctx.accounts.contributor.to_account_info(),
+        },
+    );
+    token_interface::transfer_checked(transfer_ctx, amount, ctx.accounts.mint.decimals)?;
+
+    // Only credit what actually arrived - transfer-fee mints withhold part of the amount
+    ctx.accounts.bounty_vault.reload()?;
+    let received = ctx
+        .accounts
+        .bounty_vault
+        .amount
+        .checked_sub(vault_balance_before)
+        .unwrap();
+
+    let bounty = &mut ctx.accounts.bounty;
+    let contribution = &mut ctx.accounts.contribution;
+    if contribution.version == 0 {
+        require!(
+            (bounty.contributor_count as usize) < MAX_BOUNTY_CONTRIBUTORS,
+            SolmatesError::TooManyContributors
+        );
+        contribution.version = Contribution::VERSION;
+        contribution.bounty = bounty.key();
+        contribution.bounty_created_at = bounty.created_at;
+        contribution.contributor = ctx.accounts.contributor.key();
+        contribution.bump = ctx.bumps.contribution;
+        bounty.contributor_count = bounty.contributor_count.checked_add(1).unwrap();
+    }
+    require!(
+        contribution.version == Contribution::VERSION,
+        SolmatesError::AccountVersionMismatch
+    );
+    // Left over from an earlier bounty at the same address - close it first
+    require!(
+        contribution.bounty_created_at == bounty.created_at,
+        SolmatesError::ContributionMismatch
+    );
+
+    contribution.amount = contribution.amount.checked_add(received).unwrap();
+    bounty.contributed_amount = bounty.contributed_amount.checked_add(received).unwrap();
+    bounty.reward_amount = bounty.reward_amount.checked_add(received).unwrap();
+
+    emit!(BountyContributed {
+        issuer: bounty.issuer,
+        contributor: contribution.contributor,
+        amount: received,
+        reward_amount: bounty.reward_amount,
+    });
+
+    Ok(())
+}
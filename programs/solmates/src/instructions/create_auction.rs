@@ -1,12 +1,13 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{Mint, Token, TokenAccount},
+    token_interface::{Mint, TokenAccount, TokenInterface},
 };
 
 use crate::errors::SolmatesError;
 use crate::events::AuctionCreated;
-use crate::states::{DateAuction, UserProfile};
+use crate::states::{BidderRequirements, DateAuction, UserProfile};
+use crate::utils::validate_mint;
 
 #[derive(Accounts)]
 pub struct CreateAuction<'info> {
@@ -15,19 +16,20 @@ pub struct CreateAuction<'info> {
 
     #[account(
         mut,
-        seeds = [b"profile", host.key().as_ref()],
+        seeds = [b"profile", host_profile.identity.as_ref()],
         bump = host_profile.bump,
-        constraint = host_profile.authority == host.key() @ SolmatesError::Unauthorized
+        constraint = host_profile.authority == host.key() @ SolmatesError::Unauthorized,
+        constraint = host_profile.version == UserProfile::VERSION @ SolmatesError::AccountVersionMismatch
     )]
     pub host_profile: Account<'info, UserProfile>,
 
-    pub mint: Account<'info, Mint>,
+    pub mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         init,
         payer = host,
         space = 8 + DateAuction::INIT_SPACE,
-        seeds = [b"auction", host.key().as_ref(), host_profile.auction_count.to_le_bytes().as_ref()],
+        seeds = [b"auction", host_profile.identity.as_ref(), host_profile.auction_count.to_le_bytes().as_ref()],
         bump
     )]
     pub auction: Account<'info, DateAuction>,
@@ -36,33 +38,51 @@ pub struct CreateAuction<'info> {
         init,
         payer = host,
         associated_token::mint = mint,
-        associated_token::authority = auction
+        associated_token::authority = auction,
+        associated_token::token_program = token_program
     )]
-    pub auction_vault: Account<'info, TokenAccount>,
+    pub auction_vault: InterfaceAccount<'info, TokenAccount>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<CreateAuction>, start_price: u64, duration_secs: i64) -> Result<()> {
+pub fn handler(
+    ctx: Context<CreateAuction>,
+    start_price: u64,
+    duration_secs: i64,
+    bidder_requirements: Option<BidderRequirements>,
+) -> Result<()> {
+    validate_mint(&ctx.accounts.mint)?;
+    if let Some(requirements) = &bidder_requirements {
+        requirements.validate()?;
+    }
+    require!(
+        !ctx.accounts.host_profile.is_away(Clock::get()?.unix_timestamp),
+        SolmatesError::HostAway
+    );
+
     let profile = &mut ctx.accounts.host_profile;
     let auction = &mut ctx.accounts.auction;
 
-    auction.host = ctx.accounts.host.key();
+    auction.version = DateAuction::VERSION;
+    auction.host = profile.identity;
     auction.auction_id = profile.auction_count;
     auction.mint = ctx.accounts.mint.key();
-    auction.highest_bidder = ctx.accounts.host.key(); // Initially host
+    auction.highest_bidder = profile.identity; // Initially host
     auction.highest_bid = start_price;
     auction.end_time = Clock::get()?
         .unix_timestamp
         .checked_add(duration_secs)
         .unwrap();
     auction.total_extended = 0;  // Initialize extension tracker
+    auction.bidder_requirements = bidder_requirements;
     auction.bump = ctx.bumps.auction;
 
     // Increment auction count
     profile.auction_count = profile.auction_count.checked_add(1).unwrap();
+    profile.open_auctions = profile.open_auctions.checked_add(1).unwrap();
 
     emit!(AuctionCreated {
         host: auction.host,
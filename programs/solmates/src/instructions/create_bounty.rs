@@ -1,24 +1,40 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{self, Mint, Token, TokenAccount, Transfer},
+    token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked},
 };
 
+use crate::errors::SolmatesError;
 use crate::events::BountyCreated;
-use crate::states::{BountyStatus, BountyVault};
+use crate::states::{BountyStatus, BountyVault, Milestone, PayoutMode, UserProfile};
+use crate::utils::validate_mint;
 
+/// Opens the issuer's bounty and funds its vault.
+///
+/// Breaking: the issuer must have a profile. The bounty is seeded by the profile's
+/// `identity` and counted in its `open_bounties`, so callers that created bounties from a
+/// bare wallet must now pass `issuer_profile` and create a profile first.
 #[derive(Accounts)]
 pub struct CreateBounty<'info> {
     #[account(mut)]
     pub issuer: Signer<'info>,
 
-    pub mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [b"profile", issuer_profile.identity.as_ref()],
+        bump = issuer_profile.bump,
+        constraint = issuer_profile.authority == issuer.key() @ SolmatesError::Unauthorized,
+        constraint = issuer_profile.version == UserProfile::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub issuer_profile: Account<'info, UserProfile>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         init,
         payer = issuer,
         space = 8 + BountyVault::INIT_SPACE,
-        seeds = [b"bounty", issuer.key().as_ref()],
+        seeds = [b"bounty", issuer_profile.identity.as_ref()],
         bump
     )]
     pub bounty: Account<'info, BountyVault>,
@@ -27,45 +43,83 @@ pub struct CreateBounty<'info> {
         init,
         payer = issuer,
         associated_token::mint = mint,
-        associated_token::authority = bounty
+        associated_token::authority = bounty,
+        associated_token::token_program = token_program
     )]
-    pub bounty_vault: Account<'info, TokenAccount>,
+    pub bounty_vault: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         mut,
         associated_token::mint = mint,
-        associated_token::authority = issuer
+        associated_token::authority = issuer,
+        associated_token::token_program = token_program
     )]
-    pub issuer_token_account: Account<'info, TokenAccount>,
+    pub issuer_token_account: InterfaceAccount<'info, TokenAccount>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<CreateBounty>, reward_amount: u64) -> Result<()> {
-    let bounty = &mut ctx.accounts.bounty;
+pub fn handler(
+    ctx: Context<CreateBounty>,
+    reward_amount: u64,
+    payout_mode: PayoutMode,
+    expires_at: Option<i64>,
+    milestones: Vec<Milestone>,
+    min_matchmaker_payouts: Option<u32>,
+) -> Result<()> {
+    validate_mint(&ctx.accounts.mint)?;
 
-    bounty.issuer = ctx.accounts.issuer.key();
-    bounty.mint = ctx.accounts.mint.key();
-    bounty.reward_amount = reward_amount;
-    bounty.status = BountyStatus::Open;
-    bounty.bump = ctx.bumps.bounty;
+    let current_time = Clock::get()?.unix_timestamp;
+    if let Some(expires_at) = expires_at {
+        require!(expires_at > current_time, SolmatesError::InvalidBountyExpiry);
+    }
 
     // Transfer USDC from issuer to bounty vault
+    let vault_balance_before = ctx.accounts.bounty_vault.amount;
     let transfer_ctx = CpiContext::new(
         ctx.accounts.token_program.to_account_info(),
-        Transfer {
+        TransferChecked {
             from: ctx.accounts.issuer_token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
             to: ctx.accounts.bounty_vault.to_account_info(),
             authority: ctx.accounts.issuer.to_account_info(),
         },
     );
-    token::transfer(transfer_ctx, reward_amount)?;
+    token_interface::transfer_checked(transfer_ctx, reward_amount, ctx.accounts.mint.decimals)?;
+
+    // The reward is what actually arrived - transfer-fee mints withhold part of the amount
+    ctx.accounts.bounty_vault.reload()?;
+    let received = ctx
+        .accounts
+        .bounty_vault
+        .amount
+        .checked_sub(vault_balance_before)
+        .unwrap();
+
+    let bounty = &mut ctx.accounts.bounty;
+    bounty.version = BountyVault::VERSION;
+    bounty.issuer = ctx.accounts.issuer_profile.identity;
+    bounty.mint = ctx.accounts.mint.key();
+    bounty.reward_amount = received;
+    bounty.status = BountyStatus::Open;
+    bounty.bump = ctx.bumps.bounty;
+    bounty.created_at = current_time;
+    bounty.payout_mode = payout_mode;
+    bounty.expires_at = expires_at;
+    bounty.milestones = milestones;
+    bounty.validate_milestones()?;
+    bounty.min_matchmaker_payouts = min_matchmaker_payouts;
+
+    let issuer_profile = &mut ctx.accounts.issuer_profile;
+    issuer_profile.open_bounties = issuer_profile.open_bounties.checked_add(1).unwrap();
 
     emit!(BountyCreated {
         issuer: bounty.issuer,
-        reward_amount,
+        reward_amount: received,
+        expires_at,
+        min_matchmaker_payouts,
     });
 
     Ok(())
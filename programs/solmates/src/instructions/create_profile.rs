@@ -1,7 +1,8 @@
 use anchor_lang::prelude::*;
 
-use crate::events::ProfileCreated;
-use crate::states::UserProfile;
+use crate::events::{KeyRotated, ProfileCreated};
+use crate::states::{AccessGate, UserProfile};
+use crate::utils::validate_encryption_key;
 
 #[derive(Accounts)]
 pub struct CreateProfile<'info> {
@@ -23,21 +24,40 @@ pub struct CreateProfile<'info> {
 pub fn handler(
     ctx: Context<CreateProfile>,
     dm_price: u64,
-    asset_gate_mint: Option<Pubkey>,
-    min_asset_amount: u64,
+    gate: Option<AccessGate>,
+    encryption_key: Option<[u8; 32]>,
 ) -> Result<()> {
+    if let Some(gate) = &gate {
+        gate.validate()?;
+    }
+    if let Some(key) = &encryption_key {
+        validate_encryption_key(key)?;
+    }
+
     let profile = &mut ctx.accounts.profile;
+    profile.version = UserProfile::VERSION;
+    profile.identity = ctx.accounts.authority.key();
     profile.authority = ctx.accounts.authority.key();
     profile.dm_price = dm_price;
-    profile.asset_gate_mint = asset_gate_mint;
-    profile.min_asset_amount = min_asset_amount;
+    profile.gate = gate;
     profile.auction_count = 0;
+    profile.created_at = Clock::get()?.unix_timestamp;
     profile.bump = ctx.bumps.profile;
+    profile.encryption_key = encryption_key;
+    profile.encryption_key_version = u32::from(encryption_key.is_some());
 
     emit!(ProfileCreated {
         authority: profile.authority,
         dm_price,
     });
 
+    if encryption_key.is_some() {
+        emit!(KeyRotated {
+            profile: profile.key(),
+            encryption_key,
+            key_version: profile.encryption_key_version,
+        });
+    }
+
     Ok(())
 }
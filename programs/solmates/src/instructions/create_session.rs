@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::SolmatesError;
+use crate::events::SessionCreated;
+use crate::states::{SessionDelegate, UserProfile};
+use crate::MAX_SESSION_DURATION;
+
+/// Registers a delegate key that may accept/decline DMs, bid or update the profile on the
+/// authority's behalf until it expires. Re-registering the same delegate replaces its
+/// scopes and expiry.
+#[derive(Accounts)]
+#[instruction(delegate: Pubkey)]
+pub struct CreateSession<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"profile", profile.identity.as_ref()],
+        bump = profile.bump,
+        has_one = authority,
+        constraint = profile.version == UserProfile::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub profile: Account<'info, UserProfile>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + SessionDelegate::INIT_SPACE,
+        seeds = [b"session", authority.key().as_ref(), delegate.as_ref()],
+        bump
+    )]
+    pub session: Account<'info, SessionDelegate>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<CreateSession>,
+    delegate: Pubkey,
+    scopes: u8,
+    duration_secs: i64,
+) -> Result<()> {
+    require!(
+        scopes != 0 && scopes & !SessionDelegate::ALL_SCOPES == 0,
+        SolmatesError::InvalidSessionScope
+    );
+    require!(
+        duration_secs > 0 && duration_secs <= MAX_SESSION_DURATION,
+        SolmatesError::InvalidSessionDuration
+    );
+    require_keys_neq!(
+        delegate,
+        ctx.accounts.authority.key(),
+        SolmatesError::InvalidSessionDelegate
+    );
+
+    let session = &mut ctx.accounts.session;
+    session.version = SessionDelegate::VERSION;
+    session.authority = ctx.accounts.authority.key();
+    session.delegate = delegate;
+    session.scopes = scopes;
+    session.expires_at = Clock::get()?
+        .unix_timestamp
+        .checked_add(duration_secs)
+        .unwrap();
+    session.bump = ctx.bumps.session;
+
+    emit!(SessionCreated {
+        authority: session.authority,
+        delegate,
+        scopes,
+        expires_at: session.expires_at,
+    });
+
+    Ok(())
+}
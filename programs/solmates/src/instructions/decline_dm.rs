@@ -1,8 +1,12 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 
+use crate::errors::SolmatesError;
 use crate::events::EscrowRefunded;
-use crate::states::{EscrowStatus, MessageEscrow};
+use crate::states::{
+    EscrowStatus, Inbox, InboxPage, MessageEscrow, SessionDelegate, UserProfile,
+};
+use crate::utils::{authorize_session, unindex_escrow};
 
 /// Allows the recipient to decline a pending DM escrow, triggering an immediate
 /// refund to the sender without waiting for the 48-hour expiry period.
@@ -12,11 +16,30 @@ pub struct DeclineDm<'info> {
     #[account(mut)]
     pub sender: UncheckedAccount<'info>,
 
-    /// Recipient must sign to decline
-    #[account(mut)]
-    pub recipient: Signer<'info>,
+    /// CHECK: Recipient profile identity - validated against the escrow
+    pub recipient: UncheckedAccount<'info>,
+
+    /// Recipient profile's current authority, or a session delegate acting for them
+    pub signer: Signer<'info>,
+
+    /// Optional: session the recipient's authority granted to `signer`, when `signer` is
+    /// a delegate
+    #[account(
+        seeds = [b"session", recipient_profile.authority.as_ref(), signer.key().as_ref()],
+        bump = session.bump,
+        constraint = session.version == SessionDelegate::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub session: Option<Account<'info, SessionDelegate>>,
 
-    pub mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [b"profile", recipient.key().as_ref()],
+        bump = recipient_profile.bump,
+        constraint = recipient_profile.version == UserProfile::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub recipient_profile: Account<'info, UserProfile>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         mut,
@@ -25,28 +48,60 @@ pub struct DeclineDm<'info> {
         has_one = sender,
         has_one = recipient,
         has_one = mint,
-        close = sender
+        close = sender,
+        constraint = escrow.version == MessageEscrow::VERSION @ SolmatesError::AccountVersionMismatch
     )]
     pub escrow: Account<'info, MessageEscrow>,
 
+    /// Recipient's inbox - required when the escrow was indexed on deposit
+    #[account(
+        mut,
+        seeds = [b"inbox", recipient.key().as_ref()],
+        bump = inbox.bump,
+        constraint = inbox.version == Inbox::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub inbox: Option<Account<'info, Inbox>>,
+
+    /// Inbox page indexing the escrow - required when the escrow was indexed on deposit
+    #[account(
+        mut,
+        seeds = [
+            b"inbox_page",
+            recipient.key().as_ref(),
+            escrow.inbox_page.unwrap_or_default().to_le_bytes().as_ref()
+        ],
+        bump = inbox_page.bump,
+        constraint = inbox_page.version == InboxPage::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub inbox_page: Option<Account<'info, InboxPage>>,
+
     #[account(
         mut,
         associated_token::mint = mint,
-        associated_token::authority = escrow
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program
     )]
-    pub escrow_vault: Account<'info, TokenAccount>,
+    pub escrow_vault: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         mut,
         associated_token::mint = mint,
-        associated_token::authority = sender
+        associated_token::authority = sender,
+        associated_token::token_program = token_program
     )]
-    pub sender_token_account: Account<'info, TokenAccount>,
+    pub sender_token_account: InterfaceAccount<'info, TokenAccount>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 pub fn handler(ctx: Context<DeclineDm>) -> Result<()> {
+    authorize_session(
+        &ctx.accounts.recipient_profile.authority,
+        &ctx.accounts.signer.key(),
+        ctx.accounts.session.as_deref(),
+        SessionDelegate::SCOPE_DECLINE_DM,
+    )?;
+
     let escrow = &ctx.accounts.escrow;
 
     require!(
@@ -67,14 +122,28 @@ pub fn handler(ctx: Context<DeclineDm>) -> Result<()> {
 
     let transfer_ctx = CpiContext::new_with_signer(
         ctx.accounts.token_program.to_account_info(),
-        Transfer {
+        TransferChecked {
             from: ctx.accounts.escrow_vault.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
             to: ctx.accounts.sender_token_account.to_account_info(),
             authority: ctx.accounts.escrow.to_account_info(),
         },
         signer_seeds,
     );
-    token::transfer(transfer_ctx, escrow.amount)?;
+    token_interface::transfer_checked(transfer_ctx, escrow.amount, ctx.accounts.mint.decimals)?;
+
+    unindex_escrow(
+        &ctx.accounts.escrow.key(),
+        &ctx.accounts.escrow,
+        ctx.accounts.inbox.as_deref_mut(),
+        ctx.accounts.inbox_page.as_deref_mut(),
+    )?;
+
+    // Escrow is settled - release the recipient's pending obligation
+    let recipient_profile = &mut ctx.accounts.recipient_profile;
+    recipient_profile.pending_escrows = recipient_profile.pending_escrows.saturating_sub(1);
+    recipient_profile.reputation.dms_declined =
+        recipient_profile.reputation.dms_declined.saturating_add(1);
 
     emit!(EscrowRefunded {
         sender: escrow.sender,
@@ -1,12 +1,13 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{self, Mint, Token, TokenAccount, Transfer},
+    token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked},
 };
 
 use crate::errors::SolmatesError;
 use crate::events::EscrowCreated;
-use crate::states::{EscrowStatus, MessageEscrow, UserProfile};
+use crate::states::{EscrowStatus, Inbox, InboxPage, MessageEscrow, UserProfile};
+use crate::utils::{validate_mint, verify_gate};
 use crate::ESCROW_DURATION;
 
 #[derive(Accounts)]
@@ -18,22 +19,22 @@ pub struct DepositForDm<'info> {
     pub recipient: UncheckedAccount<'info>,
 
     #[account(
+        mut,
         seeds = [b"profile", recipient.key().as_ref()],
-        bump = recipient_profile.bump
+        bump = recipient_profile.bump,
+        constraint = recipient_profile.version == UserProfile::VERSION @ SolmatesError::AccountVersionMismatch
     )]
     pub recipient_profile: Account<'info, UserProfile>,
 
-    pub mint: Account<'info, Mint>,
+    pub mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         mut,
         associated_token::mint = mint,
-        associated_token::authority = sender
+        associated_token::authority = sender,
+        associated_token::token_program = token_program
     )]
-    pub sender_token_account: Account<'info, TokenAccount>,
-
-    /// Optional: sender's token account for the asset gate mint (if required)
-    pub sender_gate_token_account: Option<Account<'info, TokenAccount>>,
+    pub sender_token_account: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         init,
@@ -48,70 +49,121 @@ pub struct DepositForDm<'info> {
         init,
         payer = sender,
         associated_token::mint = mint,
-        associated_token::authority = escrow
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program
+    )]
+    pub escrow_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = sender,
+        space = 8 + Inbox::INIT_SPACE,
+        seeds = [b"inbox", recipient.key().as_ref()],
+        bump
+    )]
+    pub inbox: Account<'info, Inbox>,
+
+    /// The inbox's current insert page - created on first use
+    #[account(
+        init_if_needed,
+        payer = sender,
+        space = 8 + InboxPage::INIT_SPACE,
+        seeds = [b"inbox_page", recipient.key().as_ref(), inbox.insert_page.to_le_bytes().as_ref()],
+        bump
     )]
-    pub escrow_vault: Account<'info, TokenAccount>,
+    pub inbox_page: Account<'info, InboxPage>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
 pub fn handler(ctx: Context<DepositForDm>, amount: u64) -> Result<()> {
-    let recipient_profile = &ctx.accounts.recipient_profile;
+    validate_mint(&ctx.accounts.mint)?;
 
-    // Check amount meets recipient's dm_price
+    let current_time = Clock::get()?.unix_timestamp;
+    let recipient_profile = &ctx.accounts.recipient_profile;
     require!(
-        amount >= recipient_profile.dm_price,
-        SolmatesError::InsufficientDmDeposit
+        !recipient_profile.is_away(current_time),
+        SolmatesError::RecipientAway
     );
 
-    // Asset gate check - if recipient has set a gate, verify sender holds the required token
-    if let Some(gate_mint) = recipient_profile.asset_gate_mint {
-        let sender_gate_ata = ctx
-            .accounts
-            .sender_gate_token_account
-            .as_ref()
-            .ok_or(SolmatesError::AssetGateRequired)?;
-
-        require!(
-            sender_gate_ata.mint == gate_mint,
-            SolmatesError::InvalidAssetGate
-        );
-        require!(
-            sender_gate_ata.amount >= recipient_profile.min_asset_amount,
-            SolmatesError::InsufficientAssetBalance
-        );
+    // Asset gate check - sender proves their holdings with token accounts passed as
+    // remaining accounts
+    if let Some(gate) = &recipient_profile.gate {
+        verify_gate(gate, &ctx.accounts.sender.key(), ctx.remaining_accounts)?;
     }
 
+    // Transfer USDC from sender to escrow vault
+    let vault_balance_before = ctx.accounts.escrow_vault.amount;
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.sender_token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            to: ctx.accounts.escrow_vault.to_account_info(),
+            authority: ctx.accounts.sender.to_account_info(),
+        },
+    );
+    token_interface::transfer_checked(transfer_ctx, amount, ctx.accounts.mint.decimals)?;
+
+    // Escrow only what actually arrived - transfer-fee mints withhold part of the amount
+    ctx.accounts.escrow_vault.reload()?;
+    let received = ctx
+        .accounts
+        .escrow_vault
+        .amount
+        .checked_sub(vault_balance_before)
+        .unwrap();
+
+    // Check amount meets recipient's current DM price
+    require!(
+        received >= ctx.accounts.recipient_profile.dm_price_at(current_time),
+        SolmatesError::InsufficientDmDeposit
+    );
+
     // Initialize escrow
     let escrow = &mut ctx.accounts.escrow;
+    escrow.version = MessageEscrow::VERSION;
     escrow.sender = ctx.accounts.sender.key();
     escrow.recipient = ctx.accounts.recipient.key();
     escrow.mint = ctx.accounts.mint.key();
-    escrow.amount = amount;
-    escrow.expiry = Clock::get()?
-        .unix_timestamp
-        .checked_add(ESCROW_DURATION)
-        .unwrap();
+    escrow.amount = received;
+    escrow.expiry = current_time.checked_add(ESCROW_DURATION).unwrap();
     escrow.status = EscrowStatus::Pending;
     escrow.bump = ctx.bumps.escrow;
 
-    // Transfer USDC from sender to escrow vault
-    let transfer_ctx = CpiContext::new(
-        ctx.accounts.token_program.to_account_info(),
-        Transfer {
-            from: ctx.accounts.sender_token_account.to_account_info(),
-            to: ctx.accounts.escrow_vault.to_account_info(),
-            authority: ctx.accounts.sender.to_account_info(),
-        },
+    // Index the escrow in the recipient's inbox, enforcing their cap
+    let inbox = &mut ctx.accounts.inbox;
+    if inbox.version == 0 {
+        inbox.version = Inbox::VERSION;
+        inbox.recipient = escrow.recipient;
+        inbox.bump = ctx.bumps.inbox;
+    }
+    require!(
+        inbox.version == Inbox::VERSION,
+        SolmatesError::AccountVersionMismatch
     );
-    token::transfer(transfer_ctx, amount)?;
+    let inbox_page = &mut ctx.accounts.inbox_page;
+    if inbox_page.version == 0 {
+        inbox_page.version = InboxPage::VERSION;
+        inbox_page.inbox = inbox.key();
+        inbox_page.index = inbox.insert_page;
+        inbox_page.bump = ctx.bumps.inbox_page;
+    }
+    require!(
+        inbox_page.version == InboxPage::VERSION,
+        SolmatesError::AccountVersionMismatch
+    );
+    escrow.inbox_page = Some(inbox.insert(inbox_page, escrow.key())?);
+
+    let recipient_profile = &mut ctx.accounts.recipient_profile;
+    recipient_profile.pending_escrows = recipient_profile.pending_escrows.checked_add(1).unwrap();
 
     emit!(EscrowCreated {
         sender: escrow.sender,
         recipient: escrow.recipient,
-        amount,
+        amount: received,
         expiry: escrow.expiry,
     });
 
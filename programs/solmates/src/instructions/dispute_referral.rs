@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::SolmatesError;
+use crate::events::ReferralDisputed;
+use crate::states::{BountyVault, Submission, SubmissionStatus, UserProfile};
+use crate::{CONSENT_TIMEOUT, DISPUTE_WINDOW};
+
+/// The issuer or the candidate objects to a claimed match before the objection period
+/// ends. The reward then only releases if both confirm within the dispute window.
+#[derive(Accounts)]
+pub struct DisputeReferral<'info> {
+    /// Issuer's authority or the candidate
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [b"profile", bounty.issuer.as_ref()],
+        bump = issuer_profile.bump,
+        constraint = issuer_profile.version == UserProfile::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub issuer_profile: Account<'info, UserProfile>,
+
+    #[account(
+        seeds = [b"bounty", bounty.issuer.as_ref()],
+        bump = bounty.bump,
+        constraint = bounty.version == BountyVault::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub bounty: Account<'info, BountyVault>,
+
+    #[account(
+        mut,
+        seeds = [b"submission", bounty.key().as_ref(), submission.matchmaker.as_ref()],
+        bump = submission.bump,
+        constraint = bounty.active_claim == Some(submission.key()) @ SolmatesError::ReferralNotClaimed,
+        constraint = submission.version == Submission::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub submission: Account<'info, Submission>,
+}
+
+pub fn handler(ctx: Context<DisputeReferral>) -> Result<()> {
+    let submission = &mut ctx.accounts.submission;
+    require!(
+        submission.status == SubmissionStatus::Claimed,
+        SolmatesError::ReferralNotClaimed
+    );
+
+    let current_time = Clock::get()?.unix_timestamp;
+    require!(
+        current_time < submission.claimed_at.saturating_add(CONSENT_TIMEOUT),
+        SolmatesError::ObjectionPeriodOver
+    );
+
+    let signer = ctx.accounts.signer.key();
+    let is_issuer = signer == ctx.accounts.issuer_profile.authority;
+    let is_candidate = signer == submission.candidate;
+    require!(is_issuer || is_candidate, SolmatesError::Unauthorized);
+
+    if is_issuer {
+        submission.issuer_confirmed = false;
+    }
+    if is_candidate {
+        submission.candidate_confirmed = false;
+    }
+    submission.status = SubmissionStatus::Disputed;
+    submission.disputed_at = current_time;
+
+    emit!(ReferralDisputed {
+        bounty: submission.bounty,
+        disputer: signer,
+        dispute_ends_at: current_time.checked_add(DISPUTE_WINDOW).unwrap(),
+    });
+
+    Ok(())
+}
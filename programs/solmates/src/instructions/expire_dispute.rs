@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::SolmatesError;
+use crate::events::ReferralDisputeExpired;
+use crate::states::{BountyVault, Submission, SubmissionStatus};
+use crate::DISPUTE_WINDOW;
+
+/// Permissionless: ends a dispute nobody settled within the window. The claim is
+/// rejected and the bounty reopens for other submissions or a cancel.
+#[derive(Accounts)]
+pub struct ExpireDispute<'info> {
+    #[account(
+        mut,
+        seeds = [b"bounty", bounty.issuer.as_ref()],
+        bump = bounty.bump,
+        constraint = bounty.version == BountyVault::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub bounty: Account<'info, BountyVault>,
+
+    #[account(
+        mut,
+        seeds = [b"submission", bounty.key().as_ref(), submission.matchmaker.as_ref()],
+        bump = submission.bump,
+        constraint = bounty.active_claim == Some(submission.key()) @ SolmatesError::ReferralNotClaimed,
+        constraint = submission.version == Submission::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub submission: Account<'info, Submission>,
+}
+
+pub fn handler(ctx: Context<ExpireDispute>) -> Result<()> {
+    let submission = &mut ctx.accounts.submission;
+    require!(
+        submission.status == SubmissionStatus::Disputed,
+        SolmatesError::ReferralNotClaimed
+    );
+    // Both parties came around - the claim can be released instead
+    require!(
+        !(submission.issuer_confirmed && submission.candidate_confirmed),
+        SolmatesError::DisputeWindowActive
+    );
+    require!(
+        Clock::get()?.unix_timestamp >= submission.disputed_at.saturating_add(DISPUTE_WINDOW),
+        SolmatesError::DisputeWindowActive
+    );
+
+    submission.status = SubmissionStatus::Rejected;
+    ctx.accounts.bounty.active_claim = None;
+
+    emit!(ReferralDisputeExpired {
+        bounty: submission.bounty,
+        matchmaker: submission.matchmaker,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::SolmatesError;
+use crate::events::RecoveryInitiated;
+use crate::states::{GuardianSet, RecoveryRequest, UserProfile};
+use crate::RECOVERY_TIMELOCK;
+
+/// A guardian proposes moving the profile to `new_authority`. The proposal counts as
+/// the guardian's approval and starts the timelock.
+#[derive(Accounts)]
+pub struct InitiateRecovery<'info> {
+    #[account(mut)]
+    pub guardian: Signer<'info>,
+
+    #[account(
+        seeds = [b"profile", profile.identity.as_ref()],
+        bump = profile.bump,
+        constraint = profile.version == UserProfile::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub profile: Account<'info, UserProfile>,
+
+    #[account(
+        seeds = [b"guardians", profile.key().as_ref()],
+        bump = guardian_set.bump,
+        constraint = guardian_set.is_guardian(&guardian.key()) @ SolmatesError::NotAGuardian,
+        constraint = guardian_set.version == GuardianSet::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub guardian_set: Account<'info, GuardianSet>,
+
+    #[account(
+        init,
+        payer = guardian,
+        space = 8 + RecoveryRequest::INIT_SPACE,
+        seeds = [b"recovery", profile.key().as_ref()],
+        bump
+    )]
+    pub recovery: Account<'info, RecoveryRequest>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitiateRecovery>, new_authority: Pubkey) -> Result<()> {
+    require_keys_neq!(
+        new_authority,
+        ctx.accounts.profile.authority,
+        SolmatesError::InvalidNewAuthority
+    );
+
+    let initiated_at = Clock::get()?.unix_timestamp;
+
+    let recovery = &mut ctx.accounts.recovery;
+    recovery.version = RecoveryRequest::VERSION;
+    recovery.profile = ctx.accounts.profile.key();
+    recovery.new_authority = new_authority;
+    recovery.proposer = ctx.accounts.guardian.key();
+    recovery.approvals = vec![ctx.accounts.guardian.key()];
+    recovery.initiated_at = initiated_at;
+    recovery.bump = ctx.bumps.recovery;
+
+    emit!(RecoveryInitiated {
+        profile: recovery.profile,
+        proposer: recovery.proposer,
+        new_authority,
+        executable_at: initiated_at.checked_add(RECOVERY_TIMELOCK).unwrap(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::SolmatesError;
+use crate::events::AttestationIssued;
+use crate::states::{Attestation, ClaimType, UserProfile, Verifier};
+
+/// A registered verifier attests that a profile passed a check. Issuing again renews
+/// the attestation and lifts any revocation.
+#[derive(Accounts)]
+#[instruction(claim_type: ClaimType)]
+pub struct IssueAttestation<'info> {
+    #[account(mut)]
+    pub verifier: Signer<'info>,
+
+    #[account(
+        seeds = [b"verifier", verifier.key().as_ref()],
+        bump = verifier_record.bump,
+        constraint = verifier_record.version == Verifier::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub verifier_record: Account<'info, Verifier>,
+
+    #[account(
+        seeds = [b"profile", profile.identity.as_ref()],
+        bump = profile.bump,
+        constraint = profile.version == UserProfile::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub profile: Account<'info, UserProfile>,
+
+    #[account(
+        init_if_needed,
+        payer = verifier,
+        space = 8 + Attestation::INIT_SPACE,
+        seeds = [
+            b"attestation",
+            profile.key().as_ref(),
+            verifier.key().as_ref(),
+            [claim_type as u8].as_ref()
+        ],
+        bump
+    )]
+    pub attestation: Account<'info, Attestation>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<IssueAttestation>,
+    claim_type: ClaimType,
+    expires_at: i64,
+) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+    require!(
+        expires_at == 0 || expires_at > current_time,
+        SolmatesError::InvalidAttestationExpiry
+    );
+
+    let attestation = &mut ctx.accounts.attestation;
+    attestation.version = Attestation::VERSION;
+    attestation.profile = ctx.accounts.profile.key();
+    attestation.verifier = ctx.accounts.verifier.key();
+    attestation.claim_type = claim_type;
+    attestation.issued_at = current_time;
+    attestation.expires_at = expires_at;
+    attestation.revoked = false;
+    attestation.bump = ctx.bumps.attestation;
+
+    emit!(AttestationIssued {
+        profile: attestation.profile,
+        verifier: attestation.verifier,
+        claim_type,
+        expires_at,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,97 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::SolmatesError;
+use crate::events::{MatchCreated, ProfileLiked};
+use crate::states::{Like, Match, UserProfile};
+
+/// Liker records a like of another profile. When the likee already liked them back, the
+/// `Match` is created in the same instruction.
+#[derive(Accounts)]
+pub struct LikeProfile<'info> {
+    #[account(mut)]
+    pub liker: Signer<'info>,
+
+    #[account(
+        seeds = [b"profile", liker_profile.identity.as_ref()],
+        bump = liker_profile.bump,
+        constraint = liker_profile.authority == liker.key() @ SolmatesError::Unauthorized,
+        constraint = liker_profile.version == UserProfile::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub liker_profile: Account<'info, UserProfile>,
+
+    #[account(
+        seeds = [b"profile", likee_profile.identity.as_ref()],
+        bump = likee_profile.bump,
+        constraint = likee_profile.version == UserProfile::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub likee_profile: Account<'info, UserProfile>,
+
+    #[account(
+        init,
+        payer = liker,
+        space = 8 + Like::INIT_SPACE,
+        seeds = [b"like", liker_profile.identity.as_ref(), likee_profile.identity.as_ref()],
+        bump
+    )]
+    pub like: Account<'info, Like>,
+
+    /// CHECK: The likee's like of the liker, if any - only its existence is read
+    #[account(
+        seeds = [b"like", likee_profile.identity.as_ref(), liker_profile.identity.as_ref()],
+        bump
+    )]
+    pub reverse_like: UncheckedAccount<'info>,
+
+    /// Optional: the pair's match - required when `reverse_like` exists
+    #[account(
+        init,
+        payer = liker,
+        space = 8 + Match::INIT_SPACE,
+        seeds = [
+            b"match",
+            Match::pair(liker_profile.identity, likee_profile.identity).0.as_ref(),
+            Match::pair(liker_profile.identity, likee_profile.identity).1.as_ref()
+        ],
+        bump
+    )]
+    pub pair_match: Option<Account<'info, Match>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<LikeProfile>) -> Result<()> {
+    let liker = ctx.accounts.liker_profile.identity;
+    let likee = ctx.accounts.likee_profile.identity;
+    require_keys_neq!(liker, likee, SolmatesError::InvalidLike);
+
+    let current_time = Clock::get()?.unix_timestamp;
+    let like = &mut ctx.accounts.like;
+    like.version = Like::VERSION;
+    like.liker = liker;
+    like.likee = likee;
+    like.liked_at = current_time;
+    like.bump = ctx.bumps.like;
+
+    emit!(ProfileLiked { liker, likee });
+
+    // The second like of the pair completes the match
+    let reverse_like = &ctx.accounts.reverse_like;
+    let liked_back = reverse_like.owner == &crate::ID && !reverse_like.data_is_empty();
+    match ctx.accounts.pair_match.as_mut() {
+        Some(pair_match) => {
+            require!(liked_back, SolmatesError::NotLikedBack);
+            let (user_a, user_b) = Match::pair(liker, likee);
+            pair_match.version = Match::VERSION;
+            pair_match.user_a = user_a;
+            pair_match.user_b = user_b;
+            pair_match.matched_at = current_time;
+            pair_match.payer = ctx.accounts.liker.key();
+            pair_match.bump = ctx.bumps.pair_match.unwrap();
+
+            emit!(MatchCreated { user_a, user_b });
+        }
+        None => require!(!liked_back, SolmatesError::MatchAccountRequired),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+
+use crate::events::AccountMigrated;
+use crate::states::{DateAuction, DateAuctionV0};
+use crate::utils::{load_legacy, write_migrated};
+
+/// Upgrades a pre-versioning date auction to the current layout in place. Anyone may
+/// call it; the caller pays the extra rent.
+#[derive(Accounts)]
+pub struct MigrateAuction<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Legacy auction - discriminator and layout are verified in the handler
+    #[account(mut, owner = crate::ID)]
+    pub auction: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<MigrateAuction>) -> Result<()> {
+    let auction_info = ctx.accounts.auction.to_account_info();
+    let legacy: DateAuctionV0 =
+        load_legacy::<DateAuction, _>(&auction_info, 8 + DateAuctionV0::INIT_SPACE)?;
+
+    // Legacy auctions were open to every bidder
+    let auction = DateAuction {
+        version: DateAuction::VERSION,
+        host: legacy.host,
+        auction_id: legacy.auction_id,
+        mint: legacy.mint,
+        highest_bidder: legacy.highest_bidder,
+        highest_bid: legacy.highest_bid,
+        end_time: legacy.end_time,
+        total_extended: legacy.total_extended,
+        bidder_requirements: None,
+        bump: legacy.bump,
+        reserved: [0; 64],
+    };
+
+    write_migrated(
+        &auction_info,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        8 + DateAuction::INIT_SPACE,
+        &auction,
+    )?;
+
+    emit!(AccountMigrated {
+        account: auction_info.key(),
+        version: DateAuction::VERSION,
+    });
+
+    Ok(())
+}
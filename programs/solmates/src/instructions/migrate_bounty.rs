@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+
+use crate::events::AccountMigrated;
+use crate::states::{BountyVault, BountyVaultV0, PayoutMode};
+use crate::utils::{load_legacy, write_migrated};
+
+/// Upgrades a pre-versioning bounty to the current layout in place. Anyone may call it;
+/// the caller pays the extra rent.
+#[derive(Accounts)]
+pub struct MigrateBounty<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Legacy bounty - discriminator and layout are verified in the handler
+    #[account(mut, owner = crate::ID)]
+    pub bounty: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<MigrateBounty>) -> Result<()> {
+    let bounty_info = ctx.accounts.bounty.to_account_info();
+    let legacy: BountyVaultV0 =
+        load_legacy::<BountyVault, _>(&bounty_info, 8 + BountyVaultV0::INIT_SPACE)?;
+
+    let bounty = BountyVault {
+        version: BountyVault::VERSION,
+        issuer: legacy.issuer,
+        mint: legacy.mint,
+        reward_amount: legacy.reward_amount,
+        status: legacy.status,
+        bump: legacy.bump,
+        created_at: 0,
+        payout_mode: PayoutMode::IssuerOnly,
+        active_claim: None,
+        expires_at: None,
+        contributed_amount: 0,
+        contributor_count: 0,
+        milestones: Vec::new(),
+        min_matchmaker_payouts: None,
+        reserved: [0; 5],
+    };
+
+    write_migrated(
+        &bounty_info,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        8 + BountyVault::INIT_SPACE,
+        &bounty,
+    )?;
+
+    emit!(AccountMigrated {
+        account: bounty_info.key(),
+        version: BountyVault::VERSION,
+    });
+
+    Ok(())
+}
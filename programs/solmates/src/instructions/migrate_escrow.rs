@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::events::AccountMigrated;
+use crate::states::{MessageEscrow, MessageEscrowV0};
+use crate::utils::{load_legacy, write_migrated};
+
+/// Upgrades a pre-versioning DM escrow to the current layout in place. Anyone may call
+/// it; the caller pays the extra rent.
+#[derive(Accounts)]
+pub struct MigrateEscrow<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Legacy escrow - discriminator and layout are verified in the handler
+    #[account(mut, owner = crate::ID)]
+    pub escrow: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<MigrateEscrow>) -> Result<()> {
+    let escrow_info = ctx.accounts.escrow.to_account_info();
+    let legacy: MessageEscrowV0 =
+        load_legacy::<MessageEscrow, _>(&escrow_info, 8 + MessageEscrowV0::INIT_SPACE)?;
+
+    let escrow = MessageEscrow {
+        version: MessageEscrow::VERSION,
+        sender: legacy.sender,
+        recipient: legacy.recipient,
+        mint: legacy.mint,
+        amount: legacy.amount,
+        expiry: legacy.expiry,
+        status: legacy.status,
+        bump: legacy.bump,
+        inbox_page: None,
+        reserved: [0; 59],
+    };
+
+    write_migrated(
+        &escrow_info,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        8 + MessageEscrow::INIT_SPACE,
+        &escrow,
+    )?;
+
+    emit!(AccountMigrated {
+        account: escrow_info.key(),
+        version: MessageEscrow::VERSION,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,273 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::SolmatesError;
+use crate::events::AccountMigrated;
+use crate::states::{
+    AccessGate, Availability, EscrowStatus, GateMode, GateRule, MessageEscrow, MessageEscrowV0,
+    Reputation, UserProfile, UserProfileV0, UserProfileV1, UserProfileV2,
+};
+use crate::utils::{load_legacy, write_migrated};
+
+/// Upgrades a pre-versioning or older-version profile to the current layout in place.
+/// Anyone may call it; the caller pays any extra rent.
+///
+/// Pre-versioning profiles never counted their obligations, so for those the remaining
+/// accounts must list them: the profile's bounty PDA, its auction PDAs for every id below
+/// `auction_count` (closed ones included), then any pending DM escrows sent to it.
+#[derive(Accounts)]
+pub struct MigrateProfile<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Outdated profile - discriminator and layout are verified in the handler
+    #[account(mut, owner = crate::ID)]
+    pub profile: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<MigrateProfile>) -> Result<()> {
+    let profile_info = ctx.accounts.profile.to_account_info();
+    let profile = if profile_info.data_len() == 8 + UserProfileV0::INIT_SPACE {
+        from_v0(
+            load_legacy::<UserProfile, _>(&profile_info, 8 + UserProfileV0::INIT_SPACE)?,
+            ctx.remaining_accounts,
+        )?
+    } else {
+        // Versions 1 and 2 share a length - the version byte picks the layout
+        let version = profile_info
+            .try_borrow_data()?
+            .get(8)
+            .copied()
+            .ok_or(SolmatesError::InvalidLegacyAccount)?;
+        match version {
+            1 => from_v1(load_legacy::<UserProfile, _>(
+                &profile_info,
+                8 + UserProfileV1::INIT_SPACE,
+            )?),
+            2 => from_v2(load_legacy::<UserProfile, _>(
+                &profile_info,
+                8 + UserProfileV2::INIT_SPACE,
+            )?),
+            _ => return err!(SolmatesError::AccountAlreadyMigrated),
+        }
+    };
+
+    write_migrated(
+        &profile_info,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        8 + UserProfile::INIT_SPACE,
+        &profile,
+    )?;
+
+    emit!(AccountMigrated {
+        account: profile_info.key(),
+        version: UserProfile::VERSION,
+    });
+
+    Ok(())
+}
+
+fn from_v1(legacy: UserProfileV1) -> UserProfile {
+    // Version 1 had no separate identity - the authority could not have rotated yet
+    UserProfile {
+        version: UserProfile::VERSION,
+        identity: legacy.authority,
+        authority: legacy.authority,
+        dm_price: legacy.dm_price,
+        gate: legacy.gate,
+        auction_count: legacy.auction_count,
+        created_at: legacy.created_at,
+        open_auctions: legacy.open_auctions,
+        open_bounties: legacy.open_bounties,
+        pending_escrows: legacy.pending_escrows,
+        bump: legacy.bump,
+        handle: None,
+        reputation: Reputation::default(),
+        encryption_key: None,
+        encryption_key_version: 0,
+        availability: Availability::Available,
+        pricing: None,
+        recent_accepts: 0,
+        recent_accepts_since: 0,
+        reserved: [0; 80],
+    }
+}
+
+fn from_v2(legacy: UserProfileV2) -> UserProfile {
+    UserProfile {
+        version: UserProfile::VERSION,
+        identity: legacy.identity,
+        authority: legacy.authority,
+        dm_price: legacy.dm_price,
+        gate: legacy.gate,
+        auction_count: legacy.auction_count,
+        created_at: legacy.created_at,
+        open_auctions: legacy.open_auctions,
+        open_bounties: legacy.open_bounties,
+        pending_escrows: legacy.pending_escrows,
+        bump: legacy.bump,
+        handle: legacy.handle,
+        reputation: legacy.reputation,
+        encryption_key: None,
+        encryption_key_version: 0,
+        availability: Availability::Available,
+        pricing: None,
+        recent_accepts: 0,
+        recent_accepts_since: 0,
+        reserved: [0; 80],
+    }
+}
+
+fn from_v0(legacy: UserProfileV0, obligations: &[AccountInfo]) -> Result<UserProfile> {
+    let gate = gate_from_v0(legacy.asset_gate_mint, legacy.min_asset_amount);
+
+    let (open_auctions, open_bounties, pending_escrows) =
+        count_v0_obligations(&legacy, obligations)?;
+
+    // Creation time was never recorded - 0 marks the profile as older than any versioned
+    // one, which it is
+    let profile = UserProfile {
+        version: UserProfile::VERSION,
+        identity: legacy.authority,
+        authority: legacy.authority,
+        dm_price: legacy.dm_price,
+        gate,
+        auction_count: legacy.auction_count,
+        created_at: 0,
+        open_auctions,
+        open_bounties,
+        pending_escrows,
+        bump: legacy.bump,
+        handle: None,
+        reputation: Reputation::default(),
+        encryption_key: None,
+        encryption_key_version: 0,
+        availability: Availability::Available,
+        pricing: None,
+        recent_accepts: 0,
+        recent_accepts_since: 0,
+        reserved: [0; 80],
+    };
+
+    Ok(profile)
+}
+
+/// The single-mint gate becomes a one-rule access gate. Pre-versioning profiles could
+/// gate on the mint with no minimum, which now means holding at least one token.
+fn gate_from_v0(asset_gate_mint: Option<Pubkey>, min_asset_amount: u64) -> Option<AccessGate> {
+    asset_gate_mint.map(|mint| AccessGate {
+        mode: GateMode::AnyOf,
+        rules: vec![GateRule::Token {
+            mint,
+            min_amount: min_asset_amount.max(1),
+        }],
+    })
+}
+
+/// Counts a pre-versioning profile's live bounty, live auctions and pending escrows, so
+/// `close_profile` can't run while they are outstanding. The bounty and auction PDAs are
+/// derived here, so none can be left out; escrows can't be enumerated and are counted as
+/// passed, but their senders can always refund them without the profile.
+fn count_v0_obligations(
+    legacy: &UserProfileV0,
+    obligations: &[AccountInfo],
+) -> Result<(u64, u64, u64)> {
+    let authority = legacy.authority;
+    let auction_count = usize::try_from(legacy.auction_count).unwrap();
+    require!(
+        obligations.len() > auction_count,
+        SolmatesError::ObligationAccountsMismatch
+    );
+    let (bounty, rest) = obligations.split_first().unwrap();
+    let (auctions, escrows) = rest.split_at(auction_count);
+
+    let is_live = |account: &AccountInfo| account.owner == &crate::ID && !account.data_is_empty();
+
+    let (bounty_key, _) =
+        Pubkey::find_program_address(&[b"bounty", authority.as_ref()], &crate::ID);
+    require_keys_eq!(
+        bounty.key(),
+        bounty_key,
+        SolmatesError::ObligationAccountsMismatch
+    );
+    let open_bounties = is_live(bounty) as u64;
+
+    let mut open_auctions = 0;
+    for (auction_id, auction) in auctions.iter().enumerate() {
+        let (auction_key, _) = Pubkey::find_program_address(
+            &[
+                b"auction",
+                authority.as_ref(),
+                (auction_id as u64).to_le_bytes().as_ref(),
+            ],
+            &crate::ID,
+        );
+        require_keys_eq!(
+            auction.key(),
+            auction_key,
+            SolmatesError::ObligationAccountsMismatch
+        );
+        open_auctions += is_live(auction) as u64;
+    }
+
+    let mut counted: Vec<Pubkey> = Vec::with_capacity(escrows.len());
+    for escrow in escrows {
+        require!(
+            escrow.owner == &crate::ID && !counted.contains(escrow.key),
+            SolmatesError::ObligationAccountsMismatch
+        );
+        let (recipient, status) = if escrow.data_len() == 8 + MessageEscrowV0::INIT_SPACE {
+            let legacy_escrow: MessageEscrowV0 =
+                load_legacy::<MessageEscrow, _>(escrow, 8 + MessageEscrowV0::INIT_SPACE)?;
+            (legacy_escrow.recipient, legacy_escrow.status)
+        } else {
+            let current_escrow =
+                MessageEscrow::try_deserialize(&mut &escrow.try_borrow_data()?[..])?;
+            (current_escrow.recipient, current_escrow.status)
+        };
+        require!(
+            recipient == authority && status == EscrowStatus::Pending,
+            SolmatesError::ObligationAccountsMismatch
+        );
+        counted.push(escrow.key());
+    }
+
+    Ok((open_auctions, open_bounties, counted.len() as u64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token_rule(gate: &AccessGate) -> (Pubkey, u64) {
+        match gate.rules[..] {
+            [GateRule::Token { mint, min_amount }] => (mint, min_amount),
+            _ => panic!("expected a single token rule"),
+        }
+    }
+
+    #[test]
+    fn ungated_v0_profile_stays_ungated() {
+        assert!(gate_from_v0(None, 0).is_none());
+        assert!(gate_from_v0(None, 5).is_none());
+    }
+
+    #[test]
+    fn v0_gate_keeps_its_minimum() {
+        let mint = Pubkey::new_unique();
+        let gate = gate_from_v0(Some(mint), 3).unwrap();
+        assert!(gate.mode == GateMode::AnyOf);
+        assert_eq!(token_rule(&gate), (mint, 3));
+        assert!(gate.validate().is_ok());
+    }
+
+    #[test]
+    fn v0_gate_without_minimum_requires_one_token() {
+        let mint = Pubkey::new_unique();
+        let gate = gate_from_v0(Some(mint), 0).unwrap();
+        assert_eq!(token_rule(&gate), (mint, 1));
+        assert!(gate.validate().is_ok());
+    }
+}
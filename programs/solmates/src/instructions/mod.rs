@@ -1,30 +1,126 @@
 pub mod accept_dm;
+pub mod add_verifier;
+pub mod approve_recovery;
+pub mod book_date;
 pub mod cancel_auction;
 pub mod cancel_bounty;
+pub mod cancel_date;
+pub mod cancel_recovery;
 pub mod claim_auction;
+pub mod claim_handle;
+pub mod claim_referral;
+pub mod claim_super_like;
+pub mod close_contribution;
+pub mod close_profile;
+pub mod confirm_attendance;
+pub mod confirm_referral;
+pub mod contribute_to_bounty;
 pub mod create_auction;
 pub mod create_bounty;
 pub mod create_profile;
+pub mod create_session;
 pub mod decline_dm;
 pub mod deposit_for_dm;
+pub mod dispute_referral;
+pub mod expire_dispute;
+pub mod initiate_recovery;
+pub mod issue_attestation;
+pub mod like_profile;
+pub mod migrate_auction;
+pub mod migrate_bounty;
+pub mod migrate_escrow;
+pub mod migrate_profile;
 pub mod payout_referral;
+pub mod payout_referral_split;
 pub mod place_bid;
+pub mod quote_dm_price;
+pub mod refund_contribution;
 pub mod refund_dm;
+pub mod refund_expired_bounty;
+pub mod refund_super_like;
+pub mod register_matchmaker;
+pub mod reject_submission;
+pub mod release_handle;
+pub mod release_milestone;
+pub mod release_referral;
+pub mod remove_verifier;
+pub mod revoke_attestation;
+pub mod revoke_session;
+pub mod rotate_authority;
+pub mod set_availability;
+pub mod set_guardians;
+pub mod set_inbox_limit;
+pub mod settle_date;
+pub mod stake_date;
+pub mod submit_referral;
+pub mod super_like_profile;
+pub mod transfer_handle;
+pub mod unmatch;
 pub mod update_bounty;
+pub mod update_matchmaker;
 pub mod update_profile;
+pub mod withdraw_submission;
 
 #[allow(ambiguous_glob_reexports)]
 pub use accept_dm::*;
+pub use add_verifier::*;
+pub use approve_recovery::*;
+pub use book_date::*;
 pub use cancel_auction::*;
 pub use cancel_bounty::*;
+pub use cancel_date::*;
+pub use cancel_recovery::*;
 pub use claim_auction::*;
+pub use claim_handle::*;
+pub use claim_referral::*;
+pub use claim_super_like::*;
+pub use close_contribution::*;
+pub use close_profile::*;
+pub use confirm_attendance::*;
+pub use confirm_referral::*;
+pub use contribute_to_bounty::*;
 pub use create_auction::*;
 pub use create_bounty::*;
 pub use create_profile::*;
+pub use create_session::*;
 pub use decline_dm::*;
 pub use deposit_for_dm::*;
+pub use dispute_referral::*;
+pub use expire_dispute::*;
+pub use initiate_recovery::*;
+pub use issue_attestation::*;
+pub use like_profile::*;
+pub use migrate_auction::*;
+pub use migrate_bounty::*;
+pub use migrate_escrow::*;
+pub use migrate_profile::*;
 pub use payout_referral::*;
+pub use payout_referral_split::*;
 pub use place_bid::*;
+pub use quote_dm_price::*;
+pub use refund_contribution::*;
 pub use refund_dm::*;
+pub use refund_expired_bounty::*;
+pub use refund_super_like::*;
+pub use register_matchmaker::*;
+pub use reject_submission::*;
+pub use release_handle::*;
+pub use release_milestone::*;
+pub use release_referral::*;
+pub use remove_verifier::*;
+pub use revoke_attestation::*;
+pub use revoke_session::*;
+pub use rotate_authority::*;
+pub use set_availability::*;
+pub use set_guardians::*;
+pub use set_inbox_limit::*;
+pub use settle_date::*;
+pub use stake_date::*;
+pub use submit_referral::*;
+pub use super_like_profile::*;
+pub use transfer_handle::*;
+pub use unmatch::*;
 pub use update_bounty::*;
+pub use update_matchmaker::*;
 pub use update_profile::*;
+pub use withdraw_submission::*;
@@ -1,48 +1,92 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{self, Mint, Token, TokenAccount, Transfer},
+    token_interface::{Mint, TokenAccount, TokenInterface},
 };
 
 use crate::errors::SolmatesError;
 use crate::events::BountyPaid;
-use crate::states::{BountyStatus, BountyVault};
-use crate::{PLATFORM_FEE_BPS, TREASURY};
+use crate::states::{BountyStatus, BountyVault, PayoutMode, Submission, UserProfile};
+use crate::utils::{
+    credit_matchmaker_record, load_contributions, pay_from_bounty, update_profile_if_exists,
+};
+use crate::TREASURY;
 
 #[derive(Accounts)]
 pub struct PayoutReferral<'info> {
     #[account(mut)]
     pub issuer: Signer<'info>,
 
-    /// CHECK: Matchmaker wallet - receives payout
+    #[account(
+        mut,
+        seeds = [b"profile", bounty.issuer.as_ref()],
+        bump = issuer_profile.bump,
+        constraint = issuer_profile.authority == issuer.key() @ SolmatesError::Unauthorized,
+        constraint = issuer_profile.version == UserProfile::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub issuer_profile: Account<'info, UserProfile>,
+
+    /// CHECK: Matchmaker wallet - receives payout and the submission's rent
+    #[account(mut)]
     pub matchmaker: UncheckedAccount<'info>,
 
-    pub mint: Account<'info, Mint>,
+    /// CHECK: Matchmaker's profile PDA - always passed, and credited with the payout
+    /// whenever the matchmaker has a profile
+    #[account(
+        mut,
+        seeds = [b"profile", matchmaker.key().as_ref()],
+        bump
+    )]
+    pub matchmaker_profile: UncheckedAccount<'info>,
 
+    /// CHECK: Matchmaker's registry record PDA - always passed, and credited with the
+    /// payout when the matchmaker is registered
     #[account(
         mut,
-        seeds = [b"bounty", issuer.key().as_ref()],
+        seeds = [b"matchmaker", matchmaker.key().as_ref()],
+        bump
+    )]
+    pub matchmaker_record: UncheckedAccount<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"bounty", bounty.issuer.as_ref()],
         bump = bounty.bump,
-        has_one = issuer,
         has_one = mint,
-        close = issuer
+        close = issuer,
+        constraint = bounty.version == BountyVault::VERSION @ SolmatesError::AccountVersionMismatch
     )]
     pub bounty: Account<'info, BountyVault>,
 
+    /// The matchmaker's submission being paid - must still be pending on this bounty
+    #[account(
+        mut,
+        seeds = [b"submission", bounty.key().as_ref(), matchmaker.key().as_ref()],
+        bump = submission.bump,
+        close = matchmaker,
+        constraint = submission.is_live_for(bounty.created_at) @ SolmatesError::SubmissionNotLive,
+        constraint = submission.version == Submission::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub submission: Account<'info, Submission>,
+
     #[account(
         mut,
         associated_token::mint = mint,
-        associated_token::authority = bounty
+        associated_token::authority = bounty,
+        associated_token::token_program = token_program
     )]
-    pub bounty_vault: Account<'info, TokenAccount>,
+    pub bounty_vault: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         init_if_needed,
         payer = issuer,
         associated_token::mint = mint,
-        associated_token::authority = matchmaker
+        associated_token::authority = matchmaker,
+        associated_token::token_program = token_program
     )]
-    pub matchmaker_token_account: Account<'info, TokenAccount>,
+    pub matchmaker_token_account: InterfaceAccount<'info, TokenAccount>,
 
     /// CHECK: Platform treasury for fees - validated against constant
     #[account(
@@ -55,73 +99,83 @@ pub struct PayoutReferral<'info> {
         init_if_needed,
         payer = issuer,
         associated_token::mint = mint,
-        associated_token::authority = treasury
+        associated_token::authority = treasury,
+        associated_token::token_program = token_program
     )]
-    pub treasury_token_account: Account<'info, TokenAccount>,
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<PayoutReferral>) -> Result<()> {
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, PayoutReferral<'info>>) -> Result<()> {
     let bounty = &ctx.accounts.bounty;
 
     require!(
         bounty.status == BountyStatus::Open,
         SolmatesError::BountyNotOpen
     );
+    require!(
+        bounty.payout_mode == PayoutMode::IssuerOnly,
+        SolmatesError::ConsentRequired
+    );
+    require!(
+        bounty.milestones.is_empty(),
+        SolmatesError::MilestoneBounty
+    );
 
-    // Copy values before CPI
-    let issuer_key = bounty.issuer;
-    let bump = bounty.bump;
-    let amount = bounty.reward_amount;
-
-    // Calculate platform fee (1%)
-    let fee = amount.checked_mul(PLATFORM_FEE_BPS).unwrap().checked_div(10000).unwrap();
-    let matchmaker_amount = amount.checked_sub(fee).unwrap();
-
-    // Transfer USDC from bounty vault to matchmaker
-    let seeds = &[b"bounty", issuer_key.as_ref(), &[bump]];
-    let signer_seeds = &[&seeds[..]];
-
-    let transfer_ctx = CpiContext::new_with_signer(
-        ctx.accounts.token_program.to_account_info(),
-        Transfer {
-            from: ctx.accounts.bounty_vault.to_account_info(),
-            to: ctx.accounts.matchmaker_token_account.to_account_info(),
-            authority: ctx.accounts.bounty.to_account_info(),
-        },
-        signer_seeds,
+    // The reward may have been lowered since the matchmaker submitted
+    require!(
+        bounty.reward_amount >= ctx.accounts.submission.min_fee,
+        SolmatesError::RewardBelowMatchmakerFee
     );
-    token::transfer(transfer_ctx, matchmaker_amount)?;
-
-    // Transfer fee to treasury
-    if fee > 0 {
-        let fee_ctx = CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.bounty_vault.to_account_info(),
-                to: ctx.accounts.treasury_token_account.to_account_info(),
-                authority: ctx.accounts.bounty.to_account_info(),
-            },
-            signer_seeds,
-        );
-        token::transfer(fee_ctx, fee)?;
-    }
+
+    // Contributions passed as remaining accounts - every contributor is credited
+    let contributors = load_contributions(bounty, ctx.remaining_accounts)?;
+
+    let issuer_key = bounty.issuer;
+    let (matchmaker_amount, fee) = pay_from_bounty(
+        &ctx.accounts.bounty,
+        &ctx.accounts.bounty_vault,
+        &ctx.accounts.mint,
+        &ctx.accounts.matchmaker_token_account,
+        &ctx.accounts.treasury_token_account,
+        &ctx.accounts.token_program,
+        bounty.reward_amount,
+    )?;
 
     // Update state after CPI
     let bounty = &mut ctx.accounts.bounty;
     bounty.status = BountyStatus::Filled;
 
+    // Release the issuer's open-bounty obligation
+    let issuer_profile = &mut ctx.accounts.issuer_profile;
+    issuer_profile.open_bounties = issuer_profile.open_bounties.saturating_sub(1);
+
+    update_profile_if_exists(&ctx.accounts.matchmaker_profile, |matchmaker_profile| {
+        matchmaker_profile.reputation.bounties_paid = matchmaker_profile
+            .reputation
+            .bounties_paid
+            .saturating_add(1);
+        matchmaker_profile.reputation.bounty_earned = matchmaker_profile
+            .reputation
+            .bounty_earned
+            .saturating_add(matchmaker_amount);
+    })?;
+
+    credit_matchmaker_record(&ctx.accounts.matchmaker_record, matchmaker_amount, true)?;
+
     emit!(BountyPaid {
         issuer: issuer_key,
         matchmaker: ctx.accounts.matchmaker.key(),
+        candidate: ctx.accounts.submission.candidate,
         amount: matchmaker_amount,
         fee,
+        contributors,
     });
 
-    // Account will be closed, rent returned to issuer
+    // Accounts will be closed - bounty rent to issuer, submission rent to matchmaker
 
     Ok(())
 }
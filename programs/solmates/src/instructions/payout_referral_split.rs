@@ -0,0 +1,226 @@
+use anchor_lang::prelude::*;
+use anchor_lang::AccountsClose;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::errors::SolmatesError;
+use crate::events::BountyPaid;
+use crate::states::{BountyStatus, BountyVault, PayoutMode, Submission, UserProfile};
+use crate::utils::{
+    credit_matchmaker_record, load_contributions, pay_from_bounty, pro_rata,
+    update_profile_if_exists,
+};
+use crate::{MAX_SPLIT_RECIPIENTS, TREASURY};
+
+/// Accounts passed per split recipient, in order: matchmaker wallet, their submission,
+/// their token account for the mint, their profile PDA and their matchmaker record PDA.
+/// Both PDAs are passed whether or not the accounts exist
+pub const SPLIT_RECIPIENT_ACCOUNTS: usize = 5;
+
+/// Issuer splits the reward among several matchmakers by basis-point shares. Each
+/// needs a live submission on the bounty. Rounding dust goes to the first recipient.
+#[derive(Accounts)]
+pub struct PayoutReferralSplit<'info> {
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"profile", bounty.issuer.as_ref()],
+        bump = issuer_profile.bump,
+        constraint = issuer_profile.authority == issuer.key() @ SolmatesError::Unauthorized,
+        constraint = issuer_profile.version == UserProfile::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub issuer_profile: Account<'info, UserProfile>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"bounty", bounty.issuer.as_ref()],
+        bump = bounty.bump,
+        has_one = mint,
+        close = issuer,
+        constraint = bounty.version == BountyVault::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub bounty: Account<'info, BountyVault>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = bounty,
+        associated_token::token_program = token_program
+    )]
+    pub bounty_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Platform treasury for fees - validated against constant
+    #[account(
+        mut,
+        constraint = treasury.key() == TREASURY @ SolmatesError::InvalidTreasury
+    )]
+    pub treasury: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = issuer,
+        associated_token::mint = mint,
+        associated_token::authority = treasury,
+        associated_token::token_program = token_program
+    )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, PayoutReferralSplit<'info>>,
+    shares_bps: Vec<u16>,
+) -> Result<()> {
+    let bounty = &ctx.accounts.bounty;
+
+    require!(
+        bounty.status == BountyStatus::Open,
+        SolmatesError::BountyNotOpen
+    );
+    require!(
+        bounty.payout_mode == PayoutMode::IssuerOnly,
+        SolmatesError::ConsentRequired
+    );
+    require!(
+        bounty.milestones.is_empty(),
+        SolmatesError::MilestoneBounty
+    );
+    require!(
+        !shares_bps.is_empty()
+            && shares_bps.len() <= MAX_SPLIT_RECIPIENTS
+            && shares_bps.iter().all(|share| *share > 0)
+            && shares_bps.iter().map(|share| *share as u64).sum::<u64>() == 10000,
+        SolmatesError::InvalidSplitShares
+    );
+
+    let recipient_accounts_len = shares_bps.len() * SPLIT_RECIPIENT_ACCOUNTS;
+    require!(
+        ctx.remaining_accounts.len() >= recipient_accounts_len,
+        SolmatesError::InvalidSplitAccounts
+    );
+    let (recipient_accounts, contribution_accounts) =
+        ctx.remaining_accounts.split_at(recipient_accounts_len);
+
+    // Contributions follow the recipients - every contributor is credited
+    let contributors = load_contributions(bounty, contribution_accounts)?;
+
+    // Each recipient's gross share, with the rounding dust going to the first one
+    let reward_amount = bounty.reward_amount;
+    let mut gross_amounts: Vec<u64> = shares_bps
+        .iter()
+        .map(|share| pro_rata(reward_amount, *share as u64, 10000))
+        .collect();
+    let dust = reward_amount
+        .checked_sub(gross_amounts.iter().sum::<u64>())
+        .unwrap();
+    gross_amounts[0] = gross_amounts[0].checked_add(dust).unwrap();
+
+    let bounty_key = bounty.key();
+    let issuer_key = bounty.issuer;
+    let mint_key = ctx.accounts.mint.key();
+    for (index, accounts) in recipient_accounts
+        .chunks(SPLIT_RECIPIENT_ACCOUNTS)
+        .enumerate()
+    {
+        let matchmaker = &accounts[0];
+        let submission = Account::<Submission>::try_from(&accounts[1])?;
+        let matchmaker_token_account = InterfaceAccount::<TokenAccount>::try_from(&accounts[2])?;
+
+        require!(
+            submission.version == Submission::VERSION,
+            SolmatesError::AccountVersionMismatch
+        );
+        let (submission_key, _) = Pubkey::find_program_address(
+            &[b"submission", bounty_key.as_ref(), matchmaker.key.as_ref()],
+            &crate::ID,
+        );
+        require_keys_eq!(
+            submission.key(),
+            submission_key,
+            SolmatesError::InvalidSplitAccounts
+        );
+        require!(
+            submission.is_live_for(ctx.accounts.bounty.created_at),
+            SolmatesError::SubmissionNotLive
+        );
+        require!(
+            gross_amounts[index] >= submission.min_fee,
+            SolmatesError::RewardBelowMatchmakerFee
+        );
+        require!(
+            matchmaker_token_account.owner == *matchmaker.key
+                && matchmaker_token_account.mint == mint_key,
+            SolmatesError::InvalidSplitAccounts
+        );
+
+        let (matchmaker_amount, fee) = pay_from_bounty(
+            &ctx.accounts.bounty,
+            &ctx.accounts.bounty_vault,
+            &ctx.accounts.mint,
+            &matchmaker_token_account,
+            &ctx.accounts.treasury_token_account,
+            &ctx.accounts.token_program,
+            gross_amounts[index],
+        )?;
+
+        let (profile_key, _) =
+            Pubkey::find_program_address(&[b"profile", matchmaker.key.as_ref()], &crate::ID);
+        require_keys_eq!(
+            accounts[3].key(),
+            profile_key,
+            SolmatesError::InvalidSplitAccounts
+        );
+        update_profile_if_exists(&accounts[3], |matchmaker_profile| {
+            matchmaker_profile.reputation.bounties_paid = matchmaker_profile
+                .reputation
+                .bounties_paid
+                .saturating_add(1);
+            matchmaker_profile.reputation.bounty_earned = matchmaker_profile
+                .reputation
+                .bounty_earned
+                .saturating_add(matchmaker_amount);
+        })?;
+
+        let (record_key, _) =
+            Pubkey::find_program_address(&[b"matchmaker", matchmaker.key.as_ref()], &crate::ID);
+        require_keys_eq!(
+            accounts[4].key(),
+            record_key,
+            SolmatesError::InvalidSplitAccounts
+        );
+        credit_matchmaker_record(&accounts[4], matchmaker_amount, true)?;
+
+        emit!(BountyPaid {
+            issuer: issuer_key,
+            matchmaker: *matchmaker.key,
+            candidate: submission.candidate,
+            amount: matchmaker_amount,
+            fee,
+            contributors: contributors.clone(),
+        });
+
+        // Submission is settled, rent returned to its matchmaker
+        submission.close(matchmaker.clone())?;
+    }
+
+    // Update state after CPI
+    let bounty = &mut ctx.accounts.bounty;
+    bounty.status = BountyStatus::Filled;
+
+    // Release the issuer's open-bounty obligation
+    let issuer_profile = &mut ctx.accounts.issuer_profile;
+    issuer_profile.open_bounties = issuer_profile.open_bounties.saturating_sub(1);
+
+    // Account will be closed, rent returned to issuer
+
+    Ok(())
+}
@@ -1,24 +1,53 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 
 use crate::errors::SolmatesError;
 use crate::events::BidPlaced;
-use crate::states::DateAuction;
+use crate::states::{DateAuction, SessionDelegate, UserProfile};
+use crate::utils::{authorize_session, verify_bidder};
 use crate::{SNIPE_EXTENSION, SNIPE_THRESHOLD, MAX_SNIPE_EXTENSIONS, MIN_BID_INCREMENT_BPS};
 
 #[derive(Accounts)]
 pub struct PlaceBid<'info> {
-    #[account(mut)]
-    pub bidder: Signer<'info>,
+    /// CHECK: Bidder wallet - owns the bid and receives any refund
+    pub bidder: UncheckedAccount<'info>,
+
+    /// Bidder, or a session delegate acting for them. A delegate must also be the
+    /// approved token delegate of `bidder_token_account` to move the bid.
+    pub signer: Signer<'info>,
+
+    /// Optional: session the bidder granted to `signer`, when `signer` is a delegate
+    #[account(
+        seeds = [b"session", bidder.key().as_ref(), signer.key().as_ref()],
+        bump = session.bump,
+        constraint = session.version == SessionDelegate::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub session: Option<Account<'info, SessionDelegate>>,
 
     /// CHECK: Previous highest bidder - receives refund
     #[account(mut)]
     pub previous_bidder: UncheckedAccount<'info>,
 
+    /// Optional: bidder's profile, required when the auction sets a minimum profile age
+    #[account(
+        seeds = [b"profile", bidder_profile.identity.as_ref()],
+        bump = bidder_profile.bump,
+        constraint = bidder_profile.authority == bidder.key() @ SolmatesError::Unauthorized,
+        constraint = bidder_profile.version == UserProfile::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub bidder_profile: Option<Account<'info, UserProfile>>,
+
     /// CHECK: Auction host
     pub host: UncheckedAccount<'info>,
 
-    pub mint: Account<'info, Mint>,
+    #[account(
+        seeds = [b"profile", host.key().as_ref()],
+        bump = host_profile.bump,
+        constraint = host_profile.version == UserProfile::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub host_profile: Account<'info, UserProfile>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         mut,
@@ -26,40 +55,66 @@ pub struct PlaceBid<'info> {
         bump = auction.bump,
         has_one = host,
         has_one = mint,
-        constraint = auction.highest_bidder == previous_bidder.key() @ SolmatesError::InvalidPreviousBidder
+        constraint = auction.highest_bidder == previous_bidder.key() @ SolmatesError::InvalidPreviousBidder,
+        constraint = auction.version == DateAuction::VERSION @ SolmatesError::AccountVersionMismatch
     )]
     pub auction: Account<'info, DateAuction>,
 
     #[account(
         mut,
         associated_token::mint = mint,
-        associated_token::authority = auction
+        associated_token::authority = auction,
+        associated_token::token_program = token_program
     )]
-    pub auction_vault: Account<'info, TokenAccount>,
+    pub auction_vault: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         mut,
         associated_token::mint = mint,
-        associated_token::authority = bidder
+        associated_token::authority = bidder,
+        associated_token::token_program = token_program
     )]
-    pub bidder_token_account: Account<'info, TokenAccount>,
+    pub bidder_token_account: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         mut,
         associated_token::mint = mint,
-        associated_token::authority = previous_bidder
+        associated_token::authority = previous_bidder,
+        associated_token::token_program = token_program
     )]
-    pub previous_bidder_token_account: Account<'info, TokenAccount>,
+    pub previous_bidder_token_account: InterfaceAccount<'info, TokenAccount>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 pub fn handler(ctx: Context<PlaceBid>, bid_amount: u64) -> Result<()> {
+    authorize_session(
+        &ctx.accounts.bidder.key(),
+        &ctx.accounts.signer.key(),
+        ctx.accounts.session.as_deref(),
+        SessionDelegate::SCOPE_PLACE_BID,
+    )?;
+
     let auction = &mut ctx.accounts.auction;
     let current_time = Clock::get()?.unix_timestamp;
 
     // Check auction is still active
     require!(current_time < auction.end_time, SolmatesError::AuctionEnded);
+    require!(
+        !ctx.accounts.host_profile.is_away(current_time),
+        SolmatesError::HostAway
+    );
+
+    // Bidder requirements - any gate holdings are proven with remaining accounts
+    if let Some(requirements) = &auction.bidder_requirements {
+        verify_bidder(
+            requirements,
+            &ctx.accounts.bidder.key(),
+            ctx.accounts.bidder_profile.as_deref(),
+            ctx.remaining_accounts,
+            current_time,
+        )?;
+    }
 
     // Check bid is higher than current
     require!(bid_amount > auction.highest_bid, SolmatesError::BidTooLow);
@@ -90,35 +145,49 @@ pub fn handler(ctx: Context<PlaceBid>, bid_amount: u64) -> Result<()> {
     ];
     let signer_seeds = &[&seeds[..]];
 
-    // Step 1: Refund previous bidder (if not the host)
+    // Step 1: Deposit new bid
+    let vault_balance_before = ctx.accounts.auction_vault.amount;
+    let deposit_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.bidder_token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            to: ctx.accounts.auction_vault.to_account_info(),
+            authority: ctx.accounts.signer.to_account_info(),
+        },
+    );
+    token_interface::transfer_checked(deposit_ctx, bid_amount, ctx.accounts.mint.decimals)?;
+
+    // The bid is what actually arrived - transfer-fee mints withhold part of the amount,
+    // so the increment rule is re-checked against the received amount
+    ctx.accounts.auction_vault.reload()?;
+    let received = ctx
+        .accounts
+        .auction_vault
+        .amount
+        .checked_sub(vault_balance_before)
+        .unwrap();
+    require!(received >= min_bid, SolmatesError::BidIncrementTooSmall);
+
+    // Step 2: Refund previous bidder (if not the host)
     if !is_first_bid {
         let refund_ctx = CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
-            Transfer {
+            TransferChecked {
                 from: ctx.accounts.auction_vault.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
                 to: ctx.accounts.previous_bidder_token_account.to_account_info(),
                 authority: ctx.accounts.auction.to_account_info(),
             },
             signer_seeds,
         );
-        token::transfer(refund_ctx, previous_bid_amount)?;
+        token_interface::transfer_checked(refund_ctx, previous_bid_amount, ctx.accounts.mint.decimals)?;
     }
 
-    // Step 2: Deposit new bid
-    let deposit_ctx = CpiContext::new(
-        ctx.accounts.token_program.to_account_info(),
-        Transfer {
-            from: ctx.accounts.bidder_token_account.to_account_info(),
-            to: ctx.accounts.auction_vault.to_account_info(),
-            authority: ctx.accounts.bidder.to_account_info(),
-        },
-    );
-    token::transfer(deposit_ctx, bid_amount)?;
-
     // Step 3: Update auction state
     let auction = &mut ctx.accounts.auction;
     auction.highest_bidder = ctx.accounts.bidder.key();
-    auction.highest_bid = bid_amount;
+    auction.highest_bid = received;
 
     // Step 4: Snipe protection - extend if within last 5 minutes (with cap at 1 hour total)
     let time_remaining = original_end_time.checked_sub(current_time).unwrap_or(0);
@@ -132,7 +201,7 @@ pub fn handler(ctx: Context<PlaceBid>, bid_amount: u64) -> Result<()> {
     emit!(BidPlaced {
         auction_id,
         bidder: ctx.accounts.bidder.key(),
-        amount: bid_amount,
+        amount: received,
         previous_bidder,
         new_end_time: auction.end_time,
     });
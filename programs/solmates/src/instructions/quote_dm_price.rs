@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::SolmatesError;
+use crate::states::UserProfile;
+
+/// Read-only: returns the deposit a DM to the profile needs right now. Clients simulate
+/// it to quote the price before building `deposit_for_dm`.
+#[derive(Accounts)]
+pub struct QuoteDmPrice<'info> {
+    #[account(
+        seeds = [b"profile", recipient_profile.identity.as_ref()],
+        bump = recipient_profile.bump,
+        constraint = recipient_profile.version == UserProfile::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub recipient_profile: Account<'info, UserProfile>,
+}
+
+pub fn handler(ctx: Context<QuoteDmPrice>) -> Result<u64> {
+    Ok(ctx
+        .accounts
+        .recipient_profile
+        .dm_price_at(Clock::get()?.unix_timestamp))
+}
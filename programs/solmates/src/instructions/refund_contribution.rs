@@ -0,0 +1,133 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::errors::SolmatesError;
+use crate::events::ContributionRefunded;
+use crate::states::{BountyStatus, BountyVault, Contribution, UserProfile};
+use crate::utils::{close_bounty, pro_rata};
+
+/// Permissionless: returns a contributor's pro-rata share of a cancelled or expired
+/// bounty. The last refund closes the bounty to the issuer.
+#[derive(Accounts)]
+pub struct RefundContribution<'info> {
+    /// CHECK: Contributor wallet - receives the contribution's rent
+    #[account(mut)]
+    pub contributor: UncheckedAccount<'info>,
+
+    /// CHECK: Issuer's current authority - receives the bounty's rent after the last refund
+    #[account(
+        mut,
+        address = issuer_profile.authority @ SolmatesError::Unauthorized
+    )]
+    pub issuer: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"profile", bounty.issuer.as_ref()],
+        bump = issuer_profile.bump,
+        constraint = issuer_profile.version == UserProfile::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub issuer_profile: Account<'info, UserProfile>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"bounty", bounty.issuer.as_ref()],
+        bump = bounty.bump,
+        has_one = mint,
+        constraint = bounty.version == BountyVault::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub bounty: Account<'info, BountyVault>,
+
+    #[account(
+        mut,
+        seeds = [b"contribution", bounty.key().as_ref(), contributor.key().as_ref()],
+        bump = contribution.bump,
+        has_one = contributor,
+        close = contributor,
+        constraint = contribution.bounty_created_at == bounty.created_at @ SolmatesError::ContributionMismatch,
+        constraint = contribution.version == Contribution::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub contribution: Account<'info, Contribution>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = bounty,
+        associated_token::token_program = token_program
+    )]
+    pub bounty_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = contributor,
+        associated_token::token_program = token_program
+    )]
+    pub contributor_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler(ctx: Context<RefundContribution>) -> Result<()> {
+    let bounty = &ctx.accounts.bounty;
+    require!(
+        bounty.status == BountyStatus::Refunding,
+        SolmatesError::BountyNotOpen
+    );
+
+    // The last contributor takes whatever is left, so rounding dust never strands
+    let contribution_amount = ctx.accounts.contribution.amount;
+    let amount = if bounty.contributor_count <= 1 {
+        ctx.accounts.bounty_vault.amount
+    } else {
+        pro_rata(
+            ctx.accounts.bounty_vault.amount,
+            contribution_amount,
+            bounty.contributed_amount,
+        )
+    };
+
+    if amount > 0 {
+        let seeds = &[b"bounty", bounty.issuer.as_ref(), &[bounty.bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.bounty_vault.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.contributor_token_account.to_account_info(),
+                authority: ctx.accounts.bounty.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token_interface::transfer_checked(transfer_ctx, amount, ctx.accounts.mint.decimals)?;
+    }
+
+    let bounty = &mut ctx.accounts.bounty;
+    bounty.contributed_amount = bounty
+        .contributed_amount
+        .saturating_sub(contribution_amount);
+    bounty.reward_amount = bounty.contributed_amount;
+    bounty.contributor_count = bounty.contributor_count.saturating_sub(1);
+
+    emit!(ContributionRefunded {
+        issuer: bounty.issuer,
+        contributor: ctx.accounts.contributor.key(),
+        amount,
+    });
+
+    if bounty.contributor_count == 0 {
+        close_bounty(
+            &mut ctx.accounts.bounty,
+            &ctx.accounts.issuer.to_account_info(),
+            &mut ctx.accounts.issuer_profile,
+        )?;
+    }
+
+    // Contribution will be closed, rent returned to contributor
+
+    Ok(())
+}
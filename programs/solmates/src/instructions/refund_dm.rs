@@ -1,9 +1,10 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 
 use crate::errors::SolmatesError;
 use crate::events::EscrowRefunded;
-use crate::states::{EscrowStatus, MessageEscrow};
+use crate::states::{EscrowStatus, Inbox, InboxPage, MessageEscrow};
+use crate::utils::{unindex_escrow, update_profile_if_exists};
 
 #[derive(Accounts)]
 pub struct RefundDm<'info> {
@@ -13,7 +14,16 @@ pub struct RefundDm<'info> {
     /// CHECK: Recipient wallet address
     pub recipient: UncheckedAccount<'info>,
 
-    pub mint: Account<'info, Mint>,
+    /// CHECK: Recipient's profile PDA - always passed, and settled whenever the profile
+    /// exists, so the refund can't skip its pending count
+    #[account(
+        mut,
+        seeds = [b"profile", recipient.key().as_ref()],
+        bump
+    )]
+    pub recipient_profile: UncheckedAccount<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         mut,
@@ -22,25 +32,50 @@ pub struct RefundDm<'info> {
         has_one = sender,
         has_one = recipient,
         has_one = mint,
-        close = sender
+        close = sender,
+        constraint = escrow.version == MessageEscrow::VERSION @ SolmatesError::AccountVersionMismatch
     )]
     pub escrow: Account<'info, MessageEscrow>,
 
+    /// Recipient's inbox - required when the escrow was indexed on deposit
+    #[account(
+        mut,
+        seeds = [b"inbox", recipient.key().as_ref()],
+        bump = inbox.bump,
+        constraint = inbox.version == Inbox::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub inbox: Option<Account<'info, Inbox>>,
+
+    /// Inbox page indexing the escrow - required when the escrow was indexed on deposit
+    #[account(
+        mut,
+        seeds = [
+            b"inbox_page",
+            recipient.key().as_ref(),
+            escrow.inbox_page.unwrap_or_default().to_le_bytes().as_ref()
+        ],
+        bump = inbox_page.bump,
+        constraint = inbox_page.version == InboxPage::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub inbox_page: Option<Account<'info, InboxPage>>,
+
     #[account(
         mut,
         associated_token::mint = mint,
-        associated_token::authority = escrow
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program
     )]
-    pub escrow_vault: Account<'info, TokenAccount>,
+    pub escrow_vault: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         mut,
         associated_token::mint = mint,
-        associated_token::authority = sender
+        associated_token::authority = sender,
+        associated_token::token_program = token_program
     )]
-    pub sender_token_account: Account<'info, TokenAccount>,
+    pub sender_token_account: InterfaceAccount<'info, TokenAccount>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 pub fn handler(ctx: Context<RefundDm>) -> Result<()> {
@@ -70,14 +105,29 @@ pub fn handler(ctx: Context<RefundDm>) -> Result<()> {
 
     let transfer_ctx = CpiContext::new_with_signer(
         ctx.accounts.token_program.to_account_info(),
-        Transfer {
+        TransferChecked {
             from: ctx.accounts.escrow_vault.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
             to: ctx.accounts.sender_token_account.to_account_info(),
             authority: ctx.accounts.escrow.to_account_info(),
         },
         signer_seeds,
     );
-    token::transfer(transfer_ctx, escrow.amount)?;
+    token_interface::transfer_checked(transfer_ctx, escrow.amount, ctx.accounts.mint.decimals)?;
+
+    unindex_escrow(
+        &ctx.accounts.escrow.key(),
+        &ctx.accounts.escrow,
+        ctx.accounts.inbox.as_deref_mut(),
+        ctx.accounts.inbox_page.as_deref_mut(),
+    )?;
+
+    // Escrow is settled - release the recipient's pending obligation
+    update_profile_if_exists(&ctx.accounts.recipient_profile, |recipient_profile| {
+        recipient_profile.pending_escrows = recipient_profile.pending_escrows.saturating_sub(1);
+        recipient_profile.reputation.dms_expired =
+            recipient_profile.reputation.dms_expired.saturating_add(1);
+    })?;
 
     emit!(EscrowRefunded {
         sender: escrow.sender,
@@ -0,0 +1,120 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::errors::SolmatesError;
+use crate::events::BountyExpired;
+use crate::states::{BountyStatus, BountyVault, Submission, SubmissionStatus, UserProfile};
+use crate::utils::refund_issuer;
+
+/// Permissionless: refunds an expired bounty to the issuer and its contributors. Pending
+/// submissions passed as remaining accounts are marked void; any left out can no longer
+/// be paid anyway, since the bounty is no longer open.
+#[derive(Accounts)]
+pub struct RefundExpiredBounty<'info> {
+    /// CHECK: Issuer's current authority - receives the bounty's rent
+    #[account(
+        mut,
+        address = issuer_profile.authority @ SolmatesError::Unauthorized
+    )]
+    pub issuer: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"profile", bounty.issuer.as_ref()],
+        bump = issuer_profile.bump,
+        constraint = issuer_profile.version == UserProfile::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub issuer_profile: Account<'info, UserProfile>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"bounty", bounty.issuer.as_ref()],
+        bump = bounty.bump,
+        has_one = mint,
+        constraint = bounty.version == BountyVault::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub bounty: Account<'info, BountyVault>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = bounty,
+        associated_token::token_program = token_program
+    )]
+    pub bounty_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = issuer,
+        associated_token::token_program = token_program
+    )]
+    pub issuer_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RefundExpiredBounty<'info>>,
+) -> Result<()> {
+    let bounty = &ctx.accounts.bounty;
+
+    require!(
+        bounty.status == BountyStatus::Open,
+        SolmatesError::BountyNotOpen
+    );
+    require!(
+        bounty.is_expired(Clock::get()?.unix_timestamp),
+        SolmatesError::BountyNotExpired
+    );
+    // A claim made before expiry has to be released or expire out of its dispute first
+    require!(
+        !bounty.is_claim_locked(),
+        SolmatesError::BountyClaimPending
+    );
+
+    // Void the pending submissions that were passed in
+    let bounty_key = bounty.key();
+    let mut voided_submissions: u32 = 0;
+    for submission_info in ctx.remaining_accounts {
+        let mut submission = Account::<Submission>::try_from(submission_info)?;
+        require!(
+            submission.version == Submission::VERSION,
+            SolmatesError::AccountVersionMismatch
+        );
+        require_keys_eq!(
+            submission.bounty,
+            bounty_key,
+            SolmatesError::SubmissionBountyMismatch
+        );
+        if submission.status == SubmissionStatus::Pending {
+            submission.status = SubmissionStatus::Void;
+            submission.exit(&crate::ID)?;
+            voided_submissions = voided_submissions.saturating_add(1);
+        }
+    }
+
+    // Return the issuer's share - contributors reclaim theirs with refund_contribution
+    let issuer_key = bounty.issuer;
+    let refunding_contributors = bounty.contributor_count;
+    let amount = refund_issuer(
+        &mut ctx.accounts.bounty,
+        &ctx.accounts.bounty_vault,
+        &ctx.accounts.mint,
+        &ctx.accounts.issuer_token_account,
+        &ctx.accounts.issuer.to_account_info(),
+        &mut ctx.accounts.issuer_profile,
+        &ctx.accounts.token_program,
+    )?;
+
+    emit!(BountyExpired {
+        issuer: issuer_key,
+        amount,
+        voided_submissions,
+        refunding_contributors,
+    });
+
+    Ok(())
+}
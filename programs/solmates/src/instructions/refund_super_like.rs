@@ -0,0 +1,123 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    self, CloseAccount, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+use crate::errors::SolmatesError;
+use crate::events::SuperLikeRefunded;
+use crate::states::{Like, SuperLike};
+
+/// Permissionless crank: returns a super-like's escrow to the sender once the window
+/// closed without a like back.
+#[derive(Accounts)]
+pub struct RefundSuperLike<'info> {
+    /// CHECK: Wallet that escrowed the tokens - receives the refund and rent
+    #[account(mut)]
+    pub sender: UncheckedAccount<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"super_like", super_like.liker.as_ref(), super_like.likee.as_ref()],
+        bump = super_like.bump,
+        has_one = sender,
+        has_one = mint,
+        close = sender,
+        constraint = super_like.version == SuperLike::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub super_like: Account<'info, SuperLike>,
+
+    /// CHECK: The likee's like of the liker, if any - a like back in time blocks the refund
+    #[account(
+        seeds = [b"like", super_like.likee.as_ref(), super_like.liker.as_ref()],
+        bump
+    )]
+    pub like_back: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = super_like,
+        associated_token::token_program = token_program
+    )]
+    pub super_like_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = sender,
+        associated_token::token_program = token_program
+    )]
+    pub sender_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler(ctx: Context<RefundSuperLike>) -> Result<()> {
+    let super_like = &ctx.accounts.super_like;
+
+    let current_time = Clock::get()?.unix_timestamp;
+    require!(
+        current_time > super_like.expiry,
+        SolmatesError::SuperLikeNotExpired
+    );
+
+    // A like back within the window earned the escrow, even if not yet claimed
+    let like_back = &ctx.accounts.like_back;
+    if like_back.owner == &crate::ID && !like_back.data_is_empty() {
+        let like_back = Like::try_deserialize(&mut &like_back.try_borrow_data()?[..])?;
+        require!(
+            like_back.liked_at > super_like.expiry,
+            SolmatesError::SuperLikeLikedBack
+        );
+    }
+
+    // Transfer USDC from the super-like vault back to sender - the whole balance, so the
+    // vault empties and can be closed
+    let seeds = &[
+        b"super_like",
+        super_like.liker.as_ref(),
+        super_like.likee.as_ref(),
+        &[super_like.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.super_like_vault.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            to: ctx.accounts.sender_token_account.to_account_info(),
+            authority: ctx.accounts.super_like.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token_interface::transfer_checked(
+        transfer_ctx,
+        ctx.accounts.super_like_vault.amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    // Vault is empty - rent returned to sender
+    let close_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.super_like_vault.to_account_info(),
+            destination: ctx.accounts.sender.to_account_info(),
+            authority: ctx.accounts.super_like.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token_interface::close_account(close_ctx)?;
+
+    emit!(SuperLikeRefunded {
+        liker: super_like.liker,
+        likee: super_like.likee,
+        amount: super_like.amount,
+    });
+
+    // Account will be closed, rent returned to sender
+
+    Ok(())
+}
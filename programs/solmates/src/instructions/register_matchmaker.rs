@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+use crate::events::MatchmakerRegistered;
+use crate::states::MatchmakerProfile;
+
+/// Registers the signer as a matchmaker. Their track record starts empty.
+#[derive(Accounts)]
+pub struct RegisterMatchmaker<'info> {
+    #[account(mut)]
+    pub matchmaker: Signer<'info>,
+
+    #[account(
+        init,
+        payer = matchmaker,
+        space = 8 + MatchmakerProfile::INIT_SPACE,
+        seeds = [b"matchmaker", matchmaker.key().as_ref()],
+        bump
+    )]
+    pub matchmaker_record: Account<'info, MatchmakerProfile>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<RegisterMatchmaker>,
+    min_fee: u64,
+    specialties_hash: [u8; 32],
+) -> Result<()> {
+    let record = &mut ctx.accounts.matchmaker_record;
+    record.version = MatchmakerProfile::VERSION;
+    record.matchmaker = ctx.accounts.matchmaker.key();
+    record.min_fee = min_fee;
+    record.specialties_hash = specialties_hash;
+    record.registered_at = Clock::get()?.unix_timestamp;
+    record.bump = ctx.bumps.matchmaker_record;
+
+    emit!(MatchmakerRegistered {
+        matchmaker: record.matchmaker,
+        min_fee,
+        specialties_hash,
+    });
+
+    Ok(())
+}
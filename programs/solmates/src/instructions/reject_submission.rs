@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::SolmatesError;
+use crate::events::SubmissionRejected;
+use crate::states::{BountyVault, Submission, SubmissionStatus, UserProfile};
+
+/// Issuer turns down a submission. It stays on-chain as rejected, so the matchmaker
+/// can't be paid for it, until the matchmaker withdraws it.
+#[derive(Accounts)]
+pub struct RejectSubmission<'info> {
+    pub issuer: Signer<'info>,
+
+    #[account(
+        seeds = [b"profile", bounty.issuer.as_ref()],
+        bump = issuer_profile.bump,
+        constraint = issuer_profile.authority == issuer.key() @ SolmatesError::Unauthorized,
+        constraint = issuer_profile.version == UserProfile::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub issuer_profile: Account<'info, UserProfile>,
+
+    #[account(
+        seeds = [b"bounty", bounty.issuer.as_ref()],
+        bump = bounty.bump,
+        constraint = bounty.version == BountyVault::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub bounty: Account<'info, BountyVault>,
+
+    #[account(
+        mut,
+        seeds = [b"submission", bounty.key().as_ref(), submission.matchmaker.as_ref()],
+        bump = submission.bump,
+        constraint = submission.version == Submission::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub submission: Account<'info, Submission>,
+}
+
+pub fn handler(ctx: Context<RejectSubmission>) -> Result<()> {
+    let submission = &mut ctx.accounts.submission;
+    require!(
+        submission.status == SubmissionStatus::Pending,
+        SolmatesError::SubmissionNotLive
+    );
+    submission.status = SubmissionStatus::Rejected;
+
+    emit!(SubmissionRejected {
+        bounty: submission.bounty,
+        matchmaker: submission.matchmaker,
+    });
+
+    Ok(())
+}
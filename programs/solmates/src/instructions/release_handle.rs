@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::SolmatesError;
+use crate::events::HandleReleased;
+use crate::states::{HandleRecord, UserProfile};
+
+/// Gives up the profile's handle so anyone can claim it. Rent goes back to the
+/// authority; the fee is not refunded.
+#[derive(Accounts)]
+pub struct ReleaseHandle<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"profile", profile.identity.as_ref()],
+        bump = profile.bump,
+        has_one = authority,
+        constraint = profile.version == UserProfile::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub profile: Account<'info, UserProfile>,
+
+    #[account(
+        mut,
+        seeds = [b"handle", handle_record.handle.as_bytes()],
+        bump = handle_record.bump,
+        has_one = profile @ SolmatesError::HandleNotOwned,
+        close = authority,
+        constraint = handle_record.version == HandleRecord::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub handle_record: Account<'info, HandleRecord>,
+}
+
+pub fn handler(ctx: Context<ReleaseHandle>) -> Result<()> {
+    ctx.accounts.profile.handle = None;
+
+    emit!(HandleReleased {
+        handle: ctx.accounts.handle_record.handle.clone(),
+        profile: ctx.accounts.profile.key(),
+    });
+
+    // Account will be closed, rent returned to authority
+
+    Ok(())
+}
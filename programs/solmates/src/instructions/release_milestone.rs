@@ -0,0 +1,217 @@
+use anchor_lang::prelude::*;
+use anchor_lang::AccountsClose;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::errors::SolmatesError;
+use crate::events::{BountyPaid, MilestoneReleased};
+use crate::states::{BountyStatus, BountyVault, Submission, UserProfile};
+use crate::utils::{
+    close_bounty, credit_matchmaker_record, pay_from_bounty, update_profile_if_exists,
+};
+use crate::TREASURY;
+
+/// Issuer releases the next milestone of a staged bounty. The first release ties the
+/// bounty to that matchmaker's submission; the last one closes the bounty.
+#[derive(Accounts)]
+pub struct ReleaseMilestone<'info> {
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"profile", bounty.issuer.as_ref()],
+        bump = issuer_profile.bump,
+        constraint = issuer_profile.authority == issuer.key() @ SolmatesError::Unauthorized,
+        constraint = issuer_profile.version == UserProfile::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub issuer_profile: Account<'info, UserProfile>,
+
+    /// CHECK: Matchmaker wallet - receives payout and, at the end, the submission's rent
+    #[account(mut)]
+    pub matchmaker: UncheckedAccount<'info>,
+
+    /// CHECK: Matchmaker's profile PDA - always passed, and credited with the payout
+    /// whenever the matchmaker has a profile
+    #[account(
+        mut,
+        seeds = [b"profile", matchmaker.key().as_ref()],
+        bump
+    )]
+    pub matchmaker_profile: UncheckedAccount<'info>,
+
+    /// CHECK: Matchmaker's registry record PDA - always passed, and credited with the
+    /// payout when the matchmaker is registered
+    #[account(
+        mut,
+        seeds = [b"matchmaker", matchmaker.key().as_ref()],
+        bump
+    )]
+    pub matchmaker_record: UncheckedAccount<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"bounty", bounty.issuer.as_ref()],
+        bump = bounty.bump,
+        has_one = mint,
+        constraint = bounty.version == BountyVault::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub bounty: Account<'info, BountyVault>,
+
+    /// The matchmaker's submission - must still be pending on this bounty
+    #[account(
+        mut,
+        seeds = [b"submission", bounty.key().as_ref(), matchmaker.key().as_ref()],
+        bump = submission.bump,
+        constraint = submission.is_live_for(bounty.created_at) @ SolmatesError::SubmissionNotLive,
+        constraint = submission.version == Submission::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub submission: Account<'info, Submission>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = bounty,
+        associated_token::token_program = token_program
+    )]
+    pub bounty_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = issuer,
+        associated_token::mint = mint,
+        associated_token::authority = matchmaker,
+        associated_token::token_program = token_program
+    )]
+    pub matchmaker_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Platform treasury for fees - validated against constant
+    #[account(
+        mut,
+        constraint = treasury.key() == TREASURY @ SolmatesError::InvalidTreasury
+    )]
+    pub treasury: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = issuer,
+        associated_token::mint = mint,
+        associated_token::authority = treasury,
+        associated_token::token_program = token_program
+    )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ReleaseMilestone>, index: u8) -> Result<()> {
+    let bounty = &ctx.accounts.bounty;
+    let index = index as usize;
+
+    require!(
+        bounty.status == BountyStatus::Open,
+        SolmatesError::BountyNotOpen
+    );
+    require!(
+        !bounty.milestones.is_empty(),
+        SolmatesError::NotMilestoneBounty
+    );
+    // Only the first unreleased milestone can go next
+    require!(
+        bounty
+            .milestones
+            .iter()
+            .position(|milestone| !milestone.released)
+            == Some(index),
+        SolmatesError::InvalidMilestoneIndex
+    );
+    let milestone = bounty.milestones[index];
+    require!(
+        Clock::get()?.unix_timestamp >= milestone.unlock_at,
+        SolmatesError::MilestoneLocked
+    );
+
+    // Every milestone goes to the matchmaker who received the first
+    let submission_key = ctx.accounts.submission.key();
+    require!(
+        bounty
+            .active_claim
+            .is_none_or(|claim| claim == submission_key),
+        SolmatesError::BountyClaimPending
+    );
+
+    let (matchmaker_amount, fee) = pay_from_bounty(
+        &ctx.accounts.bounty,
+        &ctx.accounts.bounty_vault,
+        &ctx.accounts.mint,
+        &ctx.accounts.matchmaker_token_account,
+        &ctx.accounts.treasury_token_account,
+        &ctx.accounts.token_program,
+        milestone.amount,
+    )?;
+
+    // Update state after CPI
+    let bounty = &mut ctx.accounts.bounty;
+    let first_release = bounty.active_claim.is_none();
+    bounty.milestones[index].released = true;
+    bounty.active_claim = Some(submission_key);
+    let final_release = index + 1 == bounty.milestones.len();
+
+    update_profile_if_exists(&ctx.accounts.matchmaker_profile, |matchmaker_profile| {
+        if first_release {
+            matchmaker_profile.reputation.bounties_paid = matchmaker_profile
+                .reputation
+                .bounties_paid
+                .saturating_add(1);
+        }
+        matchmaker_profile.reputation.bounty_earned = matchmaker_profile
+            .reputation
+            .bounty_earned
+            .saturating_add(matchmaker_amount);
+    })?;
+
+    credit_matchmaker_record(
+        &ctx.accounts.matchmaker_record,
+        matchmaker_amount,
+        first_release,
+    )?;
+
+    emit!(MilestoneReleased {
+        issuer: bounty.issuer,
+        matchmaker: ctx.accounts.matchmaker.key(),
+        index: index as u8,
+        amount: matchmaker_amount,
+        fee,
+    });
+
+    if final_release {
+        bounty.status = BountyStatus::Filled;
+
+        emit!(BountyPaid {
+            issuer: bounty.issuer,
+            matchmaker: ctx.accounts.matchmaker.key(),
+            candidate: ctx.accounts.submission.candidate,
+            amount: matchmaker_amount,
+            fee,
+            contributors: Vec::new(),
+        });
+
+        // Bounty rent to issuer, submission rent to matchmaker
+        ctx.accounts
+            .submission
+            .close(ctx.accounts.matchmaker.to_account_info())?;
+        close_bounty(
+            &mut ctx.accounts.bounty,
+            &ctx.accounts.issuer.to_account_info(),
+            &mut ctx.accounts.issuer_profile,
+        )?;
+    }
+
+    Ok(())
+}
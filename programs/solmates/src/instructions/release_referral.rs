@@ -0,0 +1,193 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::errors::SolmatesError;
+use crate::events::BountyPaid;
+use crate::states::{BountyStatus, BountyVault, Submission, SubmissionStatus, UserProfile};
+use crate::utils::{
+    credit_matchmaker_record, load_contributions, pay_from_bounty, update_profile_if_exists,
+};
+use crate::{CONSENT_TIMEOUT, TREASURY};
+
+/// Permissionless: pays a claimed consent-mode bounty once the issuer and the candidate
+/// have both confirmed, or once the objection period passed without an objection.
+#[derive(Accounts)]
+pub struct ReleaseReferral<'info> {
+    /// Pays for any token accounts that need creating
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Issuer's current authority - receives the bounty's rent
+    #[account(
+        mut,
+        address = issuer_profile.authority @ SolmatesError::Unauthorized
+    )]
+    pub issuer: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"profile", bounty.issuer.as_ref()],
+        bump = issuer_profile.bump,
+        constraint = issuer_profile.version == UserProfile::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub issuer_profile: Account<'info, UserProfile>,
+
+    /// CHECK: Matchmaker wallet - receives payout and the submission's rent
+    #[account(
+        mut,
+        address = submission.matchmaker @ SolmatesError::Unauthorized
+    )]
+    pub matchmaker: UncheckedAccount<'info>,
+
+    /// CHECK: Matchmaker's profile PDA - always passed, and credited with the payout
+    /// whenever the matchmaker has a profile
+    #[account(
+        mut,
+        seeds = [b"profile", matchmaker.key().as_ref()],
+        bump
+    )]
+    pub matchmaker_profile: UncheckedAccount<'info>,
+
+    /// CHECK: Matchmaker's registry record PDA - always passed, and credited with the
+    /// payout when the matchmaker is registered
+    #[account(
+        mut,
+        seeds = [b"matchmaker", matchmaker.key().as_ref()],
+        bump
+    )]
+    pub matchmaker_record: UncheckedAccount<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"bounty", bounty.issuer.as_ref()],
+        bump = bounty.bump,
+        has_one = mint,
+        close = issuer,
+        constraint = bounty.version == BountyVault::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub bounty: Account<'info, BountyVault>,
+
+    #[account(
+        mut,
+        seeds = [b"submission", bounty.key().as_ref(), submission.matchmaker.as_ref()],
+        bump = submission.bump,
+        close = matchmaker,
+        constraint = bounty.active_claim == Some(submission.key()) @ SolmatesError::ReferralNotClaimed,
+        constraint = submission.version == Submission::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub submission: Account<'info, Submission>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = bounty,
+        associated_token::token_program = token_program
+    )]
+    pub bounty_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = matchmaker,
+        associated_token::token_program = token_program
+    )]
+    pub matchmaker_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Platform treasury for fees - validated against constant
+    #[account(
+        mut,
+        constraint = treasury.key() == TREASURY @ SolmatesError::InvalidTreasury
+    )]
+    pub treasury: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = treasury,
+        associated_token::token_program = token_program
+    )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, ReleaseReferral<'info>>) -> Result<()> {
+    let bounty = &ctx.accounts.bounty;
+    require!(
+        bounty.status == BountyStatus::Open,
+        SolmatesError::BountyNotOpen
+    );
+
+    let submission = &ctx.accounts.submission;
+    let both_confirmed = submission.issuer_confirmed && submission.candidate_confirmed;
+    let unopposed = submission.status == SubmissionStatus::Claimed
+        && Clock::get()?.unix_timestamp >= submission.claimed_at.saturating_add(CONSENT_TIMEOUT);
+    require!(
+        both_confirmed || unopposed,
+        SolmatesError::ConsentNotReached
+    );
+
+    // The reward may have been lowered since the matchmaker submitted
+    require!(
+        bounty.reward_amount >= ctx.accounts.submission.min_fee,
+        SolmatesError::RewardBelowMatchmakerFee
+    );
+
+    // Contributions passed as remaining accounts - every contributor is credited
+    let contributors = load_contributions(bounty, ctx.remaining_accounts)?;
+
+    let issuer_key = bounty.issuer;
+    let (matchmaker_amount, fee) = pay_from_bounty(
+        &ctx.accounts.bounty,
+        &ctx.accounts.bounty_vault,
+        &ctx.accounts.mint,
+        &ctx.accounts.matchmaker_token_account,
+        &ctx.accounts.treasury_token_account,
+        &ctx.accounts.token_program,
+        bounty.reward_amount,
+    )?;
+
+    // Update state after CPI
+    let bounty = &mut ctx.accounts.bounty;
+    bounty.status = BountyStatus::Filled;
+    bounty.active_claim = None;
+
+    // Release the issuer's open-bounty obligation
+    let issuer_profile = &mut ctx.accounts.issuer_profile;
+    issuer_profile.open_bounties = issuer_profile.open_bounties.saturating_sub(1);
+
+    update_profile_if_exists(&ctx.accounts.matchmaker_profile, |matchmaker_profile| {
+        matchmaker_profile.reputation.bounties_paid = matchmaker_profile
+            .reputation
+            .bounties_paid
+            .saturating_add(1);
+        matchmaker_profile.reputation.bounty_earned = matchmaker_profile
+            .reputation
+            .bounty_earned
+            .saturating_add(matchmaker_amount);
+    })?;
+
+    credit_matchmaker_record(&ctx.accounts.matchmaker_record, matchmaker_amount, true)?;
+
+    emit!(BountyPaid {
+        issuer: issuer_key,
+        matchmaker: ctx.accounts.matchmaker.key(),
+        candidate: ctx.accounts.submission.candidate,
+        amount: matchmaker_amount,
+        fee,
+        contributors,
+    });
+
+    // Accounts will be closed - bounty rent to issuer, submission rent to matchmaker
+
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::SolmatesError;
+use crate::events::VerifierRemoved;
+use crate::states::Verifier;
+use crate::ADMIN;
+
+/// Deregisters a verifier. Its existing attestations stop satisfying gates. Admin only.
+#[derive(Accounts)]
+pub struct RemoveVerifier<'info> {
+    #[account(
+        mut,
+        constraint = admin.key() == ADMIN @ SolmatesError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"verifier", verifier_record.verifier.as_ref()],
+        bump = verifier_record.bump,
+        close = admin
+    )]
+    pub verifier_record: Account<'info, Verifier>,
+}
+
+pub fn handler(ctx: Context<RemoveVerifier>) -> Result<()> {
+    emit!(VerifierRemoved {
+        verifier: ctx.accounts.verifier_record.verifier,
+    });
+
+    // Account will be closed, rent returned to admin
+
+    Ok(())
+}
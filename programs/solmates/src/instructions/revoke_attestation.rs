@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::SolmatesError;
+use crate::events::AttestationRevoked;
+use crate::states::Attestation;
+
+/// The issuing verifier withdraws an attestation. The account is kept so the
+/// revocation stays visible on-chain.
+#[derive(Accounts)]
+pub struct RevokeAttestation<'info> {
+    pub verifier: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"attestation",
+            attestation.profile.as_ref(),
+            verifier.key().as_ref(),
+            [attestation.claim_type as u8].as_ref()
+        ],
+        bump = attestation.bump,
+        has_one = verifier,
+        constraint = attestation.version == Attestation::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub attestation: Account<'info, Attestation>,
+}
+
+pub fn handler(ctx: Context<RevokeAttestation>) -> Result<()> {
+    let attestation = &mut ctx.accounts.attestation;
+    attestation.revoked = true;
+
+    emit!(AttestationRevoked {
+        profile: attestation.profile,
+        verifier: attestation.verifier,
+        claim_type: attestation.claim_type,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::events::SessionRevoked;
+use crate::states::SessionDelegate;
+
+/// Revokes a session delegate before it expires and returns its rent to the authority.
+#[derive(Accounts)]
+pub struct RevokeSession<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"session", authority.key().as_ref(), session.delegate.as_ref()],
+        bump = session.bump,
+        has_one = authority,
+        close = authority
+    )]
+    pub session: Account<'info, SessionDelegate>,
+}
+
+pub fn handler(ctx: Context<RevokeSession>) -> Result<()> {
+    let session = &ctx.accounts.session;
+
+    emit!(SessionRevoked {
+        authority: session.authority,
+        delegate: session.delegate,
+    });
+
+    // Account will be closed, rent returned to authority
+
+    Ok(())
+}
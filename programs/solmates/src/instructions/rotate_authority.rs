@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::SolmatesError;
+use crate::events::AuthorityRotated;
+use crate::states::{GuardianSet, RecoveryRequest, UserProfile};
+use crate::RECOVERY_TIMELOCK;
+
+/// Executes an approved recovery once its timelock has passed. Permissionless - the new
+/// authority doesn't need its old wallet, and anyone may crank it.
+///
+/// Everything the profile owns stays keyed to its original identity, so the new
+/// authority can settle existing auctions, bounties and DMs right away. Sessions granted
+/// by the old authority stop working.
+#[derive(Accounts)]
+pub struct RotateAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"profile", profile.identity.as_ref()],
+        bump = profile.bump,
+        constraint = profile.version == UserProfile::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub profile: Account<'info, UserProfile>,
+
+    #[account(
+        seeds = [b"guardians", profile.key().as_ref()],
+        bump = guardian_set.bump,
+        constraint = guardian_set.version == GuardianSet::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub guardian_set: Account<'info, GuardianSet>,
+
+    /// CHECK: Guardian who opened the request - validated against it, receives the rent
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"recovery", profile.key().as_ref()],
+        bump = recovery.bump,
+        has_one = proposer,
+        close = proposer,
+        constraint = recovery.version == RecoveryRequest::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub recovery: Account<'info, RecoveryRequest>,
+}
+
+pub fn handler(ctx: Context<RotateAuthority>) -> Result<()> {
+    let guardian_set = &ctx.accounts.guardian_set;
+    let recovery = &ctx.accounts.recovery;
+
+    // Only approvals from guardians still in the set count - the authority may have
+    // replaced guardians since the request was opened
+    let approvals = recovery
+        .approvals
+        .iter()
+        .filter(|approver| guardian_set.is_guardian(approver))
+        .count();
+    require!(
+        approvals >= guardian_set.threshold as usize,
+        SolmatesError::RecoveryThresholdNotMet
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        now >= recovery
+            .initiated_at
+            .checked_add(RECOVERY_TIMELOCK)
+            .unwrap(),
+        SolmatesError::RecoveryTimelockActive
+    );
+
+    let profile = &mut ctx.accounts.profile;
+    let old_authority = profile.authority;
+    profile.authority = recovery.new_authority;
+
+    emit!(AuthorityRotated {
+        profile: profile.key(),
+        old_authority,
+        new_authority: profile.authority,
+    });
+
+    // Account will be closed, rent returned to proposer
+
+    Ok(())
+}
@@ -0,0 +1,170 @@
+use anchor_lang::prelude::*;
+use anchor_lang::AccountsClose;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::errors::SolmatesError;
+use crate::events::{AvailabilityChanged, EscrowRefunded};
+use crate::states::{Availability, EscrowStatus, Inbox, InboxPage, MessageEscrow, UserProfile};
+use crate::utils::unindex_escrow;
+
+/// Accounts per escrow declined in a batch: escrow, sender, mint, escrow vault, the
+/// sender's token account and the inbox page indexing the escrow (any account for escrows
+/// that predate inboxes)
+const DECLINE_BATCH_ACCOUNTS: usize = 6;
+
+/// Switches the profile between available and away. While away, new DMs, bids and
+/// auctions are rejected.
+///
+/// Pending escrows can be declined in the same call by passing them as remaining
+/// accounts, `DECLINE_BATCH_ACCOUNTS` per escrow, all under `token_program`.
+#[derive(Accounts)]
+pub struct SetAvailability<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"profile", profile.identity.as_ref()],
+        bump = profile.bump,
+        has_one = authority,
+        constraint = profile.version == UserProfile::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub profile: Account<'info, UserProfile>,
+
+    /// Optional: the profile's inbox, when declining escrows indexed in it
+    #[account(
+        mut,
+        seeds = [b"inbox", profile.identity.as_ref()],
+        bump = inbox.bump,
+        constraint = inbox.version == Inbox::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub inbox: Option<Account<'info, Inbox>>,
+
+    /// Optional: token program of the escrows being declined
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SetAvailability<'info>>,
+    availability: Availability,
+) -> Result<()> {
+    if let Availability::Away {
+        back_at: Some(back_at),
+    } = availability
+    {
+        require!(
+            back_at > Clock::get()?.unix_timestamp,
+            SolmatesError::InvalidBackAt
+        );
+    }
+
+    let profile = &mut ctx.accounts.profile;
+    profile.availability = availability;
+    let mut inbox = ctx.accounts.inbox.as_mut();
+
+    require!(
+        ctx.remaining_accounts.len() % DECLINE_BATCH_ACCOUNTS == 0,
+        SolmatesError::InvalidBatchAccounts
+    );
+    if !ctx.remaining_accounts.is_empty() {
+        let token_program = ctx
+            .accounts
+            .token_program
+            .as_ref()
+            .ok_or(SolmatesError::InvalidBatchAccounts)?;
+
+        for accounts in ctx.remaining_accounts.chunks(DECLINE_BATCH_ACCOUNTS) {
+            decline_pending(profile, inbox.as_deref_mut(), token_program, accounts)?;
+        }
+    }
+
+    emit!(AvailabilityChanged {
+        profile: profile.key(),
+        availability,
+    });
+
+    Ok(())
+}
+
+/// Refunds one pending escrow to its sender and closes it, like `decline_dm`.
+fn decline_pending<'info>(
+    profile: &mut Account<'info, UserProfile>,
+    inbox: Option<&mut Account<'info, Inbox>>,
+    token_program: &Interface<'info, TokenInterface>,
+    accounts: &'info [AccountInfo<'info>],
+) -> Result<()> {
+    let [escrow_info, sender, mint_info, vault_info, sender_token_info, page_info] = accounts
+    else {
+        return err!(SolmatesError::InvalidBatchAccounts);
+    };
+
+    let escrow = Account::<MessageEscrow>::try_from(escrow_info)?;
+    require!(
+        escrow.version == MessageEscrow::VERSION,
+        SolmatesError::AccountVersionMismatch
+    );
+    require_keys_eq!(
+        escrow.recipient,
+        profile.identity,
+        SolmatesError::Unauthorized
+    );
+    require!(
+        escrow.status == EscrowStatus::Pending,
+        SolmatesError::EscrowNotPending
+    );
+    require!(
+        escrow.sender == sender.key() && escrow.mint == mint_info.key(),
+        SolmatesError::InvalidBatchAccounts
+    );
+
+    let mint = InterfaceAccount::<Mint>::try_from(mint_info)?;
+    let vault = InterfaceAccount::<TokenAccount>::try_from(vault_info)?;
+    let sender_token_account = InterfaceAccount::<TokenAccount>::try_from(sender_token_info)?;
+    require!(
+        vault.owner == escrow.key()
+            && vault.mint == mint.key()
+            && sender_token_account.owner == escrow.sender
+            && sender_token_account.mint == mint.key(),
+        SolmatesError::InvalidBatchAccounts
+    );
+
+    let seeds = &[
+        b"escrow",
+        escrow.sender.as_ref(),
+        escrow.recipient.as_ref(),
+        &[escrow.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        TransferChecked {
+            from: vault.to_account_info(),
+            mint: mint.to_account_info(),
+            to: sender_token_account.to_account_info(),
+            authority: escrow.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token_interface::transfer_checked(transfer_ctx, escrow.amount, mint.decimals)?;
+
+    if escrow.inbox_page.is_some() {
+        let inbox = inbox.ok_or(SolmatesError::InboxRequired)?;
+        let mut page = Account::<InboxPage>::try_from(page_info)?;
+        require_keys_eq!(page.inbox, inbox.key(), SolmatesError::InvalidInboxPage);
+
+        unindex_escrow(&escrow.key(), &escrow, Some(inbox), Some(&mut page))?;
+        page.exit(&crate::ID)?;
+    }
+
+    profile.pending_escrows = profile.pending_escrows.saturating_sub(1);
+    profile.reputation.dms_declined = profile.reputation.dms_declined.saturating_add(1);
+
+    emit!(EscrowRefunded {
+        sender: escrow.sender,
+        recipient: escrow.recipient,
+        amount: escrow.amount,
+    });
+
+    // Escrow is settled, rent returned to sender
+    escrow.close(sender.clone())
+}
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::SolmatesError;
+use crate::events::GuardiansUpdated;
+use crate::states::{GuardianSet, UserProfile};
+use crate::MAX_GUARDIANS;
+
+/// Names the guardians who can recover the profile, and how many of them must agree.
+/// Calling it again replaces the set.
+#[derive(Accounts)]
+pub struct SetGuardians<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"profile", profile.identity.as_ref()],
+        bump = profile.bump,
+        has_one = authority,
+        constraint = profile.version == UserProfile::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub profile: Account<'info, UserProfile>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + GuardianSet::INIT_SPACE,
+        seeds = [b"guardians", profile.key().as_ref()],
+        bump
+    )]
+    pub guardian_set: Account<'info, GuardianSet>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<SetGuardians>, guardians: Vec<Pubkey>, threshold: u8) -> Result<()> {
+    let authority = ctx.accounts.authority.key();
+
+    require!(
+        !guardians.is_empty() && guardians.len() <= MAX_GUARDIANS,
+        SolmatesError::InvalidGuardians
+    );
+    for (i, guardian) in guardians.iter().enumerate() {
+        require!(
+            *guardian != authority && !guardians[..i].contains(guardian),
+            SolmatesError::InvalidGuardians
+        );
+    }
+    require!(
+        threshold >= 1 && threshold as usize <= guardians.len(),
+        SolmatesError::InvalidGuardianThreshold
+    );
+
+    let guardian_set = &mut ctx.accounts.guardian_set;
+    guardian_set.version = GuardianSet::VERSION;
+    guardian_set.profile = ctx.accounts.profile.key();
+    guardian_set.guardians = guardians.clone();
+    guardian_set.threshold = threshold;
+    guardian_set.bump = ctx.bumps.guardian_set;
+
+    emit!(GuardiansUpdated {
+        profile: guardian_set.profile,
+        guardians,
+        threshold,
+    });
+
+    Ok(())
+}
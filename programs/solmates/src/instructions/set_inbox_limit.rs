@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::SolmatesError;
+use crate::events::InboxLimitSet;
+use crate::states::{Inbox, UserProfile};
+
+/// Caps how many DM escrows can be pending for the profile at once. 0 removes the cap.
+/// Lowering it below the current count only blocks new deposits.
+#[derive(Accounts)]
+pub struct SetInboxLimit<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"profile", profile.identity.as_ref()],
+        bump = profile.bump,
+        has_one = authority,
+        constraint = profile.version == UserProfile::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub profile: Account<'info, UserProfile>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + Inbox::INIT_SPACE,
+        seeds = [b"inbox", profile.identity.as_ref()],
+        bump
+    )]
+    pub inbox: Account<'info, Inbox>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<SetInboxLimit>, max_pending: u32) -> Result<()> {
+    let inbox = &mut ctx.accounts.inbox;
+    if inbox.version == 0 {
+        inbox.version = Inbox::VERSION;
+        inbox.recipient = ctx.accounts.profile.identity;
+        inbox.bump = ctx.bumps.inbox;
+    }
+    require!(
+        inbox.version == Inbox::VERSION,
+        SolmatesError::AccountVersionMismatch
+    );
+    inbox.max_pending = max_pending;
+
+    emit!(InboxLimitSet {
+        recipient: inbox.recipient,
+        max_pending,
+    });
+
+    Ok(())
+}
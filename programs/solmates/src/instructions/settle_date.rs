@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::errors::SolmatesError;
+use crate::events::DateSettled;
+use crate::states::DateBooking;
+use crate::utils::pay_from_booking;
+
+/// Permissionless: pays out a fully staked date. Both confirmed - each side gets their
+/// stake back, at once. After the attendance window, a lone confirmer takes both stakes
+/// and with no confirmations each side is refunded.
+#[derive(Accounts)]
+pub struct SettleDate<'info> {
+    /// CHECK: Wallet that paid the booking's rent - validated against the booking
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"date", booking.pair_match.as_ref()],
+        bump = booking.bump,
+        has_one = payer,
+        has_one = mint,
+        close = payer,
+        constraint = booking.is_staked() @ SolmatesError::DateNotStaked,
+        constraint = booking.version == DateBooking::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub booking: Account<'info, DateBooking>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = booking,
+        associated_token::token_program = token_program
+    )]
+    pub booking_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Wallet that staked for user_a - validated against the booking
+    #[account(address = booking.party_a.staker.unwrap_or_default() @ SolmatesError::Unauthorized)]
+    pub staker_a: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = staker_a,
+        associated_token::token_program = token_program
+    )]
+    pub staker_a_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Wallet that staked for user_b - validated against the booking
+    #[account(address = booking.party_b.staker.unwrap_or_default() @ SolmatesError::Unauthorized)]
+    pub staker_b: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = staker_b,
+        associated_token::token_program = token_program
+    )]
+    pub staker_b_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler(ctx: Context<SettleDate>) -> Result<()> {
+    let booking = &ctx.accounts.booking;
+    let (party_a, party_b) = (booking.party_a, booking.party_b);
+
+    if !(party_a.confirmed && party_b.confirmed) {
+        require!(
+            Clock::get()?.unix_timestamp > booking.attendance_closes_at(),
+            SolmatesError::AttendanceWindowOpen
+        );
+    }
+
+    // A lone confirmer takes the whole vault, so rounding never strands tokens
+    let pool = ctx.accounts.booking_vault.amount;
+    let (a_amount, b_amount) = match (party_a.confirmed, party_b.confirmed) {
+        (true, false) => (pool, 0),
+        (false, true) => (0, pool),
+        _ => (party_a.deposit, party_b.deposit),
+    };
+
+    pay_from_booking(
+        booking,
+        &ctx.accounts.booking_vault,
+        &ctx.accounts.mint,
+        &ctx.accounts.staker_a_token_account,
+        &ctx.accounts.token_program,
+        a_amount,
+    )?;
+    pay_from_booking(
+        booking,
+        &ctx.accounts.booking_vault,
+        &ctx.accounts.mint,
+        &ctx.accounts.staker_b_token_account,
+        &ctx.accounts.token_program,
+        b_amount,
+    )?;
+
+    emit!(DateSettled {
+        pair_match: booking.pair_match,
+        user_a: party_a.identity,
+        user_b: party_b.identity,
+        a_amount,
+        b_amount,
+    });
+
+    // Account will be closed, rent returned to payer
+
+    Ok(())
+}
@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::errors::SolmatesError;
+use crate::events::DateStaked;
+use crate::states::{DateBooking, Match, UserProfile};
+
+/// The other side of a booked date matches the booker's stake before the scheduled time.
+#[derive(Accounts)]
+pub struct StakeDate<'info> {
+    pub staker: Signer<'info>,
+
+    #[account(
+        seeds = [b"profile", staker_profile.identity.as_ref()],
+        bump = staker_profile.bump,
+        constraint = staker_profile.authority == staker.key() @ SolmatesError::Unauthorized,
+        constraint = staker_profile.version == UserProfile::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub staker_profile: Account<'info, UserProfile>,
+
+    /// The pair must still be matched to commit to the date
+    #[account(
+        address = booking.pair_match,
+        constraint = pair_match.version == Match::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub pair_match: Account<'info, Match>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = staker,
+        associated_token::token_program = token_program
+    )]
+    pub staker_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"date", booking.pair_match.as_ref()],
+        bump = booking.bump,
+        has_one = mint,
+        constraint = booking.version == DateBooking::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub booking: Account<'info, DateBooking>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = booking,
+        associated_token::token_program = token_program
+    )]
+    pub booking_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler(ctx: Context<StakeDate>) -> Result<()> {
+    let booking = &mut ctx.accounts.booking;
+    require!(
+        Clock::get()?.unix_timestamp < booking.scheduled_at,
+        SolmatesError::DateStakingClosed
+    );
+    let stake = booking.stake;
+    let staker = ctx.accounts.staker_profile.identity;
+    require!(
+        booking.party_mut(&staker)?.staker.is_none(),
+        SolmatesError::AlreadyStaked
+    );
+
+    // Transfer USDC from staker to the booking vault
+    let vault_balance_before = ctx.accounts.booking_vault.amount;
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.staker_token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            to: ctx.accounts.booking_vault.to_account_info(),
+            authority: ctx.accounts.staker.to_account_info(),
+        },
+    );
+    token_interface::transfer_checked(transfer_ctx, stake, ctx.accounts.mint.decimals)?;
+
+    // Credit only what actually arrived - transfer-fee mints withhold part of the amount
+    ctx.accounts.booking_vault.reload()?;
+    let received = ctx
+        .accounts
+        .booking_vault
+        .amount
+        .checked_sub(vault_balance_before)
+        .unwrap();
+
+    let staker_key = ctx.accounts.staker.key();
+    let booking = &mut ctx.accounts.booking;
+    let party = booking.party_mut(&staker)?;
+    party.staker = Some(staker_key);
+    party.deposit = received;
+
+    emit!(DateStaked {
+        pair_match: booking.pair_match,
+        user: staker,
+        amount: received,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::SolmatesError;
+use crate::events::SubmissionCreated;
+use crate::states::{BountyStatus, BountyVault, Submission, SubmissionStatus};
+use crate::utils::load_matchmaker_record;
+
+/// Matchmaker proposes a candidate for an open bounty. The pitch itself stays off-chain;
+/// only its hash is recorded.
+#[derive(Accounts)]
+pub struct SubmitReferral<'info> {
+    #[account(mut)]
+    pub matchmaker: Signer<'info>,
+
+    #[account(
+        seeds = [b"bounty", bounty.issuer.as_ref()],
+        bump = bounty.bump,
+        constraint = bounty.version == BountyVault::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub bounty: Account<'info, BountyVault>,
+
+    #[account(
+        init,
+        payer = matchmaker,
+        space = 8 + Submission::INIT_SPACE,
+        seeds = [b"submission", bounty.key().as_ref(), matchmaker.key().as_ref()],
+        bump
+    )]
+    pub submission: Account<'info, Submission>,
+
+    /// CHECK: Matchmaker's registry record PDA - always passed. Registration is required
+    /// when the bounty sets a minimum track record, and a registered matchmaker's minimum
+    /// fee is enforced.
+    #[account(
+        seeds = [b"matchmaker", matchmaker.key().as_ref()],
+        bump
+    )]
+    pub matchmaker_record: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<SubmitReferral>, candidate: Pubkey, note_hash: [u8; 32]) -> Result<()> {
+    let bounty = &ctx.accounts.bounty;
+    require!(
+        bounty.status == BountyStatus::Open,
+        SolmatesError::BountyNotOpen
+    );
+    require!(
+        !bounty.is_expired(Clock::get()?.unix_timestamp),
+        SolmatesError::BountyExpired
+    );
+
+    let matchmaker = ctx.accounts.matchmaker.key();
+    require!(matchmaker != bounty.issuer, SolmatesError::Unauthorized);
+    require!(
+        candidate != bounty.issuer && candidate != matchmaker,
+        SolmatesError::InvalidCandidate
+    );
+
+    let matchmaker_record = load_matchmaker_record(&ctx.accounts.matchmaker_record)?;
+    if let Some(min_payouts) = bounty.min_matchmaker_payouts {
+        let matchmaker_record = matchmaker_record
+            .as_ref()
+            .ok_or(SolmatesError::MatchmakerNotRegistered)?;
+        require!(
+            matchmaker_record.successful_payouts >= min_payouts,
+            SolmatesError::InsufficientTrackRecord
+        );
+    }
+    if let Some(matchmaker_record) = &matchmaker_record {
+        require!(
+            bounty.reward_amount >= matchmaker_record.min_fee,
+            SolmatesError::RewardBelowMatchmakerFee
+        );
+    }
+
+    let submission = &mut ctx.accounts.submission;
+    submission.version = Submission::VERSION;
+    submission.bounty = bounty.key();
+    submission.bounty_created_at = bounty.created_at;
+    submission.matchmaker = matchmaker;
+    submission.candidate = candidate;
+    submission.note_hash = note_hash;
+    submission.submitted_at = Clock::get()?.unix_timestamp;
+    submission.status = SubmissionStatus::Pending;
+    submission.min_fee = matchmaker_record.map_or(0, |record| record.min_fee);
+    submission.bump = ctx.bumps.submission;
+
+    emit!(SubmissionCreated {
+        bounty: submission.bounty,
+        matchmaker,
+        candidate,
+        note_hash,
+    });
+
+    Ok(())
+}
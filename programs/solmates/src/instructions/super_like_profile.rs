@@ -0,0 +1,148 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::errors::SolmatesError;
+use crate::events::{ProfileLiked, SuperLikeSent};
+use crate::states::{Like, SuperLike, UserProfile};
+use crate::utils::validate_mint;
+use crate::SUPER_LIKE_WINDOW;
+
+/// Liker likes another profile and escrows tokens with it. The likee earns them by liking
+/// back within `SUPER_LIKE_WINDOW`.
+#[derive(Accounts)]
+pub struct SuperLikeProfile<'info> {
+    #[account(mut)]
+    pub liker: Signer<'info>,
+
+    #[account(
+        seeds = [b"profile", liker_profile.identity.as_ref()],
+        bump = liker_profile.bump,
+        constraint = liker_profile.authority == liker.key() @ SolmatesError::Unauthorized,
+        constraint = liker_profile.version == UserProfile::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub liker_profile: Account<'info, UserProfile>,
+
+    #[account(
+        seeds = [b"profile", likee_profile.identity.as_ref()],
+        bump = likee_profile.bump,
+        constraint = likee_profile.version == UserProfile::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub likee_profile: Account<'info, UserProfile>,
+
+    #[account(
+        init,
+        payer = liker,
+        space = 8 + Like::INIT_SPACE,
+        seeds = [b"like", liker_profile.identity.as_ref(), likee_profile.identity.as_ref()],
+        bump
+    )]
+    pub like: Account<'info, Like>,
+
+    /// CHECK: The likee's like of the liker, if any - only its existence is read
+    #[account(
+        seeds = [b"like", likee_profile.identity.as_ref(), liker_profile.identity.as_ref()],
+        bump
+    )]
+    pub reverse_like: UncheckedAccount<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = liker,
+        associated_token::token_program = token_program
+    )]
+    pub liker_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = liker,
+        space = 8 + SuperLike::INIT_SPACE,
+        seeds = [b"super_like", liker_profile.identity.as_ref(), likee_profile.identity.as_ref()],
+        bump
+    )]
+    pub super_like: Account<'info, SuperLike>,
+
+    #[account(
+        init_if_needed,
+        payer = liker,
+        associated_token::mint = mint,
+        associated_token::authority = super_like,
+        associated_token::token_program = token_program
+    )]
+    pub super_like_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<SuperLikeProfile>, amount: u64) -> Result<()> {
+    validate_mint(&ctx.accounts.mint)?;
+    require!(amount > 0, SolmatesError::InvalidSuperLikeAmount);
+
+    let liker = ctx.accounts.liker_profile.identity;
+    let likee = ctx.accounts.likee_profile.identity;
+    require_keys_neq!(liker, likee, SolmatesError::InvalidLike);
+
+    // A super-like pays for a like back - there's nothing to pay for once it happened
+    let reverse_like = &ctx.accounts.reverse_like;
+    require!(
+        reverse_like.owner != &crate::ID || reverse_like.data_is_empty(),
+        SolmatesError::AlreadyLikedBack
+    );
+
+    // Transfer USDC from liker to the super-like vault
+    let vault_balance_before = ctx.accounts.super_like_vault.amount;
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.liker_token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            to: ctx.accounts.super_like_vault.to_account_info(),
+            authority: ctx.accounts.liker.to_account_info(),
+        },
+    );
+    token_interface::transfer_checked(transfer_ctx, amount, ctx.accounts.mint.decimals)?;
+
+    // Escrow only what actually arrived - transfer-fee mints withhold part of the amount
+    ctx.accounts.super_like_vault.reload()?;
+    let received = ctx
+        .accounts
+        .super_like_vault
+        .amount
+        .checked_sub(vault_balance_before)
+        .unwrap();
+
+    let current_time = Clock::get()?.unix_timestamp;
+    let like = &mut ctx.accounts.like;
+    like.version = Like::VERSION;
+    like.liker = liker;
+    like.likee = likee;
+    like.liked_at = current_time;
+    like.bump = ctx.bumps.like;
+
+    let super_like = &mut ctx.accounts.super_like;
+    super_like.version = SuperLike::VERSION;
+    super_like.liker = liker;
+    super_like.likee = likee;
+    super_like.sender = ctx.accounts.liker.key();
+    super_like.mint = ctx.accounts.mint.key();
+    super_like.amount = received;
+    super_like.expiry = current_time.checked_add(SUPER_LIKE_WINDOW).unwrap();
+    super_like.bump = ctx.bumps.super_like;
+
+    emit!(ProfileLiked { liker, likee });
+    emit!(SuperLikeSent {
+        liker,
+        likee,
+        amount: received,
+        expiry: super_like.expiry,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::SolmatesError;
+use crate::events::HandleTransferred;
+use crate::states::{HandleRecord, UserProfile};
+
+/// Moves the profile's handle to another profile that doesn't have one yet. Both
+/// authorities sign, so a handle can't be pushed onto a profile that didn't ask for it.
+#[derive(Accounts)]
+pub struct TransferHandle<'info> {
+    pub authority: Signer<'info>,
+
+    pub new_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"profile", profile.identity.as_ref()],
+        bump = profile.bump,
+        has_one = authority,
+        constraint = profile.version == UserProfile::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub profile: Account<'info, UserProfile>,
+
+    #[account(
+        mut,
+        seeds = [b"profile", new_profile.identity.as_ref()],
+        bump = new_profile.bump,
+        constraint = new_profile.key() != profile.key() @ SolmatesError::Unauthorized,
+        constraint = new_profile.authority == new_authority.key() @ SolmatesError::Unauthorized,
+        constraint = new_profile.handle.is_none() @ SolmatesError::ProfileAlreadyHasHandle,
+        constraint = new_profile.version == UserProfile::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub new_profile: Account<'info, UserProfile>,
+
+    #[account(
+        mut,
+        seeds = [b"handle", handle_record.handle.as_bytes()],
+        bump = handle_record.bump,
+        has_one = profile @ SolmatesError::HandleNotOwned,
+        constraint = handle_record.version == HandleRecord::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub handle_record: Account<'info, HandleRecord>,
+}
+
+pub fn handler(ctx: Context<TransferHandle>) -> Result<()> {
+    let record = &mut ctx.accounts.handle_record;
+    record.profile = ctx.accounts.new_profile.key();
+    record.claimed_at = Clock::get()?.unix_timestamp;
+
+    ctx.accounts.new_profile.handle = ctx.accounts.profile.handle.take();
+
+    emit!(HandleTransferred {
+        handle: record.handle.clone(),
+        from_profile: ctx.accounts.profile.key(),
+        to_profile: record.profile,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::SolmatesError;
+use crate::events::{LikeRemoved, MatchClosed};
+use crate::states::{Like, Match, UserProfile};
+
+/// Liker withdraws their like, dissolving the pair's match if there is one. The other
+/// side's like stays until they withdraw it too. A match with a date booking can't be
+/// dissolved until the booking is settled or cancelled.
+#[derive(Accounts)]
+pub struct Unmatch<'info> {
+    #[account(mut)]
+    pub liker: Signer<'info>,
+
+    #[account(
+        seeds = [b"profile", liker_profile.identity.as_ref()],
+        bump = liker_profile.bump,
+        constraint = liker_profile.authority == liker.key() @ SolmatesError::Unauthorized,
+        constraint = liker_profile.version == UserProfile::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub liker_profile: Account<'info, UserProfile>,
+
+    #[account(
+        mut,
+        seeds = [b"like", liker_profile.identity.as_ref(), like.likee.as_ref()],
+        bump = like.bump,
+        close = liker,
+        constraint = like.version == Like::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub like: Account<'info, Like>,
+
+    /// CHECK: The pair's match PDA, passed whether or not it exists - closed when it does
+    #[account(
+        mut,
+        seeds = [
+            b"match",
+            Match::pair(like.liker, like.likee).0.as_ref(),
+            Match::pair(like.liker, like.likee).1.as_ref()
+        ],
+        bump
+    )]
+    pub pair_match: UncheckedAccount<'info>,
+
+    /// CHECK: The match's date booking PDA, passed whether or not it exists - the match
+    /// can't be dissolved while a booking holds stakes against it
+    #[account(
+        seeds = [b"date", pair_match.key().as_ref()],
+        bump
+    )]
+    pub date_booking: UncheckedAccount<'info>,
+
+    /// CHECK: Wallet that paid the match's rent - validated against the match when it
+    /// exists, ignored otherwise
+    #[account(mut)]
+    pub match_payer: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<Unmatch>) -> Result<()> {
+    let like = &ctx.accounts.like;
+
+    let match_info = ctx.accounts.pair_match.to_account_info();
+    if match_info.owner == &crate::ID && !match_info.data_is_empty() {
+        let pair_match = Match::try_deserialize(&mut &match_info.try_borrow_data()?[..])?;
+        require!(
+            pair_match.version == Match::VERSION,
+            SolmatesError::AccountVersionMismatch
+        );
+        require_keys_eq!(
+            ctx.accounts.match_payer.key(),
+            pair_match.payer,
+            SolmatesError::MatchPayerMismatch
+        );
+
+        // Settled and cancelled bookings are closed, so any booking left is still live
+        let booking_info = ctx.accounts.date_booking.to_account_info();
+        require!(
+            booking_info.owner != &crate::ID || booking_info.data_is_empty(),
+            SolmatesError::DateBookingActive
+        );
+
+        emit!(MatchClosed {
+            user_a: pair_match.user_a,
+            user_b: pair_match.user_b,
+            closed_by: like.liker,
+        });
+
+        // Close by hand - the PDA is unchecked since it may not exist
+        let match_payer = ctx.accounts.match_payer.to_account_info();
+        let rent = match_info.lamports();
+        **match_payer.try_borrow_mut_lamports()? =
+            match_payer.lamports().checked_add(rent).unwrap();
+        **match_info.try_borrow_mut_lamports()? = 0;
+        match_info.assign(&System::id());
+        match_info.resize(0)?;
+    }
+
+    emit!(LikeRemoved {
+        liker: like.liker,
+        likee: like.likee,
+    });
+
+    // Like will be closed, rent returned to the liker
+
+    Ok(())
+}
@@ -1,67 +1,121 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 
 use crate::errors::SolmatesError;
 use crate::events::BountyUpdated;
-use crate::states::{BountyStatus, BountyVault};
+use crate::states::{BountyStatus, BountyVault, UserProfile};
 
 #[derive(Accounts)]
 pub struct UpdateBounty<'info> {
     #[account(mut)]
     pub issuer: Signer<'info>,
 
-    pub mint: Account<'info, Mint>,
+    #[account(
+        seeds = [b"profile", bounty.issuer.as_ref()],
+        bump = issuer_profile.bump,
+        constraint = issuer_profile.authority == issuer.key() @ SolmatesError::Unauthorized,
+        constraint = issuer_profile.version == UserProfile::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub issuer_profile: Account<'info, UserProfile>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         mut,
-        seeds = [b"bounty", issuer.key().as_ref()],
+        seeds = [b"bounty", bounty.issuer.as_ref()],
         bump = bounty.bump,
-        has_one = issuer,
-        has_one = mint
+        has_one = mint,
+        constraint = bounty.version == BountyVault::VERSION @ SolmatesError::AccountVersionMismatch
     )]
     pub bounty: Account<'info, BountyVault>,
 
     #[account(
         mut,
         associated_token::mint = mint,
-        associated_token::authority = bounty
+        associated_token::authority = bounty,
+        associated_token::token_program = token_program
     )]
-    pub bounty_vault: Account<'info, TokenAccount>,
+    pub bounty_vault: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         mut,
         associated_token::mint = mint,
-        associated_token::authority = issuer
+        associated_token::authority = issuer,
+        associated_token::token_program = token_program
     )]
-    pub issuer_token_account: Account<'info, TokenAccount>,
+    pub issuer_token_account: InterfaceAccount<'info, TokenAccount>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
-pub fn handler(ctx: Context<UpdateBounty>, new_amount: u64) -> Result<()> {
+pub fn handler(
+    ctx: Context<UpdateBounty>,
+    new_amount: u64,
+    expires_at: Option<Option<i64>>,
+) -> Result<()> {
     let bounty = &ctx.accounts.bounty;
 
     require!(
         bounty.status == BountyStatus::Open,
         SolmatesError::BountyNotOpen
     );
+    require!(
+        !bounty.is_claim_locked(),
+        SolmatesError::BountyClaimPending
+    );
+    // Milestone amounts are fixed at creation
+    require!(
+        bounty.milestones.is_empty() || new_amount == bounty.reward_amount,
+        SolmatesError::MilestoneBounty
+    );
+    require!(
+        new_amount >= bounty.contributed_amount,
+        SolmatesError::BountyBelowContributions
+    );
+    if let Some(new_expiry) = expires_at {
+        let current_time = Clock::get()?.unix_timestamp;
+        // An expired bounty is headed for refunds - it can't be revived
+        require!(
+            !bounty.is_expired(current_time),
+            SolmatesError::BountyExpired
+        );
+        if let Some(expires_at) = new_expiry {
+            require!(
+                expires_at > current_time,
+                SolmatesError::InvalidBountyExpiry
+            );
+        }
+    }
 
     let current_amount = bounty.reward_amount;
     let issuer_key = bounty.issuer;
     let bump = bounty.bump;
+    let mut updated_amount = new_amount;
 
     if new_amount > current_amount {
         // Deposit difference
         let diff = new_amount.checked_sub(current_amount).unwrap();
         let transfer_ctx = CpiContext::new(
             ctx.accounts.token_program.to_account_info(),
-            Transfer {
+            TransferChecked {
                 from: ctx.accounts.issuer_token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
                 to: ctx.accounts.bounty_vault.to_account_info(),
                 authority: ctx.accounts.issuer.to_account_info(),
             },
         );
-        token::transfer(transfer_ctx, diff)?;
+        let vault_balance_before = ctx.accounts.bounty_vault.amount;
+        token_interface::transfer_checked(transfer_ctx, diff, ctx.accounts.mint.decimals)?;
+
+        // Only credit what actually arrived - transfer-fee mints withhold part of the amount
+        ctx.accounts.bounty_vault.reload()?;
+        let received = ctx
+            .accounts
+            .bounty_vault
+            .amount
+            .checked_sub(vault_balance_before)
+            .unwrap();
+        updated_amount = current_amount.checked_add(received).unwrap();
     } else if new_amount < current_amount {
         // Withdraw difference
         let diff = current_amount.checked_sub(new_amount).unwrap();
@@ -70,23 +124,28 @@ pub fn handler(ctx: Context<UpdateBounty>, new_amount: u64) -> Result<()> {
 
         let transfer_ctx = CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
-            Transfer {
+            TransferChecked {
                 from: ctx.accounts.bounty_vault.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
                 to: ctx.accounts.issuer_token_account.to_account_info(),
                 authority: ctx.accounts.bounty.to_account_info(),
             },
             signer_seeds,
         );
-        token::transfer(transfer_ctx, diff)?;
+        token_interface::transfer_checked(transfer_ctx, diff, ctx.accounts.mint.decimals)?;
     }
 
     // Update state after CPIs
     let bounty = &mut ctx.accounts.bounty;
-    bounty.reward_amount = new_amount;
+    bounty.reward_amount = updated_amount;
+    if let Some(expires_at) = expires_at {
+        bounty.expires_at = expires_at;
+    }
 
     emit!(BountyUpdated {
         issuer: issuer_key,
-        new_amount,
+        new_amount: updated_amount,
+        expires_at: bounty.expires_at,
     });
 
     Ok(())
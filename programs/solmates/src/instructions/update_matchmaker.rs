@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::SolmatesError;
+use crate::events::MatchmakerUpdated;
+use crate::states::MatchmakerProfile;
+
+/// Matchmaker changes their advertised terms. The track record can't be edited.
+#[derive(Accounts)]
+pub struct UpdateMatchmaker<'info> {
+    pub matchmaker: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"matchmaker", matchmaker.key().as_ref()],
+        bump = matchmaker_record.bump,
+        has_one = matchmaker,
+        constraint = matchmaker_record.version == MatchmakerProfile::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub matchmaker_record: Account<'info, MatchmakerProfile>,
+}
+
+pub fn handler(
+    ctx: Context<UpdateMatchmaker>,
+    min_fee: Option<u64>,
+    specialties_hash: Option<[u8; 32]>,
+) -> Result<()> {
+    let record = &mut ctx.accounts.matchmaker_record;
+
+    if let Some(min_fee) = min_fee {
+        record.min_fee = min_fee;
+    }
+    if let Some(specialties_hash) = specialties_hash {
+        record.specialties_hash = specialties_hash;
+    }
+
+    emit!(MatchmakerUpdated {
+        matchmaker: record.matchmaker,
+        min_fee: record.min_fee,
+        specialties_hash: record.specialties_hash,
+    });
+
+    Ok(())
+}
@@ -1,17 +1,32 @@
 use anchor_lang::prelude::*;
 
-use crate::events::ProfileUpdated;
-use crate::states::UserProfile;
+use crate::errors::SolmatesError;
+use crate::events::{DmPricingSet, KeyRotated, ProfileUpdated};
+use crate::states::{AccessGate, DmPricing, SessionDelegate, UserProfile};
+use crate::utils::{authorize_session, validate_encryption_key};
 
 #[derive(Accounts)]
 pub struct UpdateProfile<'info> {
-    pub authority: Signer<'info>,
+    /// CHECK: Profile authority - validated against the profile
+    pub authority: UncheckedAccount<'info>,
+
+    /// Authority, or a session delegate acting for them
+    pub signer: Signer<'info>,
+
+    /// Optional: session the authority granted to `signer`, when `signer` is a delegate
+    #[account(
+        seeds = [b"session", authority.key().as_ref(), signer.key().as_ref()],
+        bump = session.bump,
+        constraint = session.version == SessionDelegate::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub session: Option<Account<'info, SessionDelegate>>,
 
     #[account(
         mut,
-        seeds = [b"profile", authority.key().as_ref()],
+        seeds = [b"profile", profile.identity.as_ref()],
         bump = profile.bump,
-        has_one = authority
+        has_one = authority,
+        constraint = profile.version == UserProfile::VERSION @ SolmatesError::AccountVersionMismatch
     )]
     pub profile: Account<'info, UserProfile>,
 }
@@ -19,19 +34,59 @@ pub struct UpdateProfile<'info> {
 pub fn handler(
     ctx: Context<UpdateProfile>,
     dm_price: Option<u64>,
-    asset_gate_mint: Option<Option<Pubkey>>,
-    min_asset_amount: Option<u64>,
+    gate: Option<Option<AccessGate>>,
+    encryption_key: Option<Option<[u8; 32]>>,
+    pricing: Option<Option<DmPricing>>,
 ) -> Result<()> {
+    authorize_session(
+        &ctx.accounts.authority.key(),
+        &ctx.accounts.signer.key(),
+        ctx.accounts.session.as_deref(),
+        SessionDelegate::SCOPE_UPDATE_PROFILE,
+    )?;
+
     let profile = &mut ctx.accounts.profile;
 
     if let Some(price) = dm_price {
         profile.dm_price = price;
     }
-    if let Some(gate) = asset_gate_mint {
-        profile.asset_gate_mint = gate;
+    if let Some(gate) = gate {
+        if let Some(gate) = &gate {
+            gate.validate()?;
+        }
+        profile.gate = gate;
     }
-    if let Some(amount) = min_asset_amount {
-        profile.min_asset_amount = amount;
+    if let Some(encryption_key) = encryption_key {
+        // A session delegate must never be able to swap in a key it can read with
+        require_keys_eq!(
+            ctx.accounts.signer.key(),
+            profile.authority,
+            SolmatesError::Unauthorized
+        );
+        if let Some(key) = &encryption_key {
+            validate_encryption_key(key)?;
+        }
+
+        profile.encryption_key = encryption_key;
+        profile.encryption_key_version = profile.encryption_key_version.checked_add(1).unwrap();
+
+        emit!(KeyRotated {
+            profile: profile.key(),
+            encryption_key,
+            key_version: profile.encryption_key_version,
+        });
+    }
+
+    if let Some(pricing) = pricing {
+        if let Some(pricing) = &pricing {
+            pricing.validate()?;
+        }
+        profile.pricing = pricing;
+
+        emit!(DmPricingSet {
+            profile: profile.key(),
+            pricing,
+        });
     }
 
     emit!(ProfileUpdated {
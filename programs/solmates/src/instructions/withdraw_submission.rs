@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::SolmatesError;
+use crate::events::SubmissionWithdrawn;
+use crate::states::Submission;
+
+/// Matchmaker withdraws their submission and reclaims its rent. Also works once the
+/// submission was rejected or its bounty has closed.
+#[derive(Accounts)]
+pub struct WithdrawSubmission<'info> {
+    #[account(mut)]
+    pub matchmaker: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"submission", submission.bounty.as_ref(), matchmaker.key().as_ref()],
+        bump = submission.bump,
+        has_one = matchmaker,
+        close = matchmaker,
+        constraint = !submission.is_claimed() @ SolmatesError::BountyClaimPending,
+        constraint = submission.version == Submission::VERSION @ SolmatesError::AccountVersionMismatch
+    )]
+    pub submission: Account<'info, Submission>,
+}
+
+pub fn handler(ctx: Context<WithdrawSubmission>) -> Result<()> {
+    emit!(SubmissionWithdrawn {
+        bounty: ctx.accounts.submission.bounty,
+        matchmaker: ctx.accounts.matchmaker.key(),
+    });
+
+    // Account will be closed, rent returned to matchmaker
+
+    Ok(())
+}
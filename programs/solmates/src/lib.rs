@@ -4,8 +4,12 @@ pub mod errors;
 pub mod events;
 pub mod instructions;
 pub mod states;
+pub mod utils;
 
 use instructions::*;
+use states::{
+    AccessGate, Availability, BidderRequirements, ClaimType, DmPricing, Milestone, PayoutMode,
+};
 
 declare_id!("4G4MoTN3yYJbCWSHQtKoKK645xrbw2C3yDiy52n8rSrb");
 
@@ -19,10 +23,31 @@ pub const SNIPE_EXTENSION: i64 = 300; // 5 minutes extension (5 * 60)
 pub const MAX_SNIPE_EXTENSIONS: i64 = 3600; // Maximum 1 hour total extension
 pub const PLATFORM_FEE_BPS: u64 = 100; // 1% = 100 basis points
 pub const MIN_BID_INCREMENT_BPS: u64 = 500; // 5% minimum bid increment
+pub const MAX_GATE_RULES: usize = 4; // Maximum rules in a single access gate
+pub const MAX_BIDDER_ALLOWLIST: usize = 10; // Maximum wallets on an auction allowlist
+pub const MAX_SESSION_DURATION: i64 = 604800; // 7 days in seconds (7 * 24 * 60 * 60)
+pub const MAX_GUARDIANS: usize = 5; // Maximum guardians on a profile
+pub const RECOVERY_TIMELOCK: i64 = 259200; // 72 hours in seconds (72 * 60 * 60)
+pub const MIN_HANDLE_LEN: usize = 3; // Shortest claimable handle
+pub const MAX_HANDLE_LEN: usize = 20; // Longest claimable handle
+pub const HANDLE_FEE_LAMPORTS: u64 = 10_000_000; // 0.01 SOL to the treasury per claim
+pub const MAX_VERIFIER_NAME_LEN: usize = 32; // Longest verifier display name
+pub const INBOX_PAGE_SIZE: usize = 16; // Escrow addresses per inbox page
+pub const PRICING_WINDOW: i64 = 86400; // 24 hours of acceptances drive dynamic DM pricing
+pub const CONSENT_TIMEOUT: i64 = 604800; // 7 days to object before a claimed bounty releases
+pub const DISPUTE_WINDOW: i64 = 259200; // 72 hours for both parties to settle a dispute
+pub const MAX_BOUNTY_CONTRIBUTORS: usize = 16; // Contributors per bounty - all are passed at payout
+pub const MAX_SPLIT_RECIPIENTS: usize = 5; // Matchmakers sharing a split bounty payout
+pub const MAX_MILESTONES: usize = 3; // Staged payouts on a single bounty
+pub const SUPER_LIKE_WINDOW: i64 = 259200; // 72 hours for a like back to earn a super-like
+pub const ATTENDANCE_WINDOW: i64 = 172800; // 48 hours after a date to confirm attendance
 
 // Platform treasury address for fee collection
 pub const TREASURY: Pubkey = pubkey!("2CquYcQoBGv8MiiMfP3Lgut79oLCtDbCTrB6fnQm1WeG");
 
+// Platform admin - manages the verifier registry
+pub const ADMIN: Pubkey = pubkey!("2CquYcQoBGv8MiiMfP3Lgut79oLCtDbCTrB6fnQm1WeG");
+
 // ============================================================================
 // PROGRAM
 // ============================================================================
@@ -38,19 +63,124 @@ pub mod solmates {
     pub fn create_profile(
         ctx: Context<CreateProfile>,
         dm_price: u64,
-        asset_gate_mint: Option<Pubkey>,
-        min_asset_amount: u64,
+        gate: Option<AccessGate>,
+        encryption_key: Option<[u8; 32]>,
     ) -> Result<()> {
-        instructions::create_profile::handler(ctx, dm_price, asset_gate_mint, min_asset_amount)
+        instructions::create_profile::handler(ctx, dm_price, gate, encryption_key)
     }
 
     pub fn update_profile(
         ctx: Context<UpdateProfile>,
         dm_price: Option<u64>,
-        asset_gate_mint: Option<Option<Pubkey>>,
-        min_asset_amount: Option<u64>,
+        gate: Option<Option<AccessGate>>,
+        encryption_key: Option<Option<[u8; 32]>>,
+        pricing: Option<Option<DmPricing>>,
+    ) -> Result<()> {
+        instructions::update_profile::handler(ctx, dm_price, gate, encryption_key, pricing)
+    }
+
+    pub fn close_profile(ctx: Context<CloseProfile>) -> Result<()> {
+        instructions::close_profile::handler(ctx)
+    }
+
+    pub fn quote_dm_price(ctx: Context<QuoteDmPrice>) -> Result<u64> {
+        instructions::quote_dm_price::handler(ctx)
+    }
+
+    pub fn set_inbox_limit(ctx: Context<SetInboxLimit>, max_pending: u32) -> Result<()> {
+        instructions::set_inbox_limit::handler(ctx, max_pending)
+    }
+
+    pub fn set_availability<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SetAvailability<'info>>,
+        availability: Availability,
+    ) -> Result<()> {
+        instructions::set_availability::handler(ctx, availability)
+    }
+
+    // ------------------------------------------------------------------------
+    // SESSION INSTRUCTIONS
+    // ------------------------------------------------------------------------
+
+    pub fn create_session(
+        ctx: Context<CreateSession>,
+        delegate: Pubkey,
+        scopes: u8,
+        duration_secs: i64,
+    ) -> Result<()> {
+        instructions::create_session::handler(ctx, delegate, scopes, duration_secs)
+    }
+
+    pub fn revoke_session(ctx: Context<RevokeSession>) -> Result<()> {
+        instructions::revoke_session::handler(ctx)
+    }
+
+    // ------------------------------------------------------------------------
+    // HANDLE INSTRUCTIONS
+    // ------------------------------------------------------------------------
+
+    pub fn claim_handle(ctx: Context<ClaimHandle>, handle: String) -> Result<()> {
+        instructions::claim_handle::handler(ctx, handle)
+    }
+
+    pub fn release_handle(ctx: Context<ReleaseHandle>) -> Result<()> {
+        instructions::release_handle::handler(ctx)
+    }
+
+    pub fn transfer_handle(ctx: Context<TransferHandle>) -> Result<()> {
+        instructions::transfer_handle::handler(ctx)
+    }
+
+    // ------------------------------------------------------------------------
+    // RECOVERY INSTRUCTIONS
+    // ------------------------------------------------------------------------
+
+    pub fn set_guardians(
+        ctx: Context<SetGuardians>,
+        guardians: Vec<Pubkey>,
+        threshold: u8,
     ) -> Result<()> {
-        instructions::update_profile::handler(ctx, dm_price, asset_gate_mint, min_asset_amount)
+        instructions::set_guardians::handler(ctx, guardians, threshold)
+    }
+
+    pub fn initiate_recovery(ctx: Context<InitiateRecovery>, new_authority: Pubkey) -> Result<()> {
+        instructions::initiate_recovery::handler(ctx, new_authority)
+    }
+
+    pub fn approve_recovery(ctx: Context<ApproveRecovery>) -> Result<()> {
+        instructions::approve_recovery::handler(ctx)
+    }
+
+    pub fn cancel_recovery(ctx: Context<CancelRecovery>) -> Result<()> {
+        instructions::cancel_recovery::handler(ctx)
+    }
+
+    pub fn rotate_authority(ctx: Context<RotateAuthority>) -> Result<()> {
+        instructions::rotate_authority::handler(ctx)
+    }
+
+    // ------------------------------------------------------------------------
+    // VERIFICATION INSTRUCTIONS
+    // ------------------------------------------------------------------------
+
+    pub fn add_verifier(ctx: Context<AddVerifier>, verifier: Pubkey, name: String) -> Result<()> {
+        instructions::add_verifier::handler(ctx, verifier, name)
+    }
+
+    pub fn remove_verifier(ctx: Context<RemoveVerifier>) -> Result<()> {
+        instructions::remove_verifier::handler(ctx)
+    }
+
+    pub fn issue_attestation(
+        ctx: Context<IssueAttestation>,
+        claim_type: ClaimType,
+        expires_at: i64,
+    ) -> Result<()> {
+        instructions::issue_attestation::handler(ctx, claim_type, expires_at)
+    }
+
+    pub fn revoke_attestation(ctx: Context<RevokeAttestation>) -> Result<()> {
+        instructions::revoke_attestation::handler(ctx)
     }
 
     // ------------------------------------------------------------------------
@@ -81,8 +211,9 @@ pub mod solmates {
         ctx: Context<CreateAuction>,
         start_price: u64,
         duration_secs: i64,
+        bidder_requirements: Option<BidderRequirements>,
     ) -> Result<()> {
-        instructions::create_auction::handler(ctx, start_price, duration_secs)
+        instructions::create_auction::handler(ctx, start_price, duration_secs, bidder_requirements)
     }
 
     pub fn place_bid(ctx: Context<PlaceBid>, bid_amount: u64) -> Result<()> {
@@ -97,23 +228,198 @@ pub mod solmates {
         instructions::cancel_auction::handler(ctx)
     }
 
+    // ------------------------------------------------------------------------
+    // MATCH INSTRUCTIONS
+    // ------------------------------------------------------------------------
+
+    pub fn like_profile(ctx: Context<LikeProfile>) -> Result<()> {
+        instructions::like_profile::handler(ctx)
+    }
+
+    pub fn unmatch(ctx: Context<Unmatch>) -> Result<()> {
+        instructions::unmatch::handler(ctx)
+    }
+
+    pub fn super_like_profile(ctx: Context<SuperLikeProfile>, amount: u64) -> Result<()> {
+        instructions::super_like_profile::handler(ctx, amount)
+    }
+
+    pub fn claim_super_like(ctx: Context<ClaimSuperLike>) -> Result<()> {
+        instructions::claim_super_like::handler(ctx)
+    }
+
+    pub fn refund_super_like(ctx: Context<RefundSuperLike>) -> Result<()> {
+        instructions::refund_super_like::handler(ctx)
+    }
+
+    // ------------------------------------------------------------------------
+    // DATE INSTRUCTIONS
+    // ------------------------------------------------------------------------
+
+    pub fn book_date(ctx: Context<BookDate>, stake: u64, scheduled_at: i64) -> Result<()> {
+        instructions::book_date::handler(ctx, stake, scheduled_at)
+    }
+
+    pub fn stake_date(ctx: Context<StakeDate>) -> Result<()> {
+        instructions::stake_date::handler(ctx)
+    }
+
+    pub fn confirm_attendance(ctx: Context<ConfirmAttendance>) -> Result<()> {
+        instructions::confirm_attendance::handler(ctx)
+    }
+
+    pub fn settle_date(ctx: Context<SettleDate>) -> Result<()> {
+        instructions::settle_date::handler(ctx)
+    }
+
+    pub fn cancel_date(ctx: Context<CancelDate>) -> Result<()> {
+        instructions::cancel_date::handler(ctx)
+    }
+
+    // ------------------------------------------------------------------------
+    // MATCHMAKER INSTRUCTIONS
+    // ------------------------------------------------------------------------
+
+    pub fn register_matchmaker(
+        ctx: Context<RegisterMatchmaker>,
+        min_fee: u64,
+        specialties_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::register_matchmaker::handler(ctx, min_fee, specialties_hash)
+    }
+
+    pub fn update_matchmaker(
+        ctx: Context<UpdateMatchmaker>,
+        min_fee: Option<u64>,
+        specialties_hash: Option<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::update_matchmaker::handler(ctx, min_fee, specialties_hash)
+    }
+
     // ------------------------------------------------------------------------
     // BOUNTY INSTRUCTIONS
     // ------------------------------------------------------------------------
 
-    pub fn create_bounty(ctx: Context<CreateBounty>, reward_amount: u64) -> Result<()> {
-        instructions::create_bounty::handler(ctx, reward_amount)
+    pub fn create_bounty(
+        ctx: Context<CreateBounty>,
+        reward_amount: u64,
+        payout_mode: PayoutMode,
+        expires_at: Option<i64>,
+        milestones: Vec<Milestone>,
+        min_matchmaker_payouts: Option<u32>,
+    ) -> Result<()> {
+        instructions::create_bounty::handler(
+            ctx,
+            reward_amount,
+            payout_mode,
+            expires_at,
+            milestones,
+            min_matchmaker_payouts,
+        )
     }
 
-    pub fn update_bounty(ctx: Context<UpdateBounty>, new_amount: u64) -> Result<()> {
-        instructions::update_bounty::handler(ctx, new_amount)
+    pub fn update_bounty(
+        ctx: Context<UpdateBounty>,
+        new_amount: u64,
+        expires_at: Option<Option<i64>>,
+    ) -> Result<()> {
+        instructions::update_bounty::handler(ctx, new_amount, expires_at)
+    }
+
+    pub fn contribute_to_bounty(ctx: Context<ContributeToBounty>, amount: u64) -> Result<()> {
+        instructions::contribute_to_bounty::handler(ctx, amount)
     }
 
-    pub fn payout_referral(ctx: Context<PayoutReferral>) -> Result<()> {
+    pub fn submit_referral(
+        ctx: Context<SubmitReferral>,
+        candidate: Pubkey,
+        note_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::submit_referral::handler(ctx, candidate, note_hash)
+    }
+
+    pub fn withdraw_submission(ctx: Context<WithdrawSubmission>) -> Result<()> {
+        instructions::withdraw_submission::handler(ctx)
+    }
+
+    pub fn reject_submission(ctx: Context<RejectSubmission>) -> Result<()> {
+        instructions::reject_submission::handler(ctx)
+    }
+
+    pub fn payout_referral<'info>(
+        ctx: Context<'_, '_, 'info, 'info, PayoutReferral<'info>>,
+    ) -> Result<()> {
         instructions::payout_referral::handler(ctx)
     }
 
+    pub fn payout_referral_split<'info>(
+        ctx: Context<'_, '_, 'info, 'info, PayoutReferralSplit<'info>>,
+        shares_bps: Vec<u16>,
+    ) -> Result<()> {
+        instructions::payout_referral_split::handler(ctx, shares_bps)
+    }
+
+    pub fn release_milestone(ctx: Context<ReleaseMilestone>, index: u8) -> Result<()> {
+        instructions::release_milestone::handler(ctx, index)
+    }
+
+    pub fn claim_referral(ctx: Context<ClaimReferral>) -> Result<()> {
+        instructions::claim_referral::handler(ctx)
+    }
+
+    pub fn confirm_referral(ctx: Context<ConfirmReferral>) -> Result<()> {
+        instructions::confirm_referral::handler(ctx)
+    }
+
+    pub fn dispute_referral(ctx: Context<DisputeReferral>) -> Result<()> {
+        instructions::dispute_referral::handler(ctx)
+    }
+
+    pub fn release_referral<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ReleaseReferral<'info>>,
+    ) -> Result<()> {
+        instructions::release_referral::handler(ctx)
+    }
+
+    pub fn expire_dispute(ctx: Context<ExpireDispute>) -> Result<()> {
+        instructions::expire_dispute::handler(ctx)
+    }
+
     pub fn cancel_bounty(ctx: Context<CancelBounty>) -> Result<()> {
         instructions::cancel_bounty::handler(ctx)
     }
+
+    pub fn refund_expired_bounty<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RefundExpiredBounty<'info>>,
+    ) -> Result<()> {
+        instructions::refund_expired_bounty::handler(ctx)
+    }
+
+    pub fn refund_contribution(ctx: Context<RefundContribution>) -> Result<()> {
+        instructions::refund_contribution::handler(ctx)
+    }
+
+    pub fn close_contribution(ctx: Context<CloseContribution>) -> Result<()> {
+        instructions::close_contribution::handler(ctx)
+    }
+
+    // ------------------------------------------------------------------------
+    // MIGRATION INSTRUCTIONS
+    // ------------------------------------------------------------------------
+
+    pub fn migrate_profile(ctx: Context<MigrateProfile>) -> Result<()> {
+        instructions::migrate_profile::handler(ctx)
+    }
+
+    pub fn migrate_escrow(ctx: Context<MigrateEscrow>) -> Result<()> {
+        instructions::migrate_escrow::handler(ctx)
+    }
+
+    pub fn migrate_auction(ctx: Context<MigrateAuction>) -> Result<()> {
+        instructions::migrate_auction::handler(ctx)
+    }
+
+    pub fn migrate_bounty(ctx: Context<MigrateBounty>) -> Result<()> {
+        instructions::migrate_bounty::handler(ctx)
+    }
 }
@@ -0,0 +1,120 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::SolmatesError;
+use crate::states::ClaimType;
+use crate::MAX_GATE_RULES;
+
+/// Holding requirements a counterparty must meet, e.g. before sending a paid DM or
+/// bidding on an auction.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct AccessGate {
+    pub mode: GateMode,
+    #[max_len(MAX_GATE_RULES)]
+    pub rules: Vec<GateRule>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum GateMode {
+    /// Satisfying a single rule is enough
+    AnyOf,
+    /// Every rule must be satisfied
+    AllOf,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum GateRule {
+    /// Hold at least `min_amount` of a fungible `mint`
+    Token { mint: Pubkey, min_amount: u64 },
+    /// Hold any NFT whose Metaplex metadata carries this verified `collection`
+    Collection { collection: Pubkey },
+    /// Have a live attestation of `claim_type` from a registered verifier
+    Attestation { claim_type: ClaimType },
+}
+
+impl AccessGate {
+    pub fn validate(&self) -> Result<()> {
+        require!(!self.rules.is_empty(), SolmatesError::InvalidAssetGate);
+        require!(
+            self.rules.len() <= MAX_GATE_RULES,
+            SolmatesError::TooManyGateRules
+        );
+
+        for rule in &self.rules {
+            match rule {
+                GateRule::Token { min_amount, .. } => {
+                    require!(*min_amount > 0, SolmatesError::InvalidAssetGate);
+                }
+                GateRule::Collection { .. } | GateRule::Attestation { .. } => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(min_amount: u64) -> GateRule {
+        GateRule::Token {
+            mint: Pubkey::new_unique(),
+            min_amount,
+        }
+    }
+
+    fn collection() -> GateRule {
+        GateRule::Collection {
+            collection: Pubkey::new_unique(),
+        }
+    }
+
+    fn gate(mode: GateMode, rules: Vec<GateRule>) -> AccessGate {
+        AccessGate { mode, rules }
+    }
+
+    #[test]
+    fn accepts_rules_up_to_the_limit() {
+        assert!(gate(GateMode::AnyOf, vec![token(1)]).validate().is_ok());
+        let rules = vec![
+            token(5),
+            collection(),
+            GateRule::Attestation {
+                claim_type: ClaimType::AgeOver18,
+            },
+            token(u64::MAX),
+        ];
+        assert_eq!(rules.len(), MAX_GATE_RULES);
+        assert!(gate(GateMode::AllOf, rules).validate().is_ok());
+    }
+
+    #[test]
+    fn rejects_a_gate_without_rules() {
+        assert_eq!(
+            gate(GateMode::AnyOf, vec![]).validate().unwrap_err(),
+            SolmatesError::InvalidAssetGate.into()
+        );
+    }
+
+    #[test]
+    fn rejects_too_many_rules() {
+        let rules = (0..=MAX_GATE_RULES).map(|_| collection()).collect();
+        assert_eq!(
+            gate(GateMode::AnyOf, rules).validate().unwrap_err(),
+            SolmatesError::TooManyGateRules.into()
+        );
+    }
+
+    #[test]
+    fn rejects_a_token_rule_with_no_minimum() {
+        // Any holder, even of nothing, would pass
+        for mode in [GateMode::AnyOf, GateMode::AllOf] {
+            assert_eq!(
+                gate(mode, vec![collection(), token(0)])
+                    .validate()
+                    .unwrap_err(),
+                SolmatesError::InvalidAssetGate.into()
+            );
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+/// A verifier's statement that a profile passed a check. One per (profile, verifier,
+/// claim type); re-issuing renews it.
+#[account]
+#[derive(InitSpace)]
+pub struct Attestation {
+    pub version: u8,
+    pub profile: Pubkey,
+    pub verifier: Pubkey,
+    pub claim_type: ClaimType,
+    pub issued_at: i64,
+    pub expires_at: i64, // 0 = never expires
+    pub revoked: bool,
+    pub bump: u8,
+    pub reserved: [u8; 32], // Room for new fields without a realloc
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum ClaimType {
+    /// Government ID matched to the profile owner
+    Identity,
+    /// Profile owner is at least 18
+    AgeOver18,
+    /// Profile photos match the owner
+    Photo,
+}
+
+impl Attestation {
+    pub const VERSION: u8 = 1;
+
+    pub fn is_valid(&self, current_time: i64) -> bool {
+        !self.revoked && (self.expires_at == 0 || current_time < self.expires_at)
+    }
+}
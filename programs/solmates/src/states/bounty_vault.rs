@@ -1,17 +1,95 @@
 use anchor_lang::prelude::*;
 
+use crate::errors::SolmatesError;
+use crate::MAX_MILESTONES;
+
 #[account]
 #[derive(InitSpace)]
 pub struct BountyVault {
+    pub version: u8,
     pub issuer: Pubkey,
     pub mint: Pubkey,
     pub reward_amount: u64,
     pub status: BountyStatus,
     pub bump: u8,
+    pub created_at: i64,     // Tells apart successive bounties at the issuer's PDA
+    pub payout_mode: PayoutMode,
+    pub active_claim: Option<Pubkey>, // Submission being paid - by consent claim or by milestones
+    pub expires_at: Option<i64>,      // After this anyone can refund the issuer
+    pub contributed_amount: u64,      // Part of reward_amount funded by contributors
+    pub contributor_count: u32,       // Contribution accounts still tied to the bounty
+    #[max_len(MAX_MILESTONES)]
+    pub milestones: Vec<Milestone>, // Staged payouts, released in order - empty pays in one go
+    pub min_matchmaker_payouts: Option<u32>, // Payouts a matchmaker needs on record to submit
+    pub reserved: [u8; 5],            // Room for new fields without a realloc
+}
+
+/// Part of a staged reward, such as the introduction, the first date or a month together.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct Milestone {
+    pub amount: u64,
+    pub unlock_at: i64, // Earliest release time, 0 = no lock
+    pub released: bool,
+}
+
+impl BountyVault {
+    pub const VERSION: u8 = 1;
+
+    pub fn is_expired(&self, current_time: i64) -> bool {
+        self.expires_at.is_some_and(|expires_at| current_time >= expires_at)
+    }
+
+    /// A consent claim is in flight, so the reward can't be pulled or changed.
+    pub fn is_claim_locked(&self) -> bool {
+        self.payout_mode == PayoutMode::Consent && self.active_claim.is_some()
+    }
+
+    pub fn validate_milestones(&self) -> Result<()> {
+        if self.milestones.is_empty() {
+            return Ok(());
+        }
+        require!(
+            self.milestones.len() <= MAX_MILESTONES,
+            SolmatesError::InvalidMilestones
+        );
+        // Milestones pay one matchmaker in stages, which a single consent claim can't do
+        require!(
+            self.payout_mode == PayoutMode::IssuerOnly,
+            SolmatesError::InvalidMilestones
+        );
+        for milestone in &self.milestones {
+            require!(
+                milestone.amount > 0 && milestone.unlock_at >= 0 && !milestone.released,
+                SolmatesError::InvalidMilestones
+            );
+        }
+        let total = self
+            .milestones
+            .iter()
+            .try_fold(0u64, |total, milestone| total.checked_add(milestone.amount));
+        require!(
+            total == Some(self.reward_amount),
+            SolmatesError::InvalidMilestones
+        );
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, InitSpace)]
+pub enum PayoutMode {
+    /// The issuer pays out alone with `payout_referral`
+    #[default]
+    IssuerOnly,
+    /// A matchmaker claims the reward, and it releases once the issuer and the candidate
+    /// both confirm, or once `CONSENT_TIMEOUT` passes without an objection
+    Consent,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
 pub enum BountyStatus {
     Open,
     Filled,
+    /// Cancelled or expired - the vault is being returned to contributors, and the
+    /// bounty closes with the last refund
+    Refunding,
 }
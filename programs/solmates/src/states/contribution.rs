@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+/// One wallet's share of a crowdfunded bounty's reward.
+#[account]
+#[derive(InitSpace)]
+pub struct Contribution {
+    pub version: u8,
+    pub bounty: Pubkey,
+    pub bounty_created_at: i64, // Bounty instance contributed to
+    pub contributor: Pubkey,
+    pub amount: u64, // Received into the vault, in mint base units
+    pub bump: u8,
+    pub reserved: [u8; 32], // Room for new fields without a realloc
+}
+
+impl Contribution {
+    pub const VERSION: u8 = 1;
+}
+
+/// A contributor credited in a bounty payout.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct ContributorCredit {
+    pub contributor: Pubkey,
+    pub amount: u64,
+}
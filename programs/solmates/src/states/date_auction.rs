@@ -1,8 +1,13 @@
 use anchor_lang::prelude::*;
 
+use crate::errors::SolmatesError;
+use crate::states::AccessGate;
+use crate::MAX_BIDDER_ALLOWLIST;
+
 #[account]
 #[derive(InitSpace)]
 pub struct DateAuction {
+    pub version: u8,
     pub host: Pubkey,
     pub auction_id: u64,
     pub mint: Pubkey,
@@ -10,5 +15,37 @@ pub struct DateAuction {
     pub highest_bid: u64,
     pub end_time: i64,
     pub total_extended: i64,  // Track total extension time for snipe protection cap
+    pub bidder_requirements: Option<BidderRequirements>,
     pub bump: u8,
+    pub reserved: [u8; 64], // Room for new fields without a realloc
+}
+
+impl DateAuction {
+    pub const VERSION: u8 = 1;
+}
+
+/// Conditions a wallet must meet before it can bid on an auction.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct BidderRequirements {
+    /// Token or NFT holdings the bidder must prove
+    pub gate: Option<AccessGate>,
+    /// Minimum age of the bidder's profile in seconds (0 = no profile required)
+    pub min_profile_age: i64,
+    /// Wallets allowed to bid (empty = anyone)
+    #[max_len(MAX_BIDDER_ALLOWLIST)]
+    pub allowlist: Vec<Pubkey>,
+}
+
+impl BidderRequirements {
+    pub fn validate(&self) -> Result<()> {
+        if let Some(gate) = &self.gate {
+            gate.validate()?;
+        }
+        require!(self.min_profile_age >= 0, SolmatesError::InvalidProfileAge);
+        require!(
+            self.allowlist.len() <= MAX_BIDDER_ALLOWLIST,
+            SolmatesError::AllowlistTooLong
+        );
+        Ok(())
+    }
 }
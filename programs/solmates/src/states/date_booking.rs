@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::SolmatesError;
+use crate::ATTENDANCE_WINDOW;
+
+/// A date between two matched profiles, backed by an equal stake from each side. Stakes
+/// come back when both show up; a no-show's stake goes to the one who did.
+#[account]
+#[derive(InitSpace)]
+pub struct DateBooking {
+    pub version: u8,
+    pub pair_match: Pubkey, // Match the date was booked under
+    pub mint: Pubkey,
+    pub stake: u64, // Amount each side sends, in mint base units
+    pub scheduled_at: i64,
+    pub party_a: DateParty, // Match's user_a
+    pub party_b: DateParty, // Match's user_b
+    pub payer: Pubkey,      // Wallet that paid the rent - refunded at settlement
+    pub bump: u8,
+    pub reserved: [u8; 32], // Room for new fields without a realloc
+}
+
+/// One side of a date booking.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct DateParty {
+    pub identity: Pubkey,       // Profile identity
+    pub staker: Option<Pubkey>, // Wallet that staked - receives this side's payout
+    pub deposit: u64,           // Received into the vault
+    pub confirmed: bool,        // Confirmed attending
+}
+
+impl DateBooking {
+    pub const VERSION: u8 = 1;
+
+    pub fn has_party(&self, identity: &Pubkey) -> bool {
+        self.party_a.identity == *identity || self.party_b.identity == *identity
+    }
+
+    pub fn party_mut(&mut self, identity: &Pubkey) -> Result<&mut DateParty> {
+        if self.party_a.identity == *identity {
+            Ok(&mut self.party_a)
+        } else if self.party_b.identity == *identity {
+            Ok(&mut self.party_b)
+        } else {
+            err!(SolmatesError::NotDateParty)
+        }
+    }
+
+    pub fn is_staked(&self) -> bool {
+        self.party_a.staker.is_some() && self.party_b.staker.is_some()
+    }
+
+    /// Last moment either side can confirm attendance.
+    pub fn attendance_closes_at(&self) -> i64 {
+        self.scheduled_at.saturating_add(ATTENDANCE_WINDOW)
+    }
+}
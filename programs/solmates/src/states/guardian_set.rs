@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+use crate::MAX_GUARDIANS;
+
+/// Wallets the authority trusts to move the profile to a new wallet if theirs is lost.
+/// `threshold` of them must approve a recovery.
+#[account]
+#[derive(InitSpace)]
+pub struct GuardianSet {
+    pub version: u8,
+    pub profile: Pubkey,
+    #[max_len(MAX_GUARDIANS)]
+    pub guardians: Vec<Pubkey>,
+    pub threshold: u8,
+    pub bump: u8,
+    pub reserved: [u8; 32], // Room for new fields without a realloc
+}
+
+impl GuardianSet {
+    pub const VERSION: u8 = 1;
+
+    pub fn is_guardian(&self, key: &Pubkey) -> bool {
+        self.guardians.contains(key)
+    }
+}
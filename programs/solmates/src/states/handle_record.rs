@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+use crate::MAX_HANDLE_LEN;
+
+/// Claims a unique handle for a profile. Lives at `[b"handle", handle]`, so a handle
+/// can only be held by one profile at a time.
+#[account]
+#[derive(InitSpace)]
+pub struct HandleRecord {
+    pub version: u8,
+    #[max_len(MAX_HANDLE_LEN)]
+    pub handle: String,
+    pub profile: Pubkey,
+    pub claimed_at: i64,
+    pub bump: u8,
+    pub reserved: [u8; 32], // Room for new fields without a realloc
+}
+
+impl HandleRecord {
+    pub const VERSION: u8 = 1;
+}
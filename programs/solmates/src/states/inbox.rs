@@ -0,0 +1,229 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::SolmatesError;
+use crate::INBOX_PAGE_SIZE;
+
+/// Per-recipient index of pending DM escrows, so clients can list them without a
+/// `getProgramAccounts` scan, and an optional cap on how many can be pending at once.
+///
+/// Escrow addresses live in `InboxPage` accounts at `[b"inbox_page", recipient, index]`.
+/// New escrows go into `insert_page`. Pages with room form a list through
+/// `InboxPage::next_free`, headed by `insert_page`, so every free slot is reused before a
+/// new page is opened.
+#[account]
+#[derive(InitSpace)]
+pub struct Inbox {
+    pub version: u8,
+    pub recipient: Pubkey,
+    pub pending_count: u32,
+    pub max_pending: u32, // 0 = no cap
+    pub page_count: u32,  // Pages created so far
+    pub insert_page: u32, // Page the next escrow is indexed in
+    pub bump: u8,
+    pub reserved: [u8; 32], // Room for new fields without a realloc
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct InboxPage {
+    pub version: u8,
+    pub inbox: Pubkey,
+    pub index: u32,
+    #[max_len(INBOX_PAGE_SIZE)]
+    pub escrows: Vec<Pubkey>,
+    pub bump: u8,
+    pub next_free: Option<u32>, // Next page with room once this one fills - None = a new page
+    pub reserved: [u8; 11],     // Room for new fields without a realloc
+}
+
+impl Inbox {
+    pub const VERSION: u8 = 1;
+
+    /// Indexes `escrow` in `page`, which must be the current insert page, and returns
+    /// the page index to record on the escrow.
+    pub fn insert(&mut self, page: &mut InboxPage, escrow: Pubkey) -> Result<u32> {
+        require!(
+            self.max_pending == 0 || self.pending_count < self.max_pending,
+            SolmatesError::InboxFull
+        );
+        require!(
+            page.index == self.insert_page,
+            SolmatesError::InvalidInboxPage
+        );
+
+        page.escrows.push(escrow);
+        self.pending_count = self.pending_count.checked_add(1).unwrap();
+        self.page_count = self.page_count.max(page.index.checked_add(1).unwrap());
+
+        // A full page hands over to the next page with room, or a fresh one
+        if page.escrows.len() >= INBOX_PAGE_SIZE {
+            self.insert_page = page.next_free.take().unwrap_or(self.page_count);
+        }
+
+        Ok(page.index)
+    }
+
+    /// Drops `escrow` from `page`. A full page that frees up a slot goes to the front of
+    /// the pages with room and becomes the insert page.
+    pub fn remove(&mut self, page: &mut InboxPage, escrow: &Pubkey) {
+        if let Some(position) = page.escrows.iter().position(|key| key == escrow) {
+            let was_full = page.escrows.len() >= INBOX_PAGE_SIZE;
+            page.escrows.swap_remove(position);
+            self.pending_count = self.pending_count.saturating_sub(1);
+
+            if was_full {
+                page.next_free = (self.insert_page < self.page_count).then_some(self.insert_page);
+                self.insert_page = page.index;
+            }
+        }
+    }
+}
+
+impl InboxPage {
+    pub const VERSION: u8 = 1;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inbox(max_pending: u32) -> Inbox {
+        Inbox {
+            version: Inbox::VERSION,
+            recipient: Pubkey::new_unique(),
+            pending_count: 0,
+            max_pending,
+            page_count: 0,
+            insert_page: 0,
+            bump: 0,
+            reserved: [0; 32],
+        }
+    }
+
+    fn pages(count: u32) -> Vec<InboxPage> {
+        (0..count)
+            .map(|index| InboxPage {
+                version: InboxPage::VERSION,
+                inbox: Pubkey::default(),
+                index,
+                escrows: Vec::new(),
+                bump: 0,
+                next_free: None,
+                reserved: [0; 11],
+            })
+            .collect()
+    }
+
+    /// Inserts a fresh escrow into whichever page the inbox asks for
+    fn insert(inbox: &mut Inbox, pages: &mut [InboxPage]) -> Pubkey {
+        let escrow = Pubkey::new_unique();
+        let page = &mut pages[inbox.insert_page as usize];
+        assert_eq!(inbox.insert(page, escrow).unwrap(), page.index);
+        escrow
+    }
+
+    fn fill(inbox: &mut Inbox, pages: &mut [InboxPage]) -> Vec<Pubkey> {
+        (0..INBOX_PAGE_SIZE).map(|_| insert(inbox, pages)).collect()
+    }
+
+    #[test]
+    fn insert_stops_at_the_cap() {
+        let mut inbox = inbox(2);
+        let mut pages = pages(1);
+        let first = insert(&mut inbox, &mut pages);
+        insert(&mut inbox, &mut pages);
+
+        assert_eq!(
+            inbox
+                .insert(&mut pages[0], Pubkey::new_unique())
+                .unwrap_err(),
+            SolmatesError::InboxFull.into()
+        );
+
+        // Settling one frees a slot under the cap
+        inbox.remove(&mut pages[0], &first);
+        assert_eq!(inbox.pending_count, 1);
+        insert(&mut inbox, &mut pages);
+        assert_eq!(inbox.pending_count, 2);
+    }
+
+    #[test]
+    fn insert_only_into_the_insert_page() {
+        let mut inbox = inbox(0);
+        let mut pages = pages(2);
+        assert_eq!(
+            inbox
+                .insert(&mut pages[1], Pubkey::new_unique())
+                .unwrap_err(),
+            SolmatesError::InvalidInboxPage.into()
+        );
+    }
+
+    #[test]
+    fn full_page_hands_over_to_a_new_page() {
+        let mut inbox = inbox(0);
+        let mut pages = pages(2);
+        fill(&mut inbox, &mut pages);
+
+        assert_eq!(pages[0].escrows.len(), INBOX_PAGE_SIZE);
+        assert_eq!(inbox.page_count, 1);
+        assert_eq!(inbox.insert_page, 1);
+
+        insert(&mut inbox, &mut pages);
+        assert_eq!(inbox.page_count, 2);
+        assert_eq!(inbox.pending_count, INBOX_PAGE_SIZE as u32 + 1);
+    }
+
+    #[test]
+    fn freed_slots_are_reused_before_a_new_page() {
+        let mut inbox = inbox(0);
+        let mut pages = pages(3);
+        let first_page = fill(&mut inbox, &mut pages);
+        let second_page = fill(&mut inbox, &mut pages);
+        assert_eq!(inbox.insert_page, 2);
+
+        // Both full pages free a slot - the last one freed is used first, and links to
+        // the other. Page 2 was never opened, so nothing follows page 1.
+        inbox.remove(&mut pages[1], &second_page[3]);
+        assert_eq!(inbox.insert_page, 1);
+        assert_eq!(pages[1].next_free, None);
+        inbox.remove(&mut pages[0], &first_page[5]);
+        assert_eq!(inbox.insert_page, 0);
+        assert_eq!(pages[0].next_free, Some(1));
+
+        insert(&mut inbox, &mut pages);
+        assert_eq!(inbox.insert_page, 1);
+        assert_eq!(pages[0].next_free, None);
+        insert(&mut inbox, &mut pages);
+        assert_eq!(inbox.insert_page, 2);
+        assert_eq!(inbox.page_count, 2);
+        assert!(pages[2].escrows.is_empty());
+    }
+
+    #[test]
+    fn page_with_room_left_keeps_the_insert_page() {
+        let mut inbox = inbox(0);
+        let mut pages = pages(2);
+        let first_page = fill(&mut inbox, &mut pages);
+        let on_second_page = insert(&mut inbox, &mut pages);
+
+        // Page 1 still has room, so it stays ahead of the freed slot on page 0
+        inbox.remove(&mut pages[1], &on_second_page);
+        assert_eq!(inbox.insert_page, 1);
+        inbox.remove(&mut pages[0], &first_page[0]);
+        assert_eq!(inbox.insert_page, 0);
+        assert_eq!(pages[0].next_free, Some(1));
+    }
+
+    #[test]
+    fn removing_an_unknown_escrow_changes_nothing() {
+        let mut inbox = inbox(0);
+        let mut pages = pages(1);
+        let escrows = fill(&mut inbox, &mut pages);
+
+        inbox.remove(&mut pages[0], &Pubkey::new_unique());
+        assert_eq!(inbox.pending_count, INBOX_PAGE_SIZE as u32);
+        assert_eq!(pages[0].escrows, escrows);
+        assert_eq!(inbox.insert_page, 1);
+    }
+}
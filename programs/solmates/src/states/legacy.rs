@@ -0,0 +1,89 @@
+//! Account layouts deployed before the current ones.
+//!
+//! These accounts were sized exactly with `INIT_SPACE`, so their data length identifies
+//! them. They are only ever read by the `migrate_*` instructions.
+
+use anchor_lang::prelude::*;
+
+use crate::states::{AccessGate, BountyStatus, EscrowStatus, Reputation};
+use crate::MAX_HANDLE_LEN;
+
+#[derive(AnchorDeserialize, InitSpace)]
+pub struct UserProfileV0 {
+    pub authority: Pubkey,
+    pub dm_price: u64,
+    pub asset_gate_mint: Option<Pubkey>,
+    pub min_asset_amount: u64,
+    pub auction_count: u64,
+    pub bump: u8,
+}
+
+/// Profile version 1, before `identity` split off from `authority`.
+#[derive(AnchorDeserialize, InitSpace)]
+pub struct UserProfileV1 {
+    pub version: u8,
+    pub authority: Pubkey,
+    pub dm_price: u64,
+    pub gate: Option<AccessGate>,
+    pub auction_count: u64,
+    pub created_at: i64,
+    pub open_auctions: u64,
+    pub open_bounties: u64,
+    pub pending_escrows: u64,
+    pub bump: u8,
+    pub reserved: [u8; 128],
+}
+
+/// Profile version 2. `identity` was inserted ahead of `authority`, shifting every later
+/// field; `handle` and `reputation` were later carved from its reserved bytes. It happens
+/// to share version 1's length, so the version byte tells the two apart.
+#[derive(AnchorDeserialize, InitSpace)]
+pub struct UserProfileV2 {
+    pub version: u8,
+    pub identity: Pubkey,
+    pub authority: Pubkey,
+    pub dm_price: u64,
+    pub gate: Option<AccessGate>,
+    pub auction_count: u64,
+    pub created_at: i64,
+    pub open_auctions: u64,
+    pub open_bounties: u64,
+    pub pending_escrows: u64,
+    pub bump: u8,
+    #[max_len(MAX_HANDLE_LEN)]
+    pub handle: Option<String>,
+    pub reputation: Reputation,
+    pub reserved: [u8; 23],
+}
+
+#[derive(AnchorDeserialize, InitSpace)]
+pub struct MessageEscrowV0 {
+    pub sender: Pubkey,
+    pub recipient: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub expiry: i64,
+    pub status: EscrowStatus,
+    pub bump: u8,
+}
+
+#[derive(AnchorDeserialize, InitSpace)]
+pub struct DateAuctionV0 {
+    pub host: Pubkey,
+    pub auction_id: u64,
+    pub mint: Pubkey,
+    pub highest_bidder: Pubkey,
+    pub highest_bid: u64,
+    pub end_time: i64,
+    pub total_extended: i64,
+    pub bump: u8,
+}
+
+#[derive(AnchorDeserialize, InitSpace)]
+pub struct BountyVaultV0 {
+    pub issuer: Pubkey,
+    pub mint: Pubkey,
+    pub reward_amount: u64,
+    pub status: BountyStatus,
+    pub bump: u8,
+}
@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+/// One profile's like of another, keyed by both identities.
+#[account]
+#[derive(InitSpace)]
+pub struct Like {
+    pub version: u8,
+    pub liker: Pubkey, // Profile identity
+    pub likee: Pubkey, // Profile identity
+    pub liked_at: i64,
+    pub bump: u8,
+    pub reserved: [u8; 32], // Room for new fields without a realloc
+}
+
+impl Like {
+    pub const VERSION: u8 = 1;
+}
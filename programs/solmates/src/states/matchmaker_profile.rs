@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+/// A wallet registered as a matchmaker, with its advertised terms and the track record
+/// the program keeps as its bounties pay out.
+#[account]
+#[derive(InitSpace)]
+pub struct MatchmakerProfile {
+    pub version: u8,
+    pub matchmaker: Pubkey,
+    pub min_fee: u64,               // Smallest reward they submit to, in mint base units
+    pub specialties_hash: [u8; 32], // Hash of the off-chain specialties listing
+    pub successful_payouts: u32,    // Bounties paid out to the matchmaker
+    pub total_earned: u64,          // Net of platform fee, in mint base units
+    pub registered_at: i64,
+    pub bump: u8,
+    pub reserved: [u8; 32], // Room for new fields without a realloc
+}
+
+impl MatchmakerProfile {
+    pub const VERSION: u8 = 1;
+
+    /// Credits a payout. `new_bounty` is false for later milestones of a bounty that
+    /// already counted.
+    pub fn record_payout(&mut self, amount: u64, new_bounty: bool) {
+        if new_bounty {
+            self.successful_payouts = self.successful_payouts.saturating_add(1);
+        }
+        self.total_earned = self.total_earned.saturating_add(amount);
+    }
+}
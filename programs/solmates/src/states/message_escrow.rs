@@ -3,6 +3,7 @@ use anchor_lang::prelude::*;
 #[account]
 #[derive(InitSpace)]
 pub struct MessageEscrow {
+    pub version: u8,
     pub sender: Pubkey,
     pub recipient: Pubkey,
     pub mint: Pubkey,
@@ -10,6 +11,12 @@ pub struct MessageEscrow {
     pub expiry: i64,
     pub status: EscrowStatus,
     pub bump: u8,
+    pub inbox_page: Option<u32>, // Inbox page indexing this escrow - None if it predates inboxes
+    pub reserved: [u8; 59],      // Room for new fields without a realloc
+}
+
+impl MessageEscrow {
+    pub const VERSION: u8 = 1;
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
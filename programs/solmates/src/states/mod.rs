@@ -1,9 +1,41 @@
+pub mod access_gate;
+pub mod attestation;
 pub mod bounty_vault;
+pub mod contribution;
 pub mod date_auction;
+pub mod date_booking;
+pub mod guardian_set;
+pub mod handle_record;
+pub mod inbox;
+pub mod legacy;
+pub mod like;
+pub mod matchmaker_profile;
 pub mod message_escrow;
+pub mod mutual_match;
+pub mod recovery_request;
+pub mod session_delegate;
+pub mod submission;
+pub mod super_like;
 pub mod user_profile;
+pub mod verifier;
 
+pub use access_gate::*;
+pub use attestation::*;
 pub use bounty_vault::*;
+pub use contribution::*;
 pub use date_auction::*;
+pub use date_booking::*;
+pub use guardian_set::*;
+pub use handle_record::*;
+pub use inbox::*;
+pub use legacy::*;
+pub use like::*;
+pub use matchmaker_profile::*;
 pub use message_escrow::*;
+pub use mutual_match::*;
+pub use recovery_request::*;
+pub use session_delegate::*;
+pub use submission::*;
+pub use super_like::*;
 pub use user_profile::*;
+pub use verifier::*;
@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+/// Two profiles that liked each other. Created with the second like, so other
+/// instructions can take its existence as proof of a mutual match.
+#[account]
+#[derive(InitSpace)]
+pub struct Match {
+    pub version: u8,
+    pub user_a: Pubkey, // Lower of the two profile identities
+    pub user_b: Pubkey, // Higher of the two profile identities
+    pub matched_at: i64,
+    pub payer: Pubkey, // Wallet that paid the rent - refunded on unmatch
+    pub bump: u8,
+    pub reserved: [u8; 32], // Room for new fields without a realloc
+}
+
+impl Match {
+    pub const VERSION: u8 = 1;
+
+    /// The two identities in seed order, so either side derives the same PDA.
+    pub fn pair(first: Pubkey, second: Pubkey) -> (Pubkey, Pubkey) {
+        if first <= second {
+            (first, second)
+        } else {
+            (second, first)
+        }
+    }
+
+    pub fn is_between(&self, first: &Pubkey, second: &Pubkey) -> bool {
+        Self::pair(*first, *second) == (self.user_a, self.user_b)
+    }
+}
@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+use crate::MAX_GUARDIANS;
+
+/// A pending proposal to hand the profile to `new_authority`. It can be executed once
+/// enough guardians approve and the timelock elapses; until then the current authority
+/// can veto it.
+#[account]
+#[derive(InitSpace)]
+pub struct RecoveryRequest {
+    pub version: u8,
+    pub profile: Pubkey,
+    pub new_authority: Pubkey,
+    pub proposer: Pubkey, // Guardian who opened the request - paid its rent
+    #[max_len(MAX_GUARDIANS)]
+    pub approvals: Vec<Pubkey>,
+    pub initiated_at: i64,
+    pub bump: u8,
+    pub reserved: [u8; 32], // Room for new fields without a realloc
+}
+
+impl RecoveryRequest {
+    pub const VERSION: u8 = 1;
+}
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+/// A time-limited key allowed to perform a subset of profile actions on the authority's
+/// behalf, so routine actions don't need a wallet popup each time.
+#[account]
+#[derive(InitSpace)]
+pub struct SessionDelegate {
+    pub version: u8,
+    pub authority: Pubkey,
+    pub delegate: Pubkey,
+    pub scopes: u8, // Bitmask of SessionDelegate::SCOPE_* flags
+    pub expires_at: i64,
+    pub bump: u8,
+    pub reserved: [u8; 32], // Room for new fields without a realloc
+}
+
+impl SessionDelegate {
+    pub const VERSION: u8 = 1;
+
+    pub const SCOPE_ACCEPT_DM: u8 = 1 << 0;
+    pub const SCOPE_DECLINE_DM: u8 = 1 << 1;
+    pub const SCOPE_PLACE_BID: u8 = 1 << 2;
+    pub const SCOPE_UPDATE_PROFILE: u8 = 1 << 3;
+    pub const ALL_SCOPES: u8 = Self::SCOPE_ACCEPT_DM
+        | Self::SCOPE_DECLINE_DM
+        | Self::SCOPE_PLACE_BID
+        | Self::SCOPE_UPDATE_PROFILE;
+}
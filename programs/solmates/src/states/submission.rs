@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+
+/// A matchmaker's proposed match for a bounty. Only a matchmaker with a pending
+/// submission can be paid the reward.
+#[account]
+#[derive(InitSpace)]
+pub struct Submission {
+    pub version: u8,
+    pub bounty: Pubkey,
+    pub bounty_created_at: i64, // Bounty instance submitted to - a recreated bounty starts fresh
+    pub matchmaker: Pubkey,
+    pub candidate: Pubkey,   // Wallet of the person being introduced
+    pub note_hash: [u8; 32], // Hash of the off-chain pitch
+    pub submitted_at: i64,
+    pub status: SubmissionStatus,
+    pub bump: u8,
+    pub claimed_at: i64, // When the matchmaker claimed a consent-mode reward
+    pub issuer_confirmed: bool,
+    pub candidate_confirmed: bool,
+    pub disputed_at: i64,
+    pub min_fee: u64,      // Matchmaker's registered minimum fee when they submitted
+    pub reserved: [u8; 6], // Room for new fields without a realloc
+}
+
+impl Submission {
+    pub const VERSION: u8 = 1;
+
+    /// Pending and submitted to this instance of the bounty.
+    pub fn is_live_for(&self, bounty_created_at: i64) -> bool {
+        self.status == SubmissionStatus::Pending && self.bounty_created_at == bounty_created_at
+    }
+
+    pub fn is_claimed(&self) -> bool {
+        matches!(
+            self.status,
+            SubmissionStatus::Claimed | SubmissionStatus::Disputed
+        )
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum SubmissionStatus {
+    Pending,
+    Rejected,
+    /// Reward claimed on a consent-mode bounty, waiting on the issuer and candidate
+    Claimed,
+    /// Issuer or candidate objected to the claim
+    Disputed,
+    /// Bounty expired before the submission was paid
+    Void,
+}
@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+/// Tokens escrowed with a like. Paid to the likee if they like back before `expiry`,
+/// refunded to the sender otherwise.
+#[account]
+#[derive(InitSpace)]
+pub struct SuperLike {
+    pub version: u8,
+    pub liker: Pubkey,  // Profile identity
+    pub likee: Pubkey,  // Profile identity
+    pub sender: Pubkey, // Wallet that escrowed the tokens - receives refunds and rent
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub expiry: i64, // Last moment a like back earns the escrow
+    pub bump: u8,
+    pub reserved: [u8; 32], // Room for new fields without a realloc
+}
+
+impl SuperLike {
+    pub const VERSION: u8 = 1;
+}
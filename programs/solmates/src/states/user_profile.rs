@@ -1,12 +1,236 @@
 use anchor_lang::prelude::*;
 
+use crate::states::AccessGate;
+use crate::errors::SolmatesError;
+use crate::{MAX_HANDLE_LEN, PRICING_WINDOW};
+
 #[account]
 #[derive(InitSpace)]
 pub struct UserProfile {
-    pub authority: Pubkey,
+    pub version: u8,
+    pub identity: Pubkey,  // Wallet the profile was created with - fixed, seeds its PDAs
+    pub authority: Pubkey, // Wallet currently in control - changes on recovery
     pub dm_price: u64,
-    pub asset_gate_mint: Option<Pubkey>,
-    pub min_asset_amount: u64,
+    pub gate: Option<AccessGate>,
     pub auction_count: u64,
+    pub created_at: i64,
+    pub open_auctions: u64,   // Live auctions hosted - must be settled before closing
+    pub open_bounties: u64,   // Open bounties issued - must be settled before closing
+    pub pending_escrows: u64, // Pending DM escrows received - must be settled before closing
     pub bump: u8,
+    #[max_len(MAX_HANDLE_LEN)]
+    pub handle: Option<String>, // Handle claimed in the registry, if any
+    pub reputation: Reputation,
+    pub encryption_key: Option<[u8; 32]>, // X25519 public key DMs are sealed to
+    pub encryption_key_version: u32,      // Bumped on every key change, 0 = never set
+    pub availability: Availability,
+    pub pricing: Option<DmPricing>, // Demand-based DM price - replaces dm_price when set
+    pub recent_accepts: u32,        // DMs accepted in the current pricing window
+    pub recent_accepts_since: i64,  // Start of the current pricing window
+    pub reserved: [u8; 80], // Room for new fields without a realloc
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, InitSpace)]
+pub enum Availability {
+    #[default]
+    Available,
+    /// Not taking new DMs, bids or auctions - until `back_at` if set, otherwise until
+    /// switched back
+    Away { back_at: Option<i64> },
+}
+
+/// Bonding curve for the DM price: `base + slope * demand`, capped at `cap`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct DmPricing {
+    pub base: u64,
+    pub slope: u64, // Added per unit of demand
+    pub cap: u64,
+    pub signal: DemandSignal,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum DemandSignal {
+    /// DM escrows currently waiting on the recipient
+    PendingEscrows,
+    /// DMs the recipient accepted within the last `PRICING_WINDOW`
+    RecentAcceptances,
+}
+
+impl DmPricing {
+    pub fn validate(&self) -> Result<()> {
+        require!(self.cap >= self.base, SolmatesError::InvalidDmPricing);
+        Ok(())
+    }
+
+    pub fn price(&self, demand: u64) -> u64 {
+        self.base
+            .saturating_add(self.slope.saturating_mul(demand))
+            .min(self.cap)
+    }
+}
+
+/// Track record kept by the program as the profile's DMs, auctions and bounties settle.
+/// Counting starts from when the profile gained these fields, so older profiles may
+/// under-report history.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default, InitSpace)]
+pub struct Reputation {
+    pub dms_accepted: u32,
+    pub dms_declined: u32,
+    pub dms_expired: u32,     // Escrows refunded after going unanswered
+    pub auctions_hosted: u32, // Auctions settled with a winner
+    pub auctions_won: u32,
+    pub bounties_paid: u32,   // Bounties paid out to this profile as matchmaker
+    pub dm_earned: u64,      // Net of platform fee, in mint base units
+    pub auction_earned: u64, // Net of platform fee, in mint base units
+    pub bounty_earned: u64,  // Net of platform fee, in mint base units
+}
+
+impl UserProfile {
+    pub const VERSION: u8 = 3;
+
+    pub fn is_away(&self, current_time: i64) -> bool {
+        match self.availability {
+            Availability::Available => false,
+            Availability::Away { back_at } => back_at.is_none_or(|back_at| current_time < back_at),
+        }
+    }
+
+    /// Minimum deposit for a new DM at `current_time`.
+    pub fn dm_price_at(&self, current_time: i64) -> u64 {
+        let Some(pricing) = &self.pricing else {
+            return self.dm_price;
+        };
+        let demand = match pricing.signal {
+            DemandSignal::PendingEscrows => self.pending_escrows,
+            DemandSignal::RecentAcceptances => self.recent_accepts_at(current_time) as u64,
+        };
+        pricing.price(demand)
+    }
+
+    pub fn recent_accepts_at(&self, current_time: i64) -> u32 {
+        if current_time < self.recent_accepts_since.saturating_add(PRICING_WINDOW) {
+            self.recent_accepts
+        } else {
+            0
+        }
+    }
+
+    pub fn record_accept(&mut self, current_time: i64) {
+        if current_time >= self.recent_accepts_since.saturating_add(PRICING_WINDOW) {
+            self.recent_accepts_since = current_time;
+            self.recent_accepts = 0;
+        }
+        self.recent_accepts = self.recent_accepts.saturating_add(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn curve(base: u64, slope: u64, cap: u64, signal: DemandSignal) -> DmPricing {
+        DmPricing {
+            base,
+            slope,
+            cap,
+            signal,
+        }
+    }
+
+    fn profile(pricing: Option<DmPricing>) -> UserProfile {
+        UserProfile {
+            version: UserProfile::VERSION,
+            identity: Pubkey::new_unique(),
+            authority: Pubkey::new_unique(),
+            dm_price: 5,
+            gate: None,
+            auction_count: 0,
+            created_at: 0,
+            open_auctions: 0,
+            open_bounties: 0,
+            pending_escrows: 0,
+            bump: 0,
+            handle: None,
+            reputation: Reputation::default(),
+            encryption_key: None,
+            encryption_key_version: 0,
+            availability: Availability::Available,
+            pricing,
+            recent_accepts: 0,
+            recent_accepts_since: 0,
+            reserved: [0; 80],
+        }
+    }
+
+    #[test]
+    fn price_climbs_with_demand_up_to_the_cap() {
+        let pricing = curve(100, 10, 150, DemandSignal::PendingEscrows);
+        assert_eq!(pricing.price(0), 100);
+        assert_eq!(pricing.price(3), 130);
+        assert_eq!(pricing.price(5), 150);
+        assert_eq!(pricing.price(6), 150);
+        // Demand large enough to overflow still lands on the cap
+        assert_eq!(pricing.price(u64::MAX), 150);
+        let steep = curve(
+            u64::MAX - 1,
+            u64::MAX,
+            u64::MAX,
+            DemandSignal::PendingEscrows,
+        );
+        assert_eq!(steep.price(2), u64::MAX);
+    }
+
+    #[test]
+    fn zero_slope_is_a_flat_price() {
+        let pricing = curve(100, 0, 500, DemandSignal::PendingEscrows);
+        assert_eq!(pricing.price(0), 100);
+        assert_eq!(pricing.price(u64::MAX), 100);
+    }
+
+    #[test]
+    fn cap_below_base_is_rejected() {
+        assert_eq!(
+            curve(100, 10, 99, DemandSignal::PendingEscrows)
+                .validate()
+                .unwrap_err(),
+            SolmatesError::InvalidDmPricing.into()
+        );
+        assert!(curve(100, 10, 100, DemandSignal::PendingEscrows)
+            .validate()
+            .is_ok());
+    }
+
+    #[test]
+    fn fixed_price_without_a_curve() {
+        let mut profile = profile(None);
+        profile.pending_escrows = 10;
+        assert_eq!(profile.dm_price_at(0), 5);
+    }
+
+    #[test]
+    fn pending_escrows_drive_the_price() {
+        let mut profile = profile(Some(curve(100, 10, 1_000, DemandSignal::PendingEscrows)));
+        assert_eq!(profile.dm_price_at(0), 100);
+        profile.pending_escrows = 4;
+        assert_eq!(profile.dm_price_at(0), 140);
+    }
+
+    #[test]
+    fn recent_acceptances_drive_the_price_within_the_window() {
+        let mut profile = profile(Some(curve(100, 10, 1_000, DemandSignal::RecentAcceptances)));
+        let start = 10 * PRICING_WINDOW;
+        profile.record_accept(start);
+        profile.record_accept(start + 10);
+        assert_eq!(profile.recent_accepts_since, start);
+        assert_eq!(profile.dm_price_at(start + PRICING_WINDOW - 1), 120);
+
+        // Once the window has passed the old acceptances stop counting
+        assert_eq!(profile.dm_price_at(start + PRICING_WINDOW), 100);
+
+        // ...and the next acceptance opens a new window
+        profile.record_accept(start + PRICING_WINDOW);
+        assert_eq!(profile.recent_accepts, 1);
+        assert_eq!(profile.recent_accepts_since, start + PRICING_WINDOW);
+        assert_eq!(profile.dm_price_at(start + PRICING_WINDOW), 110);
+    }
 }
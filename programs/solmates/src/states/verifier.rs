@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+use crate::MAX_VERIFIER_NAME_LEN;
+
+/// A party the platform trusts to attest to facts about profiles (identity, age, photo
+/// checks). Attestations only count while their verifier is registered.
+#[account]
+#[derive(InitSpace)]
+pub struct Verifier {
+    pub version: u8,
+    pub verifier: Pubkey,
+    #[max_len(MAX_VERIFIER_NAME_LEN)]
+    pub name: String,
+    pub added_at: i64,
+    pub bump: u8,
+    pub reserved: [u8; 32], // Room for new fields without a realloc
+}
+
+impl Verifier {
+    pub const VERSION: u8 = 1;
+}
@@ -0,0 +1,229 @@
+use anchor_lang::prelude::*;
+use anchor_lang::AccountsClose;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::errors::SolmatesError;
+use crate::states::{
+    BountyStatus, BountyVault, Contribution, ContributorCredit, MatchmakerProfile, UserProfile,
+};
+use crate::PLATFORM_FEE_BPS;
+
+/// Pays `amount` out of the bounty vault - the platform fee to the treasury and the rest
+/// to `recipient_token_account`. Returns the net amount paid and the fee.
+pub fn pay_from_bounty<'info>(
+    bounty: &Account<'info, BountyVault>,
+    bounty_vault: &InterfaceAccount<'info, TokenAccount>,
+    mint: &InterfaceAccount<'info, Mint>,
+    recipient_token_account: &InterfaceAccount<'info, TokenAccount>,
+    treasury_token_account: &InterfaceAccount<'info, TokenAccount>,
+    token_program: &Interface<'info, TokenInterface>,
+    amount: u64,
+) -> Result<(u64, u64)> {
+    let fee = amount
+        .checked_mul(PLATFORM_FEE_BPS)
+        .unwrap()
+        .checked_div(10000)
+        .unwrap();
+    let net_amount = amount.checked_sub(fee).unwrap();
+
+    let seeds = &[b"bounty", bounty.issuer.as_ref(), &[bounty.bump]];
+    let signer_seeds = &[&seeds[..]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        TransferChecked {
+            from: bounty_vault.to_account_info(),
+            mint: mint.to_account_info(),
+            to: recipient_token_account.to_account_info(),
+            authority: bounty.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token_interface::transfer_checked(transfer_ctx, net_amount, mint.decimals)?;
+
+    if fee > 0 {
+        let fee_ctx = CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            TransferChecked {
+                from: bounty_vault.to_account_info(),
+                mint: mint.to_account_info(),
+                to: treasury_token_account.to_account_info(),
+                authority: bounty.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token_interface::transfer_checked(fee_ctx, fee, mint.decimals)?;
+    }
+
+    Ok((net_amount, fee))
+}
+
+/// Reads the matchmaker's registry record from its PDA, or `None` if they never
+/// registered. Callers always pass the PDA, so a registered record can't be left out.
+pub fn load_matchmaker_record(record: &AccountInfo) -> Result<Option<MatchmakerProfile>> {
+    if record.owner != &crate::ID || record.data_is_empty() {
+        return Ok(None);
+    }
+
+    let matchmaker_record =
+        MatchmakerProfile::try_deserialize(&mut &record.try_borrow_data()?[..])?;
+    require!(
+        matchmaker_record.version == MatchmakerProfile::VERSION,
+        SolmatesError::AccountVersionMismatch
+    );
+    Ok(Some(matchmaker_record))
+}
+
+/// Credits a payout to the matchmaker's registry record at its PDA, if they registered.
+pub fn credit_matchmaker_record(record: &AccountInfo, amount: u64, new_bounty: bool) -> Result<()> {
+    if let Some(mut matchmaker_record) = load_matchmaker_record(record)? {
+        matchmaker_record.record_payout(amount, new_bounty);
+        matchmaker_record.try_serialize(&mut &mut record.try_borrow_mut_data()?[..])?;
+    }
+    Ok(())
+}
+
+/// `pool * part / whole`, rounded down.
+pub fn pro_rata(pool: u64, part: u64, whole: u64) -> u64 {
+    if whole == 0 {
+        return 0;
+    }
+    ((pool as u128) * (part as u128) / (whole as u128)) as u64
+}
+
+/// Loads the bounty's contributions from `accounts`. Every contribution still tied to the
+/// bounty must be present, so a payout credits all of its contributors.
+pub fn load_contributions<'info>(
+    bounty: &Account<'info, BountyVault>,
+    accounts: &'info [AccountInfo<'info>],
+) -> Result<Vec<ContributorCredit>> {
+    require!(
+        accounts.len() == bounty.contributor_count as usize,
+        SolmatesError::ContributionMismatch
+    );
+
+    let mut credits: Vec<ContributorCredit> = Vec::with_capacity(accounts.len());
+    for contribution_info in accounts {
+        let contribution = Account::<Contribution>::try_from(contribution_info)?;
+        require!(
+            contribution.version == Contribution::VERSION,
+            SolmatesError::AccountVersionMismatch
+        );
+        require!(
+            contribution.bounty == bounty.key()
+                && contribution.bounty_created_at == bounty.created_at,
+            SolmatesError::ContributionMismatch
+        );
+        require!(
+            credits
+                .iter()
+                .all(|credit| credit.contributor != contribution.contributor),
+            SolmatesError::ContributionMismatch
+        );
+        credits.push(ContributorCredit {
+            contributor: contribution.contributor,
+            amount: contribution.amount,
+        });
+    }
+
+    Ok(credits)
+}
+
+/// Winds a bounty down on cancel or expiry: the issuer gets their pro-rata share of the
+/// vault and the bounty closes, or, with contributors still to refund, waits in
+/// `Refunding` until `refund_contribution` has returned every share. Returns the amount
+/// refunded to the issuer.
+pub fn refund_issuer<'info>(
+    bounty: &mut Account<'info, BountyVault>,
+    bounty_vault: &InterfaceAccount<'info, TokenAccount>,
+    mint: &InterfaceAccount<'info, Mint>,
+    issuer_token_account: &InterfaceAccount<'info, TokenAccount>,
+    issuer: &AccountInfo<'info>,
+    issuer_profile: &mut Account<'info, UserProfile>,
+    token_program: &Interface<'info, TokenInterface>,
+) -> Result<u64> {
+    let issuer_amount = pro_rata(
+        bounty_vault.amount,
+        bounty
+            .reward_amount
+            .saturating_sub(bounty.contributed_amount),
+        bounty.reward_amount,
+    );
+
+    if issuer_amount > 0 {
+        let seeds = &[b"bounty", bounty.issuer.as_ref(), &[bounty.bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        let transfer_ctx = CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            TransferChecked {
+                from: bounty_vault.to_account_info(),
+                mint: mint.to_account_info(),
+                to: issuer_token_account.to_account_info(),
+                authority: bounty.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token_interface::transfer_checked(transfer_ctx, issuer_amount, mint.decimals)?;
+    }
+
+    if bounty.contributor_count == 0 {
+        close_bounty(bounty, issuer, issuer_profile)?;
+    } else {
+        bounty.status = BountyStatus::Refunding;
+        bounty.reward_amount = bounty.contributed_amount;
+    }
+
+    Ok(issuer_amount)
+}
+
+/// Closes a wound-down bounty to the issuer and releases their open-bounty obligation.
+pub fn close_bounty<'info>(
+    bounty: &mut Account<'info, BountyVault>,
+    issuer: &AccountInfo<'info>,
+    issuer_profile: &mut Account<'info, UserProfile>,
+) -> Result<()> {
+    issuer_profile.open_bounties = issuer_profile.open_bounties.saturating_sub(1);
+    bounty.close(issuer.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pro_rata_rounds_down() {
+        assert_eq!(pro_rata(100, 1, 3), 33);
+        assert_eq!(pro_rata(100, 2, 3), 66);
+        assert_eq!(pro_rata(10, 1, 4), 2);
+        // A share too small to be worth a base unit gets nothing
+        assert_eq!(pro_rata(3, 1, 4), 0);
+    }
+
+    #[test]
+    fn pro_rata_shares_never_exceed_the_pool() {
+        // A 7-unit vault refunded to stakes of 3, 4 and 3 out of 10 - the unit of dust
+        // stays behind
+        let shares: Vec<u64> = [3, 4, 3]
+            .iter()
+            .map(|part| pro_rata(7, *part, 10))
+            .collect();
+        assert_eq!(shares, vec![2, 2, 2]);
+        assert_eq!(shares.iter().sum::<u64>(), 6);
+    }
+
+    #[test]
+    fn pro_rata_edges() {
+        assert_eq!(pro_rata(100, 0, 3), 0);
+        assert_eq!(pro_rata(100, 3, 3), 100);
+        assert_eq!(pro_rata(0, 1, 3), 0);
+        // Nothing to split against
+        assert_eq!(pro_rata(100, 0, 0), 0);
+    }
+
+    #[test]
+    fn pro_rata_does_not_overflow() {
+        assert_eq!(pro_rata(u64::MAX, u64::MAX, u64::MAX), u64::MAX);
+        assert_eq!(pro_rata(u64::MAX, u64::MAX / 2, u64::MAX), u64::MAX / 2);
+    }
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::states::DateBooking;
+
+/// Pays `amount` out of the booking vault to `recipient_token_account`. Stakes move
+/// between the two parties without a platform fee.
+pub fn pay_from_booking<'info>(
+    booking: &Account<'info, DateBooking>,
+    booking_vault: &InterfaceAccount<'info, TokenAccount>,
+    mint: &InterfaceAccount<'info, Mint>,
+    recipient_token_account: &InterfaceAccount<'info, TokenAccount>,
+    token_program: &Interface<'info, TokenInterface>,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let seeds = &[b"date", booking.pair_match.as_ref(), &[booking.bump]];
+    let signer_seeds = &[&seeds[..]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        TransferChecked {
+            from: booking_vault.to_account_info(),
+            mint: mint.to_account_info(),
+            to: recipient_token_account.to_account_info(),
+            authority: booking.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token_interface::transfer_checked(transfer_ctx, amount, mint.decimals)
+}
@@ -0,0 +1,199 @@
+use anchor_lang::prelude::*;
+use blake2::digest::consts::U24;
+use blake2::{Blake2b, Digest};
+
+use crate::errors::SolmatesError;
+use crate::states::UserProfile;
+
+/// Layout version - the first byte of every sealed envelope
+pub const ENVELOPE_VERSION: u8 = 1;
+/// Version byte, recipient key version (u32 LE) and ephemeral public key
+pub const ENVELOPE_HEADER_LEN: usize = 1 + 4 + 32;
+/// XSalsa20-Poly1305 nonce length
+pub const ENVELOPE_NONCE_LEN: usize = 24;
+/// Poly1305 authentication tag length
+pub const ENVELOPE_TAG_LEN: usize = 16;
+
+/// X25519 u-coordinates of small order (compared with the top bit cleared). A shared
+/// secret derived from any of them is predictable, so they can't be published.
+const SMALL_ORDER_KEYS: [[u8; 32]; 7] = [
+    [0; 32],
+    [
+        1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0,
+    ],
+    [
+        0xe0, 0xeb, 0x7a, 0x7c, 0x3b, 0x41, 0xb8, 0xae, 0x16, 0x56, 0xe3, 0xfa, 0xf1, 0x9f, 0xc4,
+        0x6a, 0xda, 0x09, 0x8d, 0xeb, 0x9c, 0x32, 0xb1, 0xfd, 0x86, 0x62, 0x05, 0x16, 0x5f, 0x49,
+        0xb8, 0x00,
+    ],
+    [
+        0x5f, 0x9c, 0x95, 0xbc, 0xa3, 0x50, 0x8c, 0x24, 0xb1, 0xd0, 0xb1, 0x55, 0x9c, 0x83, 0xef,
+        0x5b, 0x04, 0x44, 0x5c, 0xc4, 0x58, 0x1c, 0x8e, 0x86, 0xd8, 0x22, 0x4e, 0xdd, 0xd0, 0x9f,
+        0x11, 0x57,
+    ],
+    [
+        0xec, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        0xff, 0x7f,
+    ],
+    [
+        0xed, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        0xff, 0x7f,
+    ],
+    [
+        0xee, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        0xff, 0x7f,
+    ],
+];
+
+/// Rejects X25519 public keys no honest client would publish.
+pub fn validate_encryption_key(key: &[u8; 32]) -> Result<()> {
+    let mut masked = *key;
+    masked[31] &= 0x7f;
+    require!(
+        !SMALL_ORDER_KEYS.contains(&masked),
+        SolmatesError::InvalidEncryptionKey
+    );
+    Ok(())
+}
+
+/// A DM sealed to a recipient's published encryption key, in the layout clients
+/// exchange: `version (1) | key_version (u32 LE) | ephemeral_public_key (32) | ciphertext`.
+///
+/// Everything after the key version is a standard libsodium sealed box
+/// (`crypto_box_seal`): the sender generates a one-off X25519 keypair and encrypts with
+/// XSalsa20-Poly1305 between the ephemeral secret key and the recipient's key, using the
+/// nonce from [`SealedEnvelope::nonce`]. Only the recipient can open it, and the envelope
+/// reveals nothing about the sender. `key_version` tells the recipient which of their
+/// keys to open it with after a rotation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SealedEnvelope {
+    pub key_version: u32,
+    pub ephemeral_public_key: [u8; 32],
+    pub ciphertext: Vec<u8>,
+}
+
+impl SealedEnvelope {
+    /// Recipient key and key version to seal a message to, failing if the recipient
+    /// hasn't published one.
+    pub fn recipient_key(recipient: &UserProfile) -> Result<([u8; 32], u32)> {
+        let key = recipient
+            .encryption_key
+            .ok_or(SolmatesError::NoEncryptionKey)?;
+        Ok((key, recipient.encryption_key_version))
+    }
+
+    /// Sealed box nonce, bound to both public keys so neither side has to transmit it:
+    /// `blake2b-24(ephemeral || recipient)`, as `crypto_box_seal` derives it.
+    pub fn nonce(
+        ephemeral_public_key: &[u8; 32],
+        recipient_key: &[u8; 32],
+    ) -> [u8; ENVELOPE_NONCE_LEN] {
+        let mut hasher = Blake2b::<U24>::new();
+        hasher.update(ephemeral_public_key);
+        hasher.update(recipient_key);
+        hasher.finalize().into()
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(ENVELOPE_HEADER_LEN + self.ciphertext.len());
+        bytes.push(ENVELOPE_VERSION);
+        bytes.extend_from_slice(&self.key_version.to_le_bytes());
+        bytes.extend_from_slice(&self.ephemeral_public_key);
+        bytes.extend_from_slice(&self.ciphertext);
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        require!(
+            bytes.len() >= ENVELOPE_HEADER_LEN + ENVELOPE_TAG_LEN,
+            SolmatesError::InvalidEnvelope
+        );
+        require!(bytes[0] == ENVELOPE_VERSION, SolmatesError::InvalidEnvelope);
+
+        let mut key_version = [0u8; 4];
+        key_version.copy_from_slice(&bytes[1..5]);
+        let mut ephemeral_public_key = [0u8; 32];
+        ephemeral_public_key.copy_from_slice(&bytes[5..ENVELOPE_HEADER_LEN]);
+
+        Ok(SealedEnvelope {
+            key_version: u32::from_le_bytes(key_version),
+            ephemeral_public_key,
+            ciphertext: bytes[ENVELOPE_HEADER_LEN..].to_vec(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn envelope() -> SealedEnvelope {
+        SealedEnvelope {
+            key_version: 7,
+            ephemeral_public_key: [3; 32],
+            ciphertext: vec![9; ENVELOPE_TAG_LEN + 5],
+        }
+    }
+
+    #[test]
+    fn round_trips_through_bytes() {
+        let bytes = envelope().to_bytes();
+        assert_eq!(bytes.len(), ENVELOPE_HEADER_LEN + ENVELOPE_TAG_LEN + 5);
+        assert_eq!(bytes[0], ENVELOPE_VERSION);
+        assert_eq!(&bytes[1..5], &7u32.to_le_bytes());
+        assert_eq!(SealedEnvelope::from_bytes(&bytes).unwrap(), envelope());
+    }
+
+    #[test]
+    fn rejects_short_or_unknown_envelopes() {
+        let bytes = envelope().to_bytes();
+        let shortest = ENVELOPE_HEADER_LEN + ENVELOPE_TAG_LEN;
+        assert!(SealedEnvelope::from_bytes(&bytes[..shortest]).is_ok());
+        assert_eq!(
+            SealedEnvelope::from_bytes(&bytes[..shortest - 1]).unwrap_err(),
+            SolmatesError::InvalidEnvelope.into()
+        );
+
+        let mut unknown = bytes;
+        unknown[0] = ENVELOPE_VERSION + 1;
+        assert_eq!(
+            SealedEnvelope::from_bytes(&unknown).unwrap_err(),
+            SolmatesError::InvalidEnvelope.into()
+        );
+    }
+
+    #[test]
+    fn nonce_matches_crypto_box_seal() {
+        // blake2b-24([1; 32] || [2; 32]), as libsodium derives it
+        let expected = [
+            0x02, 0x7c, 0x5e, 0x23, 0x8e, 0xb7, 0x20, 0x85, 0x27, 0x60, 0xb5, 0x96, 0xd6, 0xb4,
+            0x70, 0xd4, 0x14, 0x5a, 0x35, 0x8c, 0x76, 0x29, 0xbf, 0x25,
+        ];
+        assert_eq!(SealedEnvelope::nonce(&[1; 32], &[2; 32]), expected);
+        assert_ne!(
+            SealedEnvelope::nonce(&[1; 32], &[2; 32]),
+            SealedEnvelope::nonce(&[2; 32], &[1; 32])
+        );
+    }
+
+    #[test]
+    fn rejects_small_order_keys() {
+        for key in SMALL_ORDER_KEYS {
+            assert_eq!(
+                validate_encryption_key(&key).unwrap_err(),
+                SolmatesError::InvalidEncryptionKey.into()
+            );
+
+            // The top bit is ignored by X25519, so setting it doesn't help
+            let mut high = key;
+            high[31] |= 0x80;
+            assert!(validate_encryption_key(&high).is_err());
+        }
+
+        assert!(validate_encryption_key(&[9; 32]).is_ok());
+    }
+}
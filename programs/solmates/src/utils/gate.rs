@@ -0,0 +1,214 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::errors::SolmatesError;
+use crate::states::{
+    AccessGate, Attestation, BidderRequirements, GateMode, GateRule, UserProfile, Verifier,
+};
+use crate::utils::{NftMetadata, TOKEN_METADATA_PROGRAM_ID};
+
+/// Verifies that `holder` satisfies `gate`.
+///
+/// Proof is supplied in `proof_accounts` (the instruction's remaining accounts), in any
+/// order: token accounts for fungible rules, plus the token account, mint and Metaplex
+/// metadata account of an NFT for collection rules, and the holder's profile, the
+/// attestation and its verifier's registry record for attestation rules. Every token
+/// account must be owned by `holder` and not frozen, and every profile controlled by
+/// `holder`, so a caller can't borrow someone else's balance or attestation to get past
+/// the gate.
+pub fn verify_gate(gate: &AccessGate, holder: &Pubkey, proof_accounts: &[AccountInfo]) -> Result<()> {
+    let proof = GateProof::load(holder, proof_accounts)?;
+
+    let mut first_failure = None;
+    for rule in &gate.rules {
+        match proof.check(rule) {
+            Ok(()) if gate.mode == GateMode::AnyOf => return Ok(()),
+            Ok(()) => {}
+            Err(err) if gate.mode == GateMode::AllOf => return Err(err),
+            Err(err) => {
+                first_failure.get_or_insert(err);
+            }
+        }
+    }
+
+    match first_failure {
+        Some(err) => Err(err),
+        None => Ok(()),
+    }
+}
+
+/// Verifies that `bidder` meets an auction's bidder requirements, applying the same gate
+/// checks `deposit_for_dm` uses for DM senders.
+pub fn verify_bidder(
+    requirements: &BidderRequirements,
+    bidder: &Pubkey,
+    bidder_profile: Option<&UserProfile>,
+    proof_accounts: &[AccountInfo],
+    current_time: i64,
+) -> Result<()> {
+    if !requirements.allowlist.is_empty() {
+        require!(
+            requirements.allowlist.contains(bidder),
+            SolmatesError::BidderNotAllowlisted
+        );
+    }
+
+    if requirements.min_profile_age > 0 {
+        let profile = bidder_profile.ok_or(SolmatesError::BidderProfileRequired)?;
+        let profile_age = current_time.checked_sub(profile.created_at).unwrap();
+        require!(
+            profile_age >= requirements.min_profile_age,
+            SolmatesError::ProfileTooNew
+        );
+    }
+
+    if let Some(gate) = &requirements.gate {
+        verify_gate(gate, bidder, proof_accounts)?;
+    }
+
+    Ok(())
+}
+
+struct GateProof {
+    holdings: Vec<TokenAccount>,
+    mints: Vec<(Pubkey, Mint)>,
+    nfts: Vec<NftMetadata>,
+    profiles: Vec<Pubkey>,
+    attestations: Vec<Attestation>,
+    verifiers: Vec<Pubkey>,
+}
+
+impl GateProof {
+    fn load(holder: &Pubkey, proof_accounts: &[AccountInfo]) -> Result<Self> {
+        let mut proof = GateProof {
+            holdings: Vec::with_capacity(proof_accounts.len()),
+            mints: Vec::new(),
+            nfts: Vec::new(),
+            profiles: Vec::new(),
+            attestations: Vec::new(),
+            verifiers: Vec::new(),
+        };
+
+        for info in proof_accounts {
+            if *info.owner == TOKEN_METADATA_PROGRAM_ID {
+                proof.nfts.push(NftMetadata::load(info)?);
+                continue;
+            }
+            if *info.owner == crate::ID {
+                proof.load_program_account(holder, info)?;
+                continue;
+            }
+
+            require!(
+                *info.owner == spl_token_2022::ID || *info.owner == anchor_spl::token::ID,
+                SolmatesError::GateAccountInvalid
+            );
+            let data = info.try_borrow_data()?;
+            let Ok(token_account) = TokenAccount::try_deserialize(&mut &data[..]) else {
+                let mint = Mint::try_deserialize(&mut &data[..])
+                    .map_err(|_| error!(SolmatesError::GateAccountInvalid))?;
+                proof.mints.push((info.key(), mint));
+                continue;
+            };
+
+            require_keys_eq!(
+                token_account.owner,
+                *holder,
+                SolmatesError::GateAccountOwnerMismatch
+            );
+            require!(!token_account.is_frozen(), SolmatesError::GateAccountFrozen);
+
+            proof.holdings.push(token_account);
+        }
+
+        Ok(proof)
+    }
+
+    fn load_program_account(&mut self, holder: &Pubkey, info: &AccountInfo) -> Result<()> {
+        let data = info.try_borrow_data()?;
+
+        if data.starts_with(UserProfile::DISCRIMINATOR) {
+            let profile = UserProfile::try_deserialize(&mut &data[..])
+                .map_err(|_| error!(SolmatesError::GateAccountInvalid))?;
+            require_keys_eq!(
+                profile.authority,
+                *holder,
+                SolmatesError::GateAccountOwnerMismatch
+            );
+            self.profiles.push(info.key());
+        } else if data.starts_with(Attestation::DISCRIMINATOR) {
+            let attestation = Attestation::try_deserialize(&mut &data[..])
+                .map_err(|_| error!(SolmatesError::GateAccountInvalid))?;
+            self.attestations.push(attestation);
+        } else if data.starts_with(Verifier::DISCRIMINATOR) {
+            let verifier = Verifier::try_deserialize(&mut &data[..])
+                .map_err(|_| error!(SolmatesError::GateAccountInvalid))?;
+            self.verifiers.push(verifier.verifier);
+        } else {
+            return err!(SolmatesError::GateAccountInvalid);
+        }
+
+        Ok(())
+    }
+
+    fn balance_of(&self, mint: &Pubkey) -> Option<u64> {
+        self.holdings
+            .iter()
+            .filter(|account| account.mint == *mint)
+            .map(|account| account.amount)
+            .max()
+    }
+
+    /// Whether `mint` was supplied and is a true NFT - indivisible with a single token
+    fn is_nft(&self, mint: &Pubkey) -> bool {
+        self.mints
+            .iter()
+            .any(|(key, account)| key == mint && account.decimals == 0 && account.supply == 1)
+    }
+
+    fn check(&self, rule: &GateRule) -> Result<()> {
+        match rule {
+            GateRule::Token { mint, min_amount } => {
+                let balance = self
+                    .balance_of(mint)
+                    .ok_or(SolmatesError::AssetGateRequired)?;
+
+                require!(
+                    balance >= *min_amount,
+                    SolmatesError::InsufficientAssetBalance
+                );
+                Ok(())
+            }
+            GateRule::Collection { collection } => {
+                // The metadata only proves the NFT exists - the holder must also own it
+                let held: Vec<&NftMetadata> = self
+                    .nfts
+                    .iter()
+                    .filter(|nft| nft.verified_collection() == Some(*collection))
+                    .filter(|nft| self.balance_of(&nft.mint).unwrap_or(0) > 0)
+                    .collect();
+                require!(!held.is_empty(), SolmatesError::CollectionNotHeld);
+
+                // A fungible or multi-edition mint can carry the same metadata
+                require!(
+                    held.iter().any(|nft| self.is_nft(&nft.mint)),
+                    SolmatesError::CollectionMintNotNft
+                );
+                Ok(())
+            }
+            GateRule::Attestation { claim_type } => {
+                let current_time = Clock::get()?.unix_timestamp;
+                let attested = self.attestations.iter().any(|attestation| {
+                    attestation.claim_type == *claim_type
+                        && attestation.is_valid(current_time)
+                        && self.profiles.contains(&attestation.profile)
+                        && self.verifiers.contains(&attestation.verifier)
+                });
+                require!(attested, SolmatesError::AttestationRequired);
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::SolmatesError;
+use crate::{MAX_HANDLE_LEN, MIN_HANDLE_LEN};
+
+/// Canonical form of a handle: ASCII lowercase. Clients normalize before deriving the
+/// handle PDA, since the program only accepts handles already in this form.
+pub fn normalize_handle(handle: &str) -> String {
+    handle.trim().to_ascii_lowercase()
+}
+
+/// Checks a handle is normalized, within length bounds, and uses only `a-z`, `0-9`
+/// and `_`.
+pub fn validate_handle(handle: &str) -> Result<()> {
+    require!(
+        handle.len() >= MIN_HANDLE_LEN && handle.len() <= MAX_HANDLE_LEN,
+        SolmatesError::InvalidHandleLength
+    );
+    require!(
+        normalize_handle(handle) == handle,
+        SolmatesError::HandleNotNormalized
+    );
+    require!(
+        handle
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'_'),
+        SolmatesError::InvalidHandleCharacters
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_case_and_surrounding_whitespace() {
+        assert_eq!(normalize_handle("Alice_Dates"), "alice_dates");
+        assert_eq!(normalize_handle("  bob42\n"), "bob42");
+        assert_eq!(normalize_handle("carol"), "carol");
+        // Only ASCII is folded - anything else is left for validation to reject
+        assert_eq!(normalize_handle("ÉVE"), "Éve");
+    }
+
+    #[test]
+    fn accepts_normalized_handles() {
+        assert!(validate_handle("alice_dates").is_ok());
+        assert!(validate_handle("bob_42").is_ok());
+        assert!(validate_handle(&"a".repeat(MIN_HANDLE_LEN)).is_ok());
+        assert!(validate_handle(&"a".repeat(MAX_HANDLE_LEN)).is_ok());
+    }
+
+    #[test]
+    fn rejects_unnormalized_handles() {
+        for handle in ["Alice", "ALICE", " alice", "alice "] {
+            assert_eq!(
+                validate_handle(handle).unwrap_err(),
+                SolmatesError::HandleNotNormalized.into()
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_characters() {
+        for handle in [
+            "alice-dates",
+            "alice.dates",
+            "alice dates",
+            "al!ce",
+            "éve_x",
+        ] {
+            assert_eq!(
+                validate_handle(handle).unwrap_err(),
+                SolmatesError::InvalidHandleCharacters.into()
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_handles_out_of_bounds() {
+        for handle in [
+            "".to_string(),
+            "a".repeat(MIN_HANDLE_LEN - 1),
+            "a".repeat(MAX_HANDLE_LEN + 1),
+        ] {
+            assert_eq!(
+                validate_handle(&handle).unwrap_err(),
+                SolmatesError::InvalidHandleLength.into()
+            );
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::SolmatesError;
+use crate::states::{Inbox, InboxPage, MessageEscrow};
+
+/// Removes a settled escrow from its recipient's inbox. Escrows deposited before inboxes
+/// existed were never indexed and need neither account; indexed ones require both.
+pub fn unindex_escrow(
+    escrow_key: &Pubkey,
+    escrow: &MessageEscrow,
+    inbox: Option<&mut Inbox>,
+    page: Option<&mut InboxPage>,
+) -> Result<()> {
+    let Some(page_index) = escrow.inbox_page else {
+        return Ok(());
+    };
+    let (Some(inbox), Some(page)) = (inbox, page) else {
+        return err!(SolmatesError::InboxRequired);
+    };
+    require!(page.index == page_index, SolmatesError::InvalidInboxPage);
+
+    inbox.remove(page, escrow_key);
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::SolmatesError;
+
+/// Metaplex Token Metadata program
+pub const TOKEN_METADATA_PROGRAM_ID: Pubkey =
+    pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+/// Account discriminator Metaplex writes as the first byte of a metadata account
+const METADATA_V1_KEY: u8 = 4;
+
+/// Leading fields of a Metaplex `Metadata` account, up to and including `collection`.
+///
+/// Decoded by hand to avoid pulling in the full Metaplex crate for a single lookup.
+/// Older accounts written before `token_standard`/`collection` existed are zero-padded,
+/// so those fields decode as `None`.
+#[derive(AnchorDeserialize)]
+pub struct NftMetadata {
+    pub key: u8,
+    pub update_authority: Pubkey,
+    pub mint: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub seller_fee_basis_points: u16,
+    pub creators: Option<Vec<Creator>>,
+    pub primary_sale_happened: bool,
+    pub is_mutable: bool,
+    pub edition_nonce: Option<u8>,
+    pub token_standard: Option<u8>,
+    pub collection: Option<Collection>,
+}
+
+#[derive(AnchorDeserialize)]
+pub struct Creator {
+    pub address: Pubkey,
+    pub verified: bool,
+    pub share: u8,
+}
+
+#[derive(AnchorDeserialize)]
+pub struct Collection {
+    pub verified: bool,
+    pub key: Pubkey,
+}
+
+impl NftMetadata {
+    /// Decodes a metadata account, rejecting anything not written by the Metaplex program.
+    pub fn load(info: &AccountInfo) -> Result<Self> {
+        require_keys_eq!(
+            *info.owner,
+            TOKEN_METADATA_PROGRAM_ID,
+            SolmatesError::GateAccountInvalid
+        );
+
+        let data = info.try_borrow_data()?;
+        let metadata = Self::deserialize(&mut &data[..])
+            .map_err(|_| error!(SolmatesError::GateAccountInvalid))?;
+        require!(
+            metadata.key == METADATA_V1_KEY,
+            SolmatesError::GateAccountInvalid
+        );
+
+        Ok(metadata)
+    }
+
+    /// Collection this NFT belongs to, if the collection authority has verified it
+    pub fn verified_collection(&self) -> Option<Pubkey> {
+        self.collection
+            .as_ref()
+            .filter(|collection| collection.verified)
+            .map(|collection| collection.key)
+    }
+}
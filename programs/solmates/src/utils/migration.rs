@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use anchor_lang::Discriminator;
+
+use crate::errors::SolmatesError;
+
+/// Reads the body of a pre-versioning account of type `T`, identified by its discriminator
+/// and its exact legacy length.
+pub fn load_legacy<T: Discriminator, L: AnchorDeserialize>(
+    account: &AccountInfo,
+    legacy_space: usize,
+) -> Result<L> {
+    let data = account.try_borrow_data()?;
+    require!(
+        data.starts_with(T::DISCRIMINATOR),
+        SolmatesError::InvalidLegacyAccount
+    );
+    require!(
+        data.len() == legacy_space,
+        SolmatesError::AccountAlreadyMigrated
+    );
+
+    L::deserialize(&mut &data[T::DISCRIMINATOR.len()..])
+        .map_err(|_| error!(SolmatesError::InvalidLegacyAccount))
+}
+
+/// Grows `account` to `new_space` bytes, topping up its rent from `payer`, and writes
+/// `state` over it in the current layout.
+pub fn write_migrated<'info, T: AccountSerialize>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    new_space: usize,
+    state: &T,
+) -> Result<()> {
+    let rent_due = Rent::get()?
+        .minimum_balance(new_space)
+        .saturating_sub(account.lamports());
+    if rent_due > 0 {
+        let transfer_ctx = CpiContext::new(
+            system_program.clone(),
+            Transfer {
+                from: payer.clone(),
+                to: account.clone(),
+            },
+        );
+        system_program::transfer(transfer_ctx, rent_due)?;
+    }
+
+    account.resize(new_space)?;
+
+    let mut data = account.try_borrow_mut_data()?;
+    state.try_serialize(&mut &mut data[..])?;
+
+    Ok(())
+}
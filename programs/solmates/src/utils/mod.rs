@@ -0,0 +1,23 @@
+pub mod bounty;
+pub mod date;
+pub mod envelope;
+pub mod gate;
+pub mod handle;
+pub mod inbox;
+pub mod metadata;
+pub mod migration;
+pub mod profile;
+pub mod session;
+pub mod token;
+
+pub use bounty::*;
+pub use date::*;
+pub use envelope::*;
+pub use gate::*;
+pub use handle::*;
+pub use inbox::*;
+pub use metadata::*;
+pub use migration::*;
+pub use profile::*;
+pub use session::*;
+pub use token::*;
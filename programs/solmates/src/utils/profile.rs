@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::SolmatesError;
+use crate::states::UserProfile;
+
+/// Applies `update` to the profile at a profile PDA the instruction always takes, if one
+/// exists. Passing the PDA unconditionally keeps a counterparty from skipping the update
+/// by leaving the profile out; an outdated profile has to be migrated first.
+pub fn update_profile_if_exists(
+    profile: &AccountInfo,
+    update: impl FnOnce(&mut UserProfile),
+) -> Result<()> {
+    if profile.owner != &crate::ID || profile.data_is_empty() {
+        return Ok(());
+    }
+
+    let mut state = UserProfile::try_deserialize(&mut &profile.try_borrow_data()?[..])
+        .map_err(|_| error!(SolmatesError::AccountVersionMismatch))?;
+    require!(
+        state.version == UserProfile::VERSION,
+        SolmatesError::AccountVersionMismatch
+    );
+    update(&mut state);
+    state.try_serialize(&mut &mut profile.try_borrow_mut_data()?[..])?;
+
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::SolmatesError;
+use crate::states::SessionDelegate;
+
+/// Checks that `signer` may act for `owner` within `scope`: either `signer` is the owner,
+/// or `session` is a live session the owner granted to `signer` covering `scope`.
+///
+/// Callers derive `session` from `[b"session", owner, signer]`, so its authority and
+/// delegate are already bound to these keys.
+pub fn authorize_session(
+    owner: &Pubkey,
+    signer: &Pubkey,
+    session: Option<&SessionDelegate>,
+    scope: u8,
+) -> Result<()> {
+    if signer == owner {
+        return Ok(());
+    }
+
+    let session = session.ok_or(SolmatesError::Unauthorized)?;
+    require!(
+        Clock::get()?.unix_timestamp < session.expires_at,
+        SolmatesError::SessionExpired
+    );
+    require!(
+        session.scopes & scope == scope,
+        SolmatesError::SessionScopeNotGranted
+    );
+
+    Ok(())
+}
@@ -0,0 +1,167 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{
+        non_transferable::NonTransferable, permanent_delegate::PermanentDelegate,
+        transfer_hook::TransferHook, BaseStateWithExtensions, StateWithExtensions,
+    },
+};
+use anchor_spl::token_interface::Mint;
+
+use crate::errors::SolmatesError;
+
+/// Rejects Token-2022 mints carrying extensions that would let a third party move,
+/// lock or intercept tokens held in a program vault. Legacy SPL Token mints have no
+/// extensions and always pass.
+pub fn validate_mint(mint: &InterfaceAccount<Mint>) -> Result<()> {
+    let mint_info = mint.to_account_info();
+    if *mint_info.owner != spl_token_2022::ID {
+        return Ok(());
+    }
+
+    let mint_data = mint_info.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+
+    // A permanent delegate can drain any vault holding this mint
+    if let Ok(extension) = mint_state.get_extension::<PermanentDelegate>() {
+        require!(
+            Option::<Pubkey>::from(extension.delegate).is_none(),
+            SolmatesError::PermanentDelegateNotSupported
+        );
+    }
+
+    // Non-transferable tokens could never leave the vault
+    require!(
+        mint_state.get_extension::<NonTransferable>().is_err(),
+        SolmatesError::NonTransferableMint
+    );
+
+    // Transfer hooks need extra accounts on every CPI, which the vault flows don't pass.
+    // An unset hook program can still be set later by the hook authority, so the
+    // extension is refused outright.
+    require!(
+        mint_state.get_extension::<TransferHook>().is_err(),
+        SolmatesError::TransferHookNotSupported
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::program_pack::Pack;
+    use anchor_spl::token::spl_token;
+    use anchor_spl::token_2022::spl_token_2022::{
+        extension::{BaseStateWithExtensionsMut, ExtensionType, StateWithExtensionsMut},
+        state::Mint as Token2022Mint,
+    };
+
+    fn base_mint() -> Token2022Mint {
+        Token2022Mint {
+            decimals: 6,
+            is_initialized: true,
+            ..Default::default()
+        }
+    }
+
+    /// Token-2022 mint carrying `extension`, left at its default (unset) values unless
+    /// `delegate` is given for a permanent delegate
+    fn token_2022_mint(extension: Option<ExtensionType>, delegate: Option<Pubkey>) -> Vec<u8> {
+        let extensions: Vec<ExtensionType> = extension.into_iter().collect();
+        let len = ExtensionType::try_calculate_account_len::<Token2022Mint>(&extensions).unwrap();
+        let mut data = vec![0u8; len];
+        let mut state =
+            StateWithExtensionsMut::<Token2022Mint>::unpack_uninitialized(&mut data).unwrap();
+        match extension {
+            Some(ExtensionType::TransferHook) => {
+                state.init_extension::<TransferHook>(true).unwrap();
+            }
+            Some(ExtensionType::NonTransferable) => {
+                state.init_extension::<NonTransferable>(true).unwrap();
+            }
+            Some(ExtensionType::PermanentDelegate) => {
+                let extension = state.init_extension::<PermanentDelegate>(true).unwrap();
+                extension.delegate = delegate.try_into().unwrap();
+            }
+            _ => {}
+        }
+        state.base = base_mint();
+        state.pack_base();
+        state.init_account_type().unwrap();
+        data
+    }
+
+    fn validate(owner: Pubkey, mut data: Vec<u8>) -> Result<()> {
+        let key = Pubkey::new_unique();
+        let mut lamports = 1_000_000;
+        let info = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+        validate_mint(&InterfaceAccount::<Mint>::try_from(&info)?)
+    }
+
+    #[test]
+    fn accepts_legacy_and_plain_token_2022_mints() {
+        let mut legacy = vec![0u8; spl_token::state::Mint::LEN];
+        spl_token::state::Mint {
+            decimals: 6,
+            is_initialized: true,
+            ..Default::default()
+        }
+        .pack_into_slice(&mut legacy);
+        assert!(validate(spl_token::ID, legacy).is_ok());
+
+        assert!(validate(spl_token_2022::ID, token_2022_mint(None, None)).is_ok());
+    }
+
+    #[test]
+    fn rejects_any_transfer_hook() {
+        // No hook program yet, but its authority could set one after the vault opens
+        assert_eq!(
+            validate(
+                spl_token_2022::ID,
+                token_2022_mint(Some(ExtensionType::TransferHook), None)
+            )
+            .unwrap_err(),
+            SolmatesError::TransferHookNotSupported.into()
+        );
+    }
+
+    #[test]
+    fn rejects_non_transferable_and_delegated_mints() {
+        assert_eq!(
+            validate(
+                spl_token_2022::ID,
+                token_2022_mint(Some(ExtensionType::NonTransferable), None)
+            )
+            .unwrap_err(),
+            SolmatesError::NonTransferableMint.into()
+        );
+        assert_eq!(
+            validate(
+                spl_token_2022::ID,
+                token_2022_mint(
+                    Some(ExtensionType::PermanentDelegate),
+                    Some(Pubkey::new_unique())
+                )
+            )
+            .unwrap_err(),
+            SolmatesError::PermanentDelegateNotSupported.into()
+        );
+
+        // A permanent delegate that was never set can't be set later
+        assert!(validate(
+            spl_token_2022::ID,
+            token_2022_mint(Some(ExtensionType::PermanentDelegate), None)
+        )
+        .is_ok());
+    }
+}
@@ -2,6 +2,7 @@ import * as anchor from "@coral-xyz/anchor";
 import { Program, BN } from "@coral-xyz/anchor";
 import { Solmates } from "../target/types/solmates";
 import {
+  AccountMeta,
   Keypair,
   PublicKey,
   SystemProgram,
@@ -14,6 +15,7 @@ import {
   getAccount,
   getAssociatedTokenAddressSync,
   TOKEN_PROGRAM_ID,
+  TOKEN_2022_PROGRAM_ID,
   ASSOCIATED_TOKEN_PROGRAM_ID,
 } from "@solana/spl-token";
 import { expect } from "chai";
@@ -48,6 +50,15 @@ describe("solmates", () => {
   // Treasury address for platform fees
   const TREASURY = new PublicKey("2CquYcQoBGv8MiiMfP3Lgut79oLCtDbCTrB6fnQm1WeG");
 
+  // Metaplex NFTs claiming one collection, loaded by the test validator from tests/fixtures.
+  // Their mint authority is the fixed keypair below, so tests can mint them out.
+  const NFT_COLLECTION = new PublicKey("BpZQGyPqGbBgXhjDNim4bs1m2D46PRqRjqyuGWEew4yt");
+  const NFT_MINT = new PublicKey("9zjZqUyjDEHEp8DYgKBhJizYutxXWFK2FSqdAXGYYszA"); // Verified member
+  const UNVERIFIED_NFT_MINT = new PublicKey("24P7yxYf6UW3kZswDnBHNvNLhd5f3DKU7VNfcq2Q9bNf"); // Collection not verified
+  const FUNGIBLE_NFT_MINT = new PublicKey("DkUkhvY2GQEx4RbjiJWtTDRrtuAse31Cd7Divhq3Uk5m"); // Verified member, but not one of one
+  const TOKEN_METADATA_PROGRAM_ID = new PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+  const nftMintAuthority = Keypair.fromSeed(new Uint8Array(32).fill(7));
+
   before(async () => {
     // Generate keypairs
     mintAuthority = Keypair.generate();
@@ -172,6 +183,129 @@ describe("solmates", () => {
     );
   }
 
+  function getSessionPda(
+    authority: PublicKey,
+    delegate: PublicKey
+  ): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("session"), authority.toBuffer(), delegate.toBuffer()],
+      program.programId
+    );
+  }
+
+  function getInboxPda(recipient: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("inbox"), recipient.toBuffer()],
+      program.programId
+    );
+  }
+
+  function getInboxPagePda(recipient: PublicKey, index: number): [PublicKey, number] {
+    const indexBuffer = Buffer.alloc(4);
+    indexBuffer.writeUInt32LE(index);
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("inbox_page"), recipient.toBuffer(), indexBuffer],
+      program.programId
+    );
+  }
+
+  // Page a new escrow to `recipient` will be indexed in
+  async function getInsertPagePda(recipient: PublicKey): Promise<PublicKey> {
+    const inbox = await program.account.inbox.fetchNullable(getInboxPda(recipient)[0]);
+    return getInboxPagePda(recipient, inbox ? inbox.insertPage : 0)[0];
+  }
+
+  // Page an existing escrow is indexed in
+  async function getEscrowPagePda(
+    escrow: PublicKey,
+    recipient: PublicKey
+  ): Promise<PublicKey> {
+    const { inboxPage } = await program.account.messageEscrow.fetch(escrow);
+    return getInboxPagePda(recipient, inboxPage ?? 0)[0];
+  }
+
+  function getSubmissionPda(
+    bounty: PublicKey,
+    matchmaker: PublicKey
+  ): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("submission"), bounty.toBuffer(), matchmaker.toBuffer()],
+      program.programId
+    );
+  }
+
+  function getContributionPda(
+    bounty: PublicKey,
+    contributor: PublicKey
+  ): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("contribution"), bounty.toBuffer(), contributor.toBuffer()],
+      program.programId
+    );
+  }
+
+  function getLikePda(liker: PublicKey, likee: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("like"), liker.toBuffer(), likee.toBuffer()],
+      program.programId
+    );
+  }
+
+  function getMatchPda(first: PublicKey, second: PublicKey): [PublicKey, number] {
+    const [userA, userB] =
+      Buffer.compare(first.toBuffer(), second.toBuffer()) <= 0
+        ? [first, second]
+        : [second, first];
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("match"), userA.toBuffer(), userB.toBuffer()],
+      program.programId
+    );
+  }
+
+  function getMetadataPda(mint: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("metadata"), TOKEN_METADATA_PROGRAM_ID.toBuffer(), mint.toBuffer()],
+      TOKEN_METADATA_PROGRAM_ID
+    )[0];
+  }
+
+  // Mints `amount` of a fixture NFT to `owner`, returning their token account
+  async function mintFixtureNft(
+    mint: PublicKey,
+    owner: Keypair,
+    amount: number
+  ): Promise<PublicKey> {
+    const tokenAccount = await createAssociatedTokenAccount(
+      provider.connection,
+      owner,
+      mint,
+      owner.publicKey
+    );
+    await mintTo(provider.connection, owner, mint, tokenAccount, nftMintAuthority, amount);
+    return tokenAccount;
+  }
+
+  function getSuperLikePda(liker: PublicKey, likee: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("super_like"), liker.toBuffer(), likee.toBuffer()],
+      program.programId
+    );
+  }
+
+  function getDateBookingPda(pairMatch: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("date"), pairMatch.toBuffer()],
+      program.programId
+    );
+  }
+
+  function getMatchmakerPda(matchmaker: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("matchmaker"), matchmaker.toBuffer()],
+      program.programId
+    );
+  }
+
   function getBountyPda(issuer: PublicKey): [PublicKey, number] {
     return PublicKey.findProgramAddressSync(
       [Buffer.from("bounty"), issuer.toBuffer()],
@@ -186,10 +320,9 @@ describe("solmates", () => {
     it("creates a profile for alice", async () => {
       const [profilePda] = getProfilePda(alice.publicKey);
       const dmPrice = new BN(5 * ONE_USDC); // 5 USDC to DM
-      const minAssetAmount = new BN(0);
 
       const tx = await program.methods
-        .createProfile(dmPrice, null, minAssetAmount)
+        .createProfile(dmPrice, null, null)
         .accountsStrict({
           authority: alice.publicKey,
           profile: profilePda,
@@ -210,10 +343,9 @@ describe("solmates", () => {
     it("creates a profile for bob", async () => {
       const [profilePda] = getProfilePda(bob.publicKey);
       const dmPrice = new BN(TEN_USDC); // 10 USDC to DM
-      const minAssetAmount = new BN(0);
 
       await program.methods
-        .createProfile(dmPrice, null, minAssetAmount)
+        .createProfile(dmPrice, null, null)
         .accountsStrict({
           authority: bob.publicKey,
           profile: profilePda,
@@ -230,10 +362,9 @@ describe("solmates", () => {
     it("creates a profile for charlie (matchmaker)", async () => {
       const [profilePda] = getProfilePda(charlie.publicKey);
       const dmPrice = new BN(ONE_USDC);
-      const minAssetAmount = new BN(0);
 
       await program.methods
-        .createProfile(dmPrice, null, minAssetAmount)
+        .createProfile(dmPrice, null, null)
         .accountsStrict({
           authority: charlie.publicKey,
           profile: profilePda,
@@ -251,9 +382,11 @@ describe("solmates", () => {
       const newDmPrice = new BN(8 * ONE_USDC); // Update to 8 USDC
 
       const tx = await program.methods
-        .updateProfile(newDmPrice, null, null)
+        .updateProfile(newDmPrice, null, null, null)
         .accountsStrict({
           authority: alice.publicKey,
+          signer: alice.publicKey,
+          session: null,
           profile: profilePda,
         })
         .signers([alice])
@@ -264,6 +397,52 @@ describe("solmates", () => {
       const profile = await program.account.userProfile.fetch(profilePda);
       expect(profile.dmPrice.toNumber()).to.equal(8 * ONE_USDC);
     });
+
+    it("bob publishes and rotates an encryption key", async () => {
+      const [profilePda] = getProfilePda(bob.publicKey);
+      const updateAccounts = {
+        authority: bob.publicKey,
+        signer: bob.publicKey,
+        session: null,
+        profile: profilePda,
+      };
+
+      await program.methods
+        .updateProfile(null, null, Array.from(Keypair.generate().publicKey.toBytes()), null)
+        .accountsStrict(updateAccounts)
+        .signers([bob])
+        .rpc();
+
+      const rotatedKey = Array.from(Keypair.generate().publicKey.toBytes());
+      await program.methods
+        .updateProfile(null, null, rotatedKey, null)
+        .accountsStrict(updateAccounts)
+        .signers([bob])
+        .rpc();
+
+      const profile = await program.account.userProfile.fetch(profilePda);
+      expect(profile.encryptionKey).to.deep.equal(rotatedKey);
+      expect(profile.encryptionKeyVersion).to.equal(2);
+    });
+
+    it("rejects a small-order encryption key", async () => {
+      try {
+        await program.methods
+          .updateProfile(null, null, new Array(32).fill(0), null)
+          .accountsStrict({
+            authority: bob.publicKey,
+            signer: bob.publicKey,
+            session: null,
+            profile: getProfilePda(bob.publicKey)[0],
+          })
+          .signers([bob])
+          .rpc();
+
+        expect.fail("Should have thrown InvalidEncryptionKey error");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("InvalidEncryptionKey");
+      }
+    });
   });
 
   // ============================================
@@ -289,9 +468,10 @@ describe("solmates", () => {
           recipientProfile: bobProfilePda,
           mint: usdcMint,
           senderTokenAccount: aliceTokenAccount,
-          senderGateTokenAccount: null,
           escrow: escrowPda,
           escrowVault: escrowVault,
+          inbox: getInboxPda(bob.publicKey)[0],
+          inboxPage: await getInsertPagePda(bob.publicKey),
           tokenProgram: TOKEN_PROGRAM_ID,
           associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
@@ -307,6 +487,15 @@ describe("solmates", () => {
       expect(escrow.recipient.toString()).to.equal(bob.publicKey.toString());
       expect(escrow.amount.toNumber()).to.equal(TEN_USDC);
       expect(escrow.status).to.deep.equal({ pending: {} });
+      expect(escrow.inboxPage).to.equal(0);
+
+      // Verify the escrow was indexed in bob's inbox
+      const inbox = await program.account.inbox.fetch(getInboxPda(bob.publicKey)[0]);
+      expect(inbox.pendingCount).to.equal(1);
+      const page = await program.account.inboxPage.fetch(
+        getInboxPagePda(bob.publicKey, 0)[0]
+      );
+      expect(page.escrows.map((e) => e.toString())).to.include(escrowPda.toString());
 
       // Verify USDC was transferred
       const finalBalance = (
@@ -336,9 +525,15 @@ describe("solmates", () => {
         .accountsStrict({
           sender: alice.publicKey,
           recipient: bob.publicKey,
+          recipientAuthority: bob.publicKey,
+          signer: bob.publicKey,
+          session: null,
+          recipientProfile: getProfilePda(bob.publicKey)[0],
           mint: usdcMint,
           escrow: escrowPda,
           escrowVault: escrowVault,
+          inbox: getInboxPda(bob.publicKey)[0],
+          inboxPage: await getEscrowPagePda(escrowPda, bob.publicKey),
           recipientTokenAccount: bobTokenAccount,
           treasury: TREASURY,
           treasuryTokenAccount: treasuryTokenAccount,
@@ -358,6 +553,17 @@ describe("solmates", () => {
       const expectedAmount = TEN_USDC - Math.floor(TEN_USDC / 100); // 99% after 1% fee
       expect(Number(finalBalance) - Number(initialBalance)).to.equal(expectedAmount);
 
+      // Verify bob's DM reputation was credited
+      const bobProfile = await program.account.userProfile.fetch(
+        getProfilePda(bob.publicKey)[0]
+      );
+      expect(bobProfile.reputation.dmsAccepted).to.equal(1);
+      expect(bobProfile.reputation.dmEarned.toNumber()).to.equal(expectedAmount);
+
+      // Verify the escrow left bob's inbox
+      const inbox = await program.account.inbox.fetch(getInboxPda(bob.publicKey)[0]);
+      expect(inbox.pendingCount).to.equal(0);
+
       // Verify escrow vault is empty (tokens transferred out)
       const vaultBalance = await getAccount(provider.connection, escrowVault);
       expect(Number(vaultBalance.amount)).to.equal(0);
@@ -378,9 +584,10 @@ describe("solmates", () => {
           recipientProfile: aliceProfilePda,
           mint: usdcMint,
           senderTokenAccount: charlieTokenAccount,
-          senderGateTokenAccount: null,
           escrow: escrowPda,
           escrowVault: escrowVault,
+          inbox: getInboxPda(alice.publicKey)[0],
+          inboxPage: await getInsertPagePda(alice.publicKey),
           tokenProgram: TOKEN_PROGRAM_ID,
           associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
@@ -391,343 +598,2674 @@ describe("solmates", () => {
       const escrow = await program.account.messageEscrow.fetch(escrowPda);
       expect(escrow.status).to.deep.equal({ pending: {} });
     });
-  });
 
-  // ============================================
-  // DATE AUCTION TESTS
-  // ============================================
-  describe("Date Auction", () => {
-    it("alice creates a date auction", async () => {
-      const [aliceProfile] = getProfilePda(alice.publicKey);
-      const profile = await program.account.userProfile.fetch(aliceProfile);
-      const auctionId = profile.auctionCount.toNumber();
-
-      const [auctionPda] = getAuctionPda(alice.publicKey, auctionId);
-      const auctionVault = getAssociatedTokenAddressSync(
-        usdcMint,
-        auctionPda,
-        true
+    it("bob deposits a Token-2022 stablecoin to DM charlie", async () => {
+      const token2022Mint = await createMint(
+        provider.connection,
+        mintAuthority,
+        mintAuthority.publicKey,
+        null,
+        USDC_DECIMALS,
+        undefined,
+        undefined,
+        TOKEN_2022_PROGRAM_ID
+      );
+      const bobToken2022Account = await createAssociatedTokenAccount(
+        provider.connection,
+        bob,
+        token2022Mint,
+        bob.publicKey,
+        undefined,
+        TOKEN_2022_PROGRAM_ID
+      );
+      await mintTo(
+        provider.connection,
+        mintAuthority,
+        token2022Mint,
+        bobToken2022Account,
+        mintAuthority,
+        TEN_USDC,
+        [],
+        undefined,
+        TOKEN_2022_PROGRAM_ID
       );
 
-      const startPrice = new BN(TEN_USDC); // 10 USDC starting price
-      const durationSecs = new BN(24 * 60 * 60); // 24 hours
+      const [escrowPda] = getEscrowPda(bob.publicKey, charlie.publicKey);
+      const [charlieProfilePda] = getProfilePda(charlie.publicKey);
+      const escrowVault = getAssociatedTokenAddressSync(
+        token2022Mint,
+        escrowPda,
+        true,
+        TOKEN_2022_PROGRAM_ID
+      );
 
-      const tx = await program.methods
-        .createAuction(startPrice, durationSecs)
+      await program.methods
+        .depositForDm(new BN(2 * ONE_USDC))
         .accountsStrict({
-          host: alice.publicKey,
-          hostProfile: aliceProfile,
-          mint: usdcMint,
-          auction: auctionPda,
-          auctionVault: auctionVault,
-          tokenProgram: TOKEN_PROGRAM_ID,
+          sender: bob.publicKey,
+          recipient: charlie.publicKey,
+          recipientProfile: charlieProfilePda,
+          mint: token2022Mint,
+          senderTokenAccount: bobToken2022Account,
+          escrow: escrowPda,
+          escrowVault: escrowVault,
+          inbox: getInboxPda(charlie.publicKey)[0],
+          inboxPage: await getInsertPagePda(charlie.publicKey),
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
           associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
         })
-        .signers([alice])
+        .signers([bob])
         .rpc();
 
-      console.log("Create auction tx:", tx);
-
-      // Verify auction was created
-      const auction = await program.account.dateAuction.fetch(auctionPda);
-      expect(auction.host.toString()).to.equal(alice.publicKey.toString());
-      expect(auction.auctionId.toNumber()).to.equal(auctionId);
-      expect(auction.highestBidder.toString()).to.equal(alice.publicKey.toString()); // Initially host
-      expect(auction.highestBid.toNumber()).to.equal(TEN_USDC); // Start price
+      const escrow = await program.account.messageEscrow.fetch(escrowPda);
+      expect(escrow.amount.toNumber()).to.equal(2 * ONE_USDC);
 
-      // Verify profile auction count incremented
-      const updatedProfile = await program.account.userProfile.fetch(aliceProfile);
-      expect(updatedProfile.auctionCount.toNumber()).to.equal(auctionId + 1);
+      const vault = await getAccount(
+        provider.connection,
+        escrowVault,
+        undefined,
+        TOKEN_2022_PROGRAM_ID
+      );
+      expect(Number(vault.amount)).to.equal(2 * ONE_USDC);
     });
 
-    it("bob places a bid on alice's auction", async () => {
-      const [aliceProfile] = getProfilePda(alice.publicKey);
-      const profile = await program.account.userProfile.fetch(aliceProfile);
-      const auctionId = profile.auctionCount.toNumber() - 1; // Last auction
+    it("rejects deposits beyond charlie's inbox limit", async () => {
+      const [charlieProfilePda] = getProfilePda(charlie.publicKey);
+      const setLimit = (maxPending: number) =>
+        program.methods
+          .setInboxLimit(maxPending)
+          .accountsStrict({
+            authority: charlie.publicKey,
+            profile: charlieProfilePda,
+            inbox: getInboxPda(charlie.publicKey)[0],
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([charlie])
+          .rpc();
 
-      const [auctionPda] = getAuctionPda(alice.publicKey, auctionId);
-      const auctionVault = getAssociatedTokenAddressSync(
-        usdcMint,
-        auctionPda,
-        true
-      );
+      // Bob's Token-2022 DM already fills a limit of one
+      await setLimit(1);
 
-      const bidAmount = new BN(15 * ONE_USDC); // 15 USDC
+      const [escrowPda] = getEscrowPda(alice.publicKey, charlie.publicKey);
+      try {
+        await program.methods
+          .depositForDm(new BN(ONE_USDC))
+          .accountsStrict({
+            sender: alice.publicKey,
+            recipient: charlie.publicKey,
+            recipientProfile: charlieProfilePda,
+            mint: usdcMint,
+            senderTokenAccount: aliceTokenAccount,
+            escrow: escrowPda,
+            escrowVault: getAssociatedTokenAddressSync(usdcMint, escrowPda, true),
+            inbox: getInboxPda(charlie.publicKey)[0],
+            inboxPage: await getInsertPagePda(charlie.publicKey),
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([alice])
+          .rpc();
 
-      const initialBalance = (
-        await getAccount(provider.connection, bobTokenAccount)
-      ).amount;
+        expect.fail("Should have thrown InboxFull error");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("InboxFull");
+      }
 
-      const tx = await program.methods
-        .placeBid(bidAmount)
+      await setLimit(0);
+      const inbox = await program.account.inbox.fetch(getInboxPda(charlie.publicKey)[0]);
+      expect(inbox.maxPending).to.equal(0);
+      expect(inbox.pendingCount).to.equal(1);
+    });
+
+    it("bob prices DMs by recent demand", async () => {
+      const [bobProfilePda] = getProfilePda(bob.publicKey);
+      await program.methods
+        .updateProfile(null, null, null, {
+          base: new BN(5 * ONE_USDC),
+          slope: new BN(2 * ONE_USDC),
+          cap: new BN(TEN_USDC),
+          signal: { recentAcceptances: {} },
+        })
         .accountsStrict({
-          bidder: bob.publicKey,
-          previousBidder: alice.publicKey, // Host is previous bidder
-          host: alice.publicKey,
-          mint: usdcMint,
-          auction: auctionPda,
-          auctionVault: auctionVault,
-          bidderTokenAccount: bobTokenAccount,
-          previousBidderTokenAccount: aliceTokenAccount,
-          tokenProgram: TOKEN_PROGRAM_ID,
+          authority: bob.publicKey,
+          signer: bob.publicKey,
+          session: null,
+          profile: bobProfilePda,
         })
         .signers([bob])
         .rpc();
 
-      console.log("Place bid tx:", tx);
-
-      // Verify auction was updated
-      const auction = await program.account.dateAuction.fetch(auctionPda);
-      expect(auction.highestBidder.toString()).to.equal(bob.publicKey.toString());
-      expect(auction.highestBid.toNumber()).to.equal(15 * ONE_USDC);
+      // Bob accepted alice's DM within the window: 5 + 2 * 1 USDC
+      const quote = await program.methods
+        .quoteDmPrice()
+        .accountsStrict({ recipientProfile: bobProfilePda })
+        .view();
+      expect(quote.toNumber()).to.equal(7 * ONE_USDC);
+    });
 
-      // Verify USDC was transferred
-      const finalBalance = (
-        await getAccount(provider.connection, bobTokenAccount)
-      ).amount;
-      expect(Number(initialBalance) - Number(finalBalance)).to.equal(15 * ONE_USDC);
+    it("rejects a pricing curve capped below its base", async () => {
+      try {
+        await program.methods
+          .updateProfile(null, null, null, {
+            base: new BN(TEN_USDC),
+            slope: new BN(ONE_USDC),
+            cap: new BN(ONE_USDC),
+            signal: { pendingEscrows: {} },
+          })
+          .accountsStrict({
+            authority: bob.publicKey,
+            signer: bob.publicKey,
+            session: null,
+            profile: getProfilePda(bob.publicKey)[0],
+          })
+          .signers([bob])
+          .rpc();
 
-      // Verify vault has the bid
-      const vaultBalance = (
-        await getAccount(provider.connection, auctionVault)
-      ).amount;
-      expect(Number(vaultBalance)).to.equal(15 * ONE_USDC);
+        expect.fail("Should have thrown InvalidDmPricing error");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("InvalidDmPricing");
+      }
     });
 
-    it("charlie outbids bob (bob gets refunded)", async () => {
-      const [aliceProfile] = getProfilePda(alice.publicKey);
-      const profile = await program.account.userProfile.fetch(aliceProfile);
-      const auctionId = profile.auctionCount.toNumber() - 1;
-
-      const [auctionPda] = getAuctionPda(alice.publicKey, auctionId);
-      const auctionVault = getAssociatedTokenAddressSync(
-        usdcMint,
-        auctionPda,
-        true
+    it("refunding an expired DM always settles the recipient's profile", async () => {
+      // The escrow, its vault and the recipient's profile are loaded from tests/fixtures,
+      // with an expiry long past
+      const sender = Keypair.fromSeed(new Uint8Array(32).fill(9));
+      const recipient = new PublicKey("QeTjxj98ZvP2dNMdHnNaBAdw23UapQmYGPwbA86pXpC");
+      const mint = new PublicKey("7gVRbZD1W6CTHAsCnAH5iR4DiEfWpHCnrTZEjbEJDePc");
+      const [recipientProfile] = getProfilePda(recipient);
+      const [escrowPda] = getEscrowPda(sender.publicKey, recipient);
+
+      const sig = await provider.connection.requestAirdrop(
+        sender.publicKey,
+        LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(sig, "confirmed");
+      const senderTokenAccount = await createAssociatedTokenAccount(
+        provider.connection,
+        sender,
+        mint,
+        sender.publicKey
       );
 
-      const bobInitialBalance = (
-        await getAccount(provider.connection, bobTokenAccount)
-      ).amount;
-      const charlieInitialBalance = (
-        await getAccount(provider.connection, charlieTokenAccount)
-      ).amount;
+      const refundAccounts = {
+        sender: sender.publicKey,
+        recipient,
+        recipientProfile,
+        mint,
+        escrow: escrowPda,
+        inbox: null,
+        inboxPage: null,
+        escrowVault: getAssociatedTokenAddressSync(mint, escrowPda, true),
+        senderTokenAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      };
+
+      // The sender can't swap in another profile to skip the recipient's
+      try {
+        await program.methods
+          .refundDm()
+          .accountsStrict({
+            ...refundAccounts,
+            recipientProfile: getProfilePda(bob.publicKey)[0],
+          })
+          .signers([sender])
+          .rpc();
 
-      const bidAmount = new BN(25 * ONE_USDC); // 25 USDC
+        expect.fail("Should have thrown ConstraintSeeds error");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("ConstraintSeeds");
+      }
 
-      const tx = await program.methods
-        .placeBid(bidAmount)
-        .accountsStrict({
-          bidder: charlie.publicKey,
-          previousBidder: bob.publicKey,
-          host: alice.publicKey,
-          mint: usdcMint,
-          auction: auctionPda,
-          auctionVault: auctionVault,
-          bidderTokenAccount: charlieTokenAccount,
-          previousBidderTokenAccount: bobTokenAccount,
-          tokenProgram: TOKEN_PROGRAM_ID,
-        })
-        .signers([charlie])
+      await program.methods
+        .refundDm()
+        .accountsStrict(refundAccounts)
+        .signers([sender])
         .rpc();
 
-      console.log("Outbid tx:", tx);
+      const refunded = await getAccount(provider.connection, senderTokenAccount);
+      expect(Number(refunded.amount)).to.equal(2 * ONE_USDC);
+      expect(await program.account.messageEscrow.fetchNullable(escrowPda)).to.be.null;
 
-      // Verify bob was refunded
-      const bobFinalBalance = (
-        await getAccount(provider.connection, bobTokenAccount)
-      ).amount;
-      expect(Number(bobFinalBalance) - Number(bobInitialBalance)).to.equal(15 * ONE_USDC);
+      const profile = await program.account.userProfile.fetch(recipientProfile);
+      expect(profile.pendingEscrows.toNumber()).to.equal(0);
+      expect(profile.reputation.dmsExpired).to.equal(1);
+    });
+  });
+
+  // ============================================
+  // ASSET GATE TESTS
+  // ============================================
+  describe("Asset Gate", () => {
+    it("rejects a sender proving the gate with someone else's token account", async () => {
+      const [charlieProfilePda] = getProfilePda(charlie.publicKey);
+      await program.methods
+        .updateProfile(null, {
+          mode: { anyOf: {} },
+          rules: [{ token: { mint: usdcMint, minAmount: new BN(ONE_USDC) } }],
+        }, null, null)
+        .accountsStrict({
+          authority: charlie.publicKey,
+          signer: charlie.publicKey,
+          session: null,
+          profile: charlieProfilePda,
+        })
+        .signers([charlie])
+        .rpc();
+
+      const [escrowPda] = getEscrowPda(alice.publicKey, charlie.publicKey);
+      const escrowVault = getAssociatedTokenAddressSync(usdcMint, escrowPda, true);
+      const depositAccounts = {
+        sender: alice.publicKey,
+        recipient: charlie.publicKey,
+        recipientProfile: charlieProfilePda,
+        mint: usdcMint,
+        senderTokenAccount: aliceTokenAccount,
+        escrow: escrowPda,
+        escrowVault: escrowVault,
+        inbox: getInboxPda(charlie.publicKey)[0],
+        inboxPage: await getInsertPagePda(charlie.publicKey),
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      };
+
+      try {
+        await program.methods
+          .depositForDm(new BN(ONE_USDC))
+          .accountsStrict(depositAccounts)
+          .remainingAccounts([
+            { pubkey: bobTokenAccount, isWritable: false, isSigner: false },
+          ])
+          .signers([alice])
+          .rpc();
+
+        expect.fail("Should have thrown GateAccountOwnerMismatch error");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("GateAccountOwnerMismatch");
+      }
+
+      // Alice's own balance satisfies the gate
+      await program.methods
+        .depositForDm(new BN(ONE_USDC))
+        .accountsStrict(depositAccounts)
+        .remainingAccounts([
+          { pubkey: aliceTokenAccount, isWritable: false, isSigner: false },
+        ])
+        .signers([alice])
+        .rpc();
+
+      const escrow = await program.account.messageEscrow.fetch(escrowPda);
+      expect(escrow.status).to.deep.equal({ pending: {} });
+    });
+  });
+
+  // ============================================
+  // VERIFIER ATTESTATION TESTS
+  // ============================================
+  describe("Verifier Attestations", () => {
+    it("only the admin can register verifiers", async () => {
+      const [verifierPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("verifier"), bob.publicKey.toBuffer()],
+        program.programId
+      );
+
+      try {
+        await program.methods
+          .addVerifier(bob.publicKey, "Bob's ID checks")
+          .accountsStrict({
+            admin: bob.publicKey,
+            verifierRecord: verifierPda,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([bob])
+          .rpc();
+
+        expect.fail("Should have thrown Unauthorized error");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("Unauthorized");
+      }
+    });
+
+    it("rejects a DM to an age-gated profile without an attestation", async () => {
+      const frank = Keypair.generate();
+      const sig = await provider.connection.requestAirdrop(
+        frank.publicKey,
+        LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(sig, "confirmed");
+
+      const [frankProfilePda] = getProfilePda(frank.publicKey);
+      await program.methods
+        .createProfile(new BN(ONE_USDC), {
+          mode: { allOf: {} },
+          rules: [{ attestation: { claimType: { ageOver18: {} } } }],
+        }, null)
+        .accountsStrict({
+          authority: frank.publicKey,
+          profile: frankProfilePda,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([frank])
+        .rpc();
+
+      const [escrowPda] = getEscrowPda(alice.publicKey, frank.publicKey);
+      try {
+        await program.methods
+          .depositForDm(new BN(ONE_USDC))
+          .accountsStrict({
+            sender: alice.publicKey,
+            recipient: frank.publicKey,
+            recipientProfile: frankProfilePda,
+            mint: usdcMint,
+            senderTokenAccount: aliceTokenAccount,
+            escrow: escrowPda,
+            escrowVault: getAssociatedTokenAddressSync(usdcMint, escrowPda, true),
+            inbox: getInboxPda(frank.publicKey)[0],
+            inboxPage: await getInsertPagePda(frank.publicKey),
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .remainingAccounts([
+            {
+              pubkey: getProfilePda(alice.publicKey)[0],
+              isWritable: false,
+              isSigner: false,
+            },
+          ])
+          .signers([alice])
+          .rpc();
+
+        expect.fail("Should have thrown AttestationRequired error");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("AttestationRequired");
+      }
+    });
+  });
+
+  // ============================================
+  // CLOSE PROFILE TESTS
+  // ============================================
+  describe("Close Profile", () => {
+    it("refuses to close a profile with pending escrows", async () => {
+      const [aliceProfilePda] = getProfilePda(alice.publicKey);
+      try {
+        await program.methods
+          .closeProfile()
+          .accountsStrict({
+            authority: alice.publicKey,
+            profile: aliceProfilePda,
+          })
+          .signers([alice])
+          .rpc();
+
+        expect.fail("Should have thrown ProfileHasPendingEscrows error");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("ProfileHasPendingEscrows");
+      }
+    });
+
+    it("closes a profile with no outstanding obligations", async () => {
+      const dave = Keypair.generate();
+      const sig = await provider.connection.requestAirdrop(
+        dave.publicKey,
+        LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(sig, "confirmed");
+
+      const [profilePda] = getProfilePda(dave.publicKey);
+      await program.methods
+        .createProfile(new BN(ONE_USDC), null, null)
+        .accountsStrict({
+          authority: dave.publicKey,
+          profile: profilePda,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([dave])
+        .rpc();
+
+      await program.methods
+        .closeProfile()
+        .accountsStrict({
+          authority: dave.publicKey,
+          profile: profilePda,
+        })
+        .signers([dave])
+        .rpc();
+
+      const closed = await provider.connection.getAccountInfo(profilePda);
+      expect(closed).to.be.null;
+    });
+  });
+
+  // ============================================
+  // AWAY MODE TESTS
+  // ============================================
+  describe("Away Mode", () => {
+    let gina: Keypair;
+    let ginaProfilePda: PublicKey;
+
+    before(async () => {
+      gina = Keypair.generate();
+      const sig = await provider.connection.requestAirdrop(
+        gina.publicKey,
+        LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(sig, "confirmed");
+
+      [ginaProfilePda] = getProfilePda(gina.publicKey);
+      await program.methods
+        .createProfile(new BN(ONE_USDC), null, null)
+        .accountsStrict({
+          authority: gina.publicKey,
+          profile: ginaProfilePda,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([gina])
+        .rpc();
+    });
+
+    it("rejects DMs while the recipient is away", async () => {
+      await program.methods
+        .setAvailability({ away: { backAt: null } })
+        .accountsStrict({
+          authority: gina.publicKey,
+          profile: ginaProfilePda,
+          inbox: null,
+          tokenProgram: null,
+        })
+        .signers([gina])
+        .rpc();
+
+      const [escrowPda] = getEscrowPda(alice.publicKey, gina.publicKey);
+      try {
+        await program.methods
+          .depositForDm(new BN(ONE_USDC))
+          .accountsStrict({
+            sender: alice.publicKey,
+            recipient: gina.publicKey,
+            recipientProfile: ginaProfilePda,
+            mint: usdcMint,
+            senderTokenAccount: aliceTokenAccount,
+            escrow: escrowPda,
+            escrowVault: getAssociatedTokenAddressSync(usdcMint, escrowPda, true),
+            inbox: getInboxPda(gina.publicKey)[0],
+            inboxPage: await getInsertPagePda(gina.publicKey),
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([alice])
+          .rpc();
+
+        expect.fail("Should have thrown RecipientAway error");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("RecipientAway");
+      }
+    });
+
+    it("gina comes back", async () => {
+      await program.methods
+        .setAvailability({ available: {} })
+        .accountsStrict({
+          authority: gina.publicKey,
+          profile: ginaProfilePda,
+          inbox: null,
+          tokenProgram: null,
+        })
+        .signers([gina])
+        .rpc();
+
+      const profile = await program.account.userProfile.fetch(ginaProfilePda);
+      expect(profile.availability).to.deep.equal({ available: {} });
+    });
+  });
+
+  // ============================================
+  // SESSION DELEGATE TESTS
+  // ============================================
+  describe("Session Delegate", () => {
+    const SCOPE_DECLINE_DM = 2;
+    let delegate: Keypair;
+
+    before(async () => {
+      delegate = Keypair.generate();
+      const sig = await provider.connection.requestAirdrop(
+        delegate.publicKey,
+        LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(sig, "confirmed");
+    });
+
+    it("alice registers a session delegate for declining DMs", async () => {
+      const [profilePda] = getProfilePda(alice.publicKey);
+      const [sessionPda] = getSessionPda(alice.publicKey, delegate.publicKey);
+
+      await program.methods
+        .createSession(delegate.publicKey, SCOPE_DECLINE_DM, new BN(60 * 60))
+        .accountsStrict({
+          authority: alice.publicKey,
+          profile: profilePda,
+          session: sessionPda,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([alice])
+        .rpc();
+
+      const session = await program.account.sessionDelegate.fetch(sessionPda);
+      expect(session.delegate.toString()).to.equal(delegate.publicKey.toString());
+      expect(session.scopes).to.equal(SCOPE_DECLINE_DM);
+    });
+
+    it("the delegate cannot accept a DM outside its scope", async () => {
+      const [escrowPda] = getEscrowPda(charlie.publicKey, alice.publicKey);
+      const [sessionPda] = getSessionPda(alice.publicKey, delegate.publicKey);
+
+      try {
+        await program.methods
+          .acceptDm()
+          .accountsStrict({
+            sender: charlie.publicKey,
+            recipient: alice.publicKey,
+            recipientAuthority: alice.publicKey,
+            signer: delegate.publicKey,
+            session: sessionPda,
+            recipientProfile: getProfilePda(alice.publicKey)[0],
+            mint: usdcMint,
+            escrow: escrowPda,
+            escrowVault: getAssociatedTokenAddressSync(usdcMint, escrowPda, true),
+            inbox: getInboxPda(alice.publicKey)[0],
+            inboxPage: await getEscrowPagePda(escrowPda, alice.publicKey),
+            recipientTokenAccount: aliceTokenAccount,
+            treasury: TREASURY,
+            treasuryTokenAccount: getAssociatedTokenAddressSync(usdcMint, TREASURY),
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([delegate])
+          .rpc();
+
+        expect.fail("Should have thrown SessionScopeNotGranted error");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("SessionScopeNotGranted");
+      }
+    });
+
+    it("the delegate declines charlie's DM and charlie is refunded", async () => {
+      const [escrowPda] = getEscrowPda(charlie.publicKey, alice.publicKey);
+      const [sessionPda] = getSessionPda(alice.publicKey, delegate.publicKey);
+
+      const initialCharlieBalance = (
+        await getAccount(provider.connection, charlieTokenAccount)
+      ).amount;
+      const escrowAmount = (
+        await program.account.messageEscrow.fetch(escrowPda)
+      ).amount.toNumber();
+
+      await program.methods
+        .declineDm()
+        .accountsStrict({
+          sender: charlie.publicKey,
+          recipient: alice.publicKey,
+          signer: delegate.publicKey,
+          session: sessionPda,
+          recipientProfile: getProfilePda(alice.publicKey)[0],
+          mint: usdcMint,
+          escrow: escrowPda,
+          escrowVault: getAssociatedTokenAddressSync(usdcMint, escrowPda, true),
+          inbox: getInboxPda(alice.publicKey)[0],
+          inboxPage: await getEscrowPagePda(escrowPda, alice.publicKey),
+          senderTokenAccount: charlieTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([delegate])
+        .rpc();
+
+      const finalCharlieBalance = (
+        await getAccount(provider.connection, charlieTokenAccount)
+      ).amount;
+      expect(Number(finalCharlieBalance) - Number(initialCharlieBalance)).to.equal(
+        escrowAmount
+      );
+    });
+
+    it("alice revokes the session", async () => {
+      const [sessionPda] = getSessionPda(alice.publicKey, delegate.publicKey);
+
+      await program.methods
+        .revokeSession()
+        .accountsStrict({
+          authority: alice.publicKey,
+          session: sessionPda,
+        })
+        .signers([alice])
+        .rpc();
+
+      const closed = await provider.connection.getAccountInfo(sessionPda);
+      expect(closed).to.be.null;
+    });
+  });
+
+  // ============================================
+  // PROFILE RECOVERY TESTS
+  // ============================================
+  describe("Profile Recovery", () => {
+    let eve: Keypair;
+    let newWallet: Keypair;
+    let eveProfilePda: PublicKey;
+    let guardianSetPda: PublicKey;
+    let recoveryPda: PublicKey;
+
+    before(async () => {
+      eve = Keypair.generate();
+      newWallet = Keypair.generate();
+      const sig = await provider.connection.requestAirdrop(
+        eve.publicKey,
+        LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(sig, "confirmed");
+
+      [eveProfilePda] = getProfilePda(eve.publicKey);
+      [guardianSetPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("guardians"), eveProfilePda.toBuffer()],
+        program.programId
+      );
+      [recoveryPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("recovery"), eveProfilePda.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .createProfile(new BN(ONE_USDC), null, null)
+        .accountsStrict({
+          authority: eve.publicKey,
+          profile: eveProfilePda,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eve])
+        .rpc();
+    });
+
+    it("eve names bob and charlie as 2-of-2 guardians", async () => {
+      await program.methods
+        .setGuardians([bob.publicKey, charlie.publicKey], 2)
+        .accountsStrict({
+          authority: eve.publicKey,
+          profile: eveProfilePda,
+          guardianSet: guardianSetPda,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eve])
+        .rpc();
+
+      const guardianSet = await program.account.guardianSet.fetch(guardianSetPda);
+      expect(guardianSet.guardians.length).to.equal(2);
+      expect(guardianSet.threshold).to.equal(2);
+    });
+
+    it("guardians approve a recovery, but it can't run before the timelock", async () => {
+      await program.methods
+        .initiateRecovery(newWallet.publicKey)
+        .accountsStrict({
+          guardian: bob.publicKey,
+          profile: eveProfilePda,
+          guardianSet: guardianSetPda,
+          recovery: recoveryPda,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([bob])
+        .rpc();
+
+      await program.methods
+        .approveRecovery()
+        .accountsStrict({
+          guardian: charlie.publicKey,
+          guardianSet: guardianSetPda,
+          recovery: recoveryPda,
+        })
+        .signers([charlie])
+        .rpc();
+
+      try {
+        await program.methods
+          .rotateAuthority()
+          .accountsStrict({
+            profile: eveProfilePda,
+            guardianSet: guardianSetPda,
+            proposer: bob.publicKey,
+            recovery: recoveryPda,
+          })
+          .rpc();
+
+        expect.fail("Should have thrown RecoveryTimelockActive error");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("RecoveryTimelockActive");
+      }
+    });
+
+    it("eve vetoes the recovery from the current wallet", async () => {
+      await program.methods
+        .cancelRecovery()
+        .accountsStrict({
+          authority: eve.publicKey,
+          profile: eveProfilePda,
+          proposer: bob.publicKey,
+          recovery: recoveryPda,
+        })
+        .signers([eve])
+        .rpc();
+
+      const closed = await provider.connection.getAccountInfo(recoveryPda);
+      expect(closed).to.be.null;
+
+      const profile = await program.account.userProfile.fetch(eveProfilePda);
+      expect(profile.authority.toString()).to.equal(eve.publicKey.toString());
+    });
+
+    it("approvals from removed guardians are dropped", async () => {
+      await program.methods
+        .initiateRecovery(newWallet.publicKey)
+        .accountsStrict({
+          guardian: bob.publicKey,
+          profile: eveProfilePda,
+          guardianSet: guardianSetPda,
+          recovery: recoveryPda,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([bob])
+        .rpc();
+
+      // Eve swaps bob out while bob's approval is on the request
+      await program.methods
+        .setGuardians([charlie.publicKey, alice.publicKey], 2)
+        .accountsStrict({
+          authority: eve.publicKey,
+          profile: eveProfilePda,
+          guardianSet: guardianSetPda,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([eve])
+        .rpc();
+
+      await program.methods
+        .approveRecovery()
+        .accountsStrict({
+          guardian: charlie.publicKey,
+          guardianSet: guardianSetPda,
+          recovery: recoveryPda,
+        })
+        .signers([charlie])
+        .rpc();
+
+      const recovery = await program.account.recoveryRequest.fetch(recoveryPda);
+      expect(recovery.approvals.map((key: PublicKey) => key.toString())).to.deep.equal([
+        charlie.publicKey.toString(),
+      ]);
+
+      await program.methods
+        .cancelRecovery()
+        .accountsStrict({
+          authority: eve.publicKey,
+          profile: eveProfilePda,
+          proposer: bob.publicKey,
+          recovery: recoveryPda,
+        })
+        .signers([eve])
+        .rpc();
+    });
+  });
+
+  // ============================================
+  // HANDLE REGISTRY TESTS
+  // ============================================
+  describe("Handle Registry", () => {
+    function getHandlePda(handle: string): [PublicKey, number] {
+      return PublicKey.findProgramAddressSync(
+        [Buffer.from("handle"), Buffer.from(handle)],
+        program.programId
+      );
+    }
+
+    it("bob claims a handle", async () => {
+      const [profilePda] = getProfilePda(bob.publicKey);
+      const [handlePda] = getHandlePda("bob_dates");
+
+      await program.methods
+        .claimHandle("bob_dates")
+        .accountsStrict({
+          authority: bob.publicKey,
+          profile: profilePda,
+          handleRecord: handlePda,
+          treasury: TREASURY,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([bob])
+        .rpc();
+
+      const record = await program.account.handleRecord.fetch(handlePda);
+      expect(record.profile.toString()).to.equal(profilePda.toString());
+      const profile = await program.account.userProfile.fetch(profilePda);
+      expect(profile.handle).to.equal("bob_dates");
+    });
+
+    it("rejects a handle that isn't lowercase", async () => {
+      const [handlePda] = getHandlePda("Alice");
+      try {
+        await program.methods
+          .claimHandle("Alice")
+          .accountsStrict({
+            authority: alice.publicKey,
+            profile: getProfilePda(alice.publicKey)[0],
+            handleRecord: handlePda,
+            treasury: TREASURY,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([alice])
+          .rpc();
+
+        expect.fail("Should have thrown HandleNotNormalized error");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("HandleNotNormalized");
+      }
+    });
+
+    it("bob transfers the handle to charlie, who releases it", async () => {
+      const [handlePda] = getHandlePda("bob_dates");
+      const [charlieProfilePda] = getProfilePda(charlie.publicKey);
+
+      const transfer = (newAuthority: Keypair) =>
+        program.methods
+          .transferHandle()
+          .accountsStrict({
+            authority: bob.publicKey,
+            newAuthority: newAuthority.publicKey,
+            profile: getProfilePda(bob.publicKey)[0],
+            newProfile: charlieProfilePda,
+            handleRecord: handlePda,
+          })
+          .signers([bob, newAuthority])
+          .rpc();
+
+      // Charlie has to accept the handle, bob can't sign for them
+      try {
+        await transfer(bob);
+        expect.fail("Should have thrown Unauthorized error");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("Unauthorized");
+      }
+
+      await transfer(charlie);
+
+      const record = await program.account.handleRecord.fetch(handlePda);
+      expect(record.profile.toString()).to.equal(charlieProfilePda.toString());
+
+      await program.methods
+        .releaseHandle()
+        .accountsStrict({
+          authority: charlie.publicKey,
+          profile: charlieProfilePda,
+          handleRecord: handlePda,
+        })
+        .signers([charlie])
+        .rpc();
+
+      const closed = await provider.connection.getAccountInfo(handlePda);
+      expect(closed).to.be.null;
+      const profile = await program.account.userProfile.fetch(charlieProfilePda);
+      expect(profile.handle).to.be.null;
+    });
+  });
+
+  // ============================================
+  // DATE AUCTION TESTS
+  // ============================================
+  describe("Date Auction", () => {
+    it("alice creates a date auction", async () => {
+      const [aliceProfile] = getProfilePda(alice.publicKey);
+      const profile = await program.account.userProfile.fetch(aliceProfile);
+      const auctionId = profile.auctionCount.toNumber();
+
+      const [auctionPda] = getAuctionPda(alice.publicKey, auctionId);
+      const auctionVault = getAssociatedTokenAddressSync(
+        usdcMint,
+        auctionPda,
+        true
+      );
+
+      const startPrice = new BN(TEN_USDC); // 10 USDC starting price
+      const durationSecs = new BN(24 * 60 * 60); // 24 hours
+
+      const tx = await program.methods
+        .createAuction(startPrice, durationSecs, null)
+        .accountsStrict({
+          host: alice.publicKey,
+          hostProfile: aliceProfile,
+          mint: usdcMint,
+          auction: auctionPda,
+          auctionVault: auctionVault,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([alice])
+        .rpc();
+
+      console.log("Create auction tx:", tx);
+
+      // Verify auction was created
+      const auction = await program.account.dateAuction.fetch(auctionPda);
+      expect(auction.host.toString()).to.equal(alice.publicKey.toString());
+      expect(auction.auctionId.toNumber()).to.equal(auctionId);
+      expect(auction.highestBidder.toString()).to.equal(alice.publicKey.toString()); // Initially host
+      expect(auction.highestBid.toNumber()).to.equal(TEN_USDC); // Start price
+
+      // Verify profile auction count incremented
+      const updatedProfile = await program.account.userProfile.fetch(aliceProfile);
+      expect(updatedProfile.auctionCount.toNumber()).to.equal(auctionId + 1);
+    });
+
+    it("bob places a bid on alice's auction", async () => {
+      const [aliceProfile] = getProfilePda(alice.publicKey);
+      const profile = await program.account.userProfile.fetch(aliceProfile);
+      const auctionId = profile.auctionCount.toNumber() - 1; // Last auction
+
+      const [auctionPda] = getAuctionPda(alice.publicKey, auctionId);
+      const auctionVault = getAssociatedTokenAddressSync(
+        usdcMint,
+        auctionPda,
+        true
+      );
+
+      const bidAmount = new BN(15 * ONE_USDC); // 15 USDC
+
+      const initialBalance = (
+        await getAccount(provider.connection, bobTokenAccount)
+      ).amount;
+
+      const tx = await program.methods
+        .placeBid(bidAmount)
+        .accountsStrict({
+          bidder: bob.publicKey,
+          signer: bob.publicKey,
+          session: null,
+          bidderProfile: null,
+          previousBidder: alice.publicKey, // Host is previous bidder
+          host: alice.publicKey,
+          hostProfile: getProfilePda(alice.publicKey)[0],
+          mint: usdcMint,
+          auction: auctionPda,
+          auctionVault: auctionVault,
+          bidderTokenAccount: bobTokenAccount,
+          previousBidderTokenAccount: aliceTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([bob])
+        .rpc();
+
+      console.log("Place bid tx:", tx);
+
+      // Verify auction was updated
+      const auction = await program.account.dateAuction.fetch(auctionPda);
+      expect(auction.highestBidder.toString()).to.equal(bob.publicKey.toString());
+      expect(auction.highestBid.toNumber()).to.equal(15 * ONE_USDC);
+
+      // Verify USDC was transferred
+      const finalBalance = (
+        await getAccount(provider.connection, bobTokenAccount)
+      ).amount;
+      expect(Number(initialBalance) - Number(finalBalance)).to.equal(15 * ONE_USDC);
+
+      // Verify vault has the bid
+      const vaultBalance = (
+        await getAccount(provider.connection, auctionVault)
+      ).amount;
+      expect(Number(vaultBalance)).to.equal(15 * ONE_USDC);
+    });
+
+    it("charlie outbids bob (bob gets refunded)", async () => {
+      const [aliceProfile] = getProfilePda(alice.publicKey);
+      const profile = await program.account.userProfile.fetch(aliceProfile);
+      const auctionId = profile.auctionCount.toNumber() - 1;
+
+      const [auctionPda] = getAuctionPda(alice.publicKey, auctionId);
+      const auctionVault = getAssociatedTokenAddressSync(
+        usdcMint,
+        auctionPda,
+        true
+      );
+
+      const bobInitialBalance = (
+        await getAccount(provider.connection, bobTokenAccount)
+      ).amount;
+      const charlieInitialBalance = (
+        await getAccount(provider.connection, charlieTokenAccount)
+      ).amount;
+
+      const bidAmount = new BN(25 * ONE_USDC); // 25 USDC
+
+      const tx = await program.methods
+        .placeBid(bidAmount)
+        .accountsStrict({
+          bidder: charlie.publicKey,
+          signer: charlie.publicKey,
+          session: null,
+          bidderProfile: null,
+          previousBidder: bob.publicKey,
+          host: alice.publicKey,
+          hostProfile: getProfilePda(alice.publicKey)[0],
+          mint: usdcMint,
+          auction: auctionPda,
+          auctionVault: auctionVault,
+          bidderTokenAccount: charlieTokenAccount,
+          previousBidderTokenAccount: bobTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([charlie])
+        .rpc();
+
+      console.log("Outbid tx:", tx);
+
+      // Verify bob was refunded
+      const bobFinalBalance = (
+        await getAccount(provider.connection, bobTokenAccount)
+      ).amount;
+      expect(Number(bobFinalBalance) - Number(bobInitialBalance)).to.equal(15 * ONE_USDC);
+
+      // Verify charlie's bid was taken
+      const charlieFinalBalance = (
+        await getAccount(provider.connection, charlieTokenAccount)
+      ).amount;
+      expect(Number(charlieInitialBalance) - Number(charlieFinalBalance)).to.equal(25 * ONE_USDC);
+
+      // Verify auction state
+      const auction = await program.account.dateAuction.fetch(auctionPda);
+      expect(auction.highestBidder.toString()).to.equal(charlie.publicKey.toString());
+      expect(auction.highestBid.toNumber()).to.equal(25 * ONE_USDC);
+    });
+  });
+
+  // ============================================
+  // COLLECTION GATE AND BIDDER REQUIREMENT TESTS
+  // ============================================
+  describe("Collection Gate and Bidder Requirements", () => {
+    let gwen: Keypair;
+    let gwenProfilePda: PublicKey;
+    let gwenTokenAccount: PublicKey;
+    let aliceNftAccount: PublicKey;
+    let aliceUnverifiedNftAccount: PublicKey;
+    let aliceFungibleNftAccount: PublicKey;
+
+    const collectionGate = {
+      mode: { anyOf: {} },
+      rules: [{ collection: { collection: NFT_COLLECTION } }],
+    };
+
+    // Token account, mint and metadata proving a holding of `mint`
+    const nftProof = (tokenAccount: PublicKey, mint: PublicKey): AccountMeta[] =>
+      [tokenAccount, mint, getMetadataPda(mint)].map((pubkey) => ({
+        pubkey,
+        isWritable: false,
+        isSigner: false,
+      }));
+
+    before(async () => {
+      gwen = Keypair.generate();
+      const sig = await provider.connection.requestAirdrop(
+        gwen.publicKey,
+        LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(sig, "confirmed");
+
+      // Gwen only takes DMs from holders of the collection
+      [gwenProfilePda] = getProfilePda(gwen.publicKey);
+      await program.methods
+        .createProfile(new BN(ONE_USDC), collectionGate, null)
+        .accountsStrict({
+          authority: gwen.publicKey,
+          profile: gwenProfilePda,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([gwen])
+        .rpc();
+
+      gwenTokenAccount = await createAssociatedTokenAccount(
+        provider.connection,
+        gwen,
+        usdcMint,
+        gwen.publicKey
+      );
+
+      aliceNftAccount = await mintFixtureNft(NFT_MINT, alice, 1);
+      aliceUnverifiedNftAccount = await mintFixtureNft(UNVERIFIED_NFT_MINT, alice, 1);
+      // Two tokens of a zero-decimal mint - same metadata as an NFT, but not one of one
+      aliceFungibleNftAccount = await mintFixtureNft(FUNGIBLE_NFT_MINT, alice, 2);
+    });
+
+    async function depositToGwen(proof: AccountMeta[]): Promise<PublicKey> {
+      const [escrowPda] = getEscrowPda(alice.publicKey, gwen.publicKey);
+      await program.methods
+        .depositForDm(new BN(ONE_USDC))
+        .accountsStrict({
+          sender: alice.publicKey,
+          recipient: gwen.publicKey,
+          recipientProfile: gwenProfilePda,
+          mint: usdcMint,
+          senderTokenAccount: aliceTokenAccount,
+          escrow: escrowPda,
+          escrowVault: getAssociatedTokenAddressSync(usdcMint, escrowPda, true),
+          inbox: getInboxPda(gwen.publicKey)[0],
+          inboxPage: await getInsertPagePda(gwen.publicKey),
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(proof)
+        .signers([alice])
+        .rpc();
+      return escrowPda;
+    }
+
+    it("rejects a DM proved with an NFT whose collection isn't verified", async () => {
+      try {
+        await depositToGwen(nftProof(aliceUnverifiedNftAccount, UNVERIFIED_NFT_MINT));
+        expect.fail("Should have thrown CollectionNotHeld error");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("CollectionNotHeld");
+      }
+    });
+
+    it("rejects a DM proved with a collection mint that isn't one of one", async () => {
+      try {
+        await depositToGwen(nftProof(aliceFungibleNftAccount, FUNGIBLE_NFT_MINT));
+        expect.fail("Should have thrown CollectionMintNotNft error");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("CollectionMintNotNft");
+      }
+    });
+
+    it("rejects a DM proved without the NFT's mint", async () => {
+      try {
+        const proof = nftProof(aliceNftAccount, NFT_MINT);
+        await depositToGwen(proof.filter(({ pubkey }) => !pubkey.equals(NFT_MINT)));
+        expect.fail("Should have thrown CollectionMintNotNft error");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("CollectionMintNotNft");
+      }
+    });
+
+    it("accepts a DM from a holder of a verified collection NFT", async () => {
+      const escrowPda = await depositToGwen(nftProof(aliceNftAccount, NFT_MINT));
+
+      const escrow = await program.account.messageEscrow.fetch(escrowPda);
+      expect(escrow.status).to.deep.equal({ pending: {} });
+      expect(escrow.recipient.toString()).to.equal(gwen.publicKey.toString());
+    });
+
+    async function createGwenAuction(bidderRequirements: any): Promise<PublicKey> {
+      const profile = await program.account.userProfile.fetch(gwenProfilePda);
+      const [auctionPda] = getAuctionPda(gwen.publicKey, profile.auctionCount.toNumber());
+      await program.methods
+        .createAuction(new BN(ONE_USDC), new BN(24 * 60 * 60), bidderRequirements)
+        .accountsStrict({
+          host: gwen.publicKey,
+          hostProfile: gwenProfilePda,
+          mint: usdcMint,
+          auction: auctionPda,
+          auctionVault: getAssociatedTokenAddressSync(usdcMint, auctionPda, true),
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([gwen])
+        .rpc();
+      return auctionPda;
+    }
+
+    function bidOnGwenAuction(
+      auctionPda: PublicKey,
+      bidder: Keypair,
+      bidderTokenAccount: PublicKey,
+      bidderProfile: PublicKey | null
+    ) {
+      return program.methods
+        .placeBid(new BN(2 * ONE_USDC))
+        .accountsStrict({
+          bidder: bidder.publicKey,
+          signer: bidder.publicKey,
+          session: null,
+          bidderProfile,
+          previousBidder: gwen.publicKey,
+          host: gwen.publicKey,
+          hostProfile: gwenProfilePda,
+          mint: usdcMint,
+          auction: auctionPda,
+          auctionVault: getAssociatedTokenAddressSync(usdcMint, auctionPda, true),
+          bidderTokenAccount,
+          previousBidderTokenAccount: gwenTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([bidder]);
+    }
+
+    it("requires bidders to have a profile of the minimum age", async () => {
+      const auctionPda = await createGwenAuction({
+        gate: null,
+        minProfileAge: new BN(365 * 24 * 60 * 60),
+        allowlist: [],
+      });
+
+      try {
+        await bidOnGwenAuction(auctionPda, bob, bobTokenAccount, null).rpc();
+        expect.fail("Should have thrown BidderProfileRequired error");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("BidderProfileRequired");
+      }
+
+      try {
+        await bidOnGwenAuction(
+          auctionPda,
+          bob,
+          bobTokenAccount,
+          getProfilePda(bob.publicKey)[0]
+        ).rpc();
+        expect.fail("Should have thrown ProfileTooNew error");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("ProfileTooNew");
+      }
+    });
+
+    it("requires bidders to hold both the token and the collection", async () => {
+      const auctionPda = await createGwenAuction({
+        gate: {
+          mode: { allOf: {} },
+          rules: [
+            { token: { mint: usdcMint, minAmount: new BN(ONE_USDC) } },
+            { collection: { collection: NFT_COLLECTION } },
+          ],
+        },
+        minProfileAge: new BN(1),
+        allowlist: [],
+      });
+
+      // Bob holds USDC but no NFT from the collection
+      try {
+        await bidOnGwenAuction(
+          auctionPda,
+          bob,
+          bobTokenAccount,
+          getProfilePda(bob.publicKey)[0]
+        )
+          .remainingAccounts([
+            { pubkey: bobTokenAccount, isWritable: false, isSigner: false },
+          ])
+          .rpc();
+        expect.fail("Should have thrown CollectionNotHeld error");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("CollectionNotHeld");
+      }
+
+      // Alice holds both, and her profile is older than a second
+      await bidOnGwenAuction(
+        auctionPda,
+        alice,
+        aliceTokenAccount,
+        getProfilePda(alice.publicKey)[0]
+      )
+        .remainingAccounts([
+          { pubkey: aliceTokenAccount, isWritable: false, isSigner: false },
+          ...nftProof(aliceNftAccount, NFT_MINT),
+        ])
+        .rpc();
+
+      const auction = await program.account.dateAuction.fetch(auctionPda);
+      expect(auction.highestBidder.toString()).to.equal(alice.publicKey.toString());
+      expect(auction.highestBid.toNumber()).to.equal(2 * ONE_USDC);
+    });
+  });
+
+  // ============================================
+  // MATCH TESTS
+  // ============================================
+  describe("Likes and Matches", () => {
+    const like = (liker: Keypair, likee: PublicKey, pairMatch: PublicKey | null) =>
+      program.methods
+        .likeProfile()
+        .accountsStrict({
+          liker: liker.publicKey,
+          likerProfile: getProfilePda(liker.publicKey)[0],
+          likeeProfile: getProfilePda(likee)[0],
+          like: getLikePda(liker.publicKey, likee)[0],
+          reverseLike: getLikePda(likee, liker.publicKey)[0],
+          pairMatch,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([liker])
+        .rpc();
+    const unmatch = (liker: Keypair, likee: PublicKey, matchPayer: PublicKey) =>
+      program.methods
+        .unmatch()
+        .accountsStrict({
+          liker: liker.publicKey,
+          likerProfile: getProfilePda(liker.publicKey)[0],
+          like: getLikePda(liker.publicKey, likee)[0],
+          pairMatch: getMatchPda(liker.publicKey, likee)[0],
+          dateBooking: getDateBookingPda(getMatchPda(liker.publicKey, likee)[0])[0],
+          matchPayer,
+        })
+        .signers([liker])
+        .rpc();
+
+    it("a like back creates the match", async () => {
+      const [matchPda] = getMatchPda(bob.publicKey, charlie.publicKey);
+
+      // A first like can't create a match
+      try {
+        await like(bob, charlie.publicKey, matchPda);
+        expect.fail("Should have thrown NotLikedBack error");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("NotLikedBack");
+      }
+      await like(bob, charlie.publicKey, null);
+
+      const bobLike = await program.account.like.fetch(
+        getLikePda(bob.publicKey, charlie.publicKey)[0]
+      );
+      expect(bobLike.liker.toString()).to.equal(bob.publicKey.toString());
+      expect(bobLike.likee.toString()).to.equal(charlie.publicKey.toString());
+
+      // The second like must bring the match along
+      try {
+        await like(charlie, bob.publicKey, null);
+        expect.fail("Should have thrown MatchAccountRequired error");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("MatchAccountRequired");
+      }
+      await like(charlie, bob.publicKey, matchPda);
+
+      const pairMatch = await program.account.match.fetch(matchPda);
+      expect([pairMatch.userA.toString(), pairMatch.userB.toString()]).to.have.members([
+        bob.publicKey.toString(),
+        charlie.publicKey.toString(),
+      ]);
+      expect(pairMatch.payer.toString()).to.equal(charlie.publicKey.toString());
+    });
+
+    it("unmatching closes the match and refunds its rent to the payer", async () => {
+      const [matchPda] = getMatchPda(bob.publicKey, charlie.publicKey);
+
+      // Rent goes back to charlie, who paid it
+      try {
+        await unmatch(bob, charlie.publicKey, bob.publicKey);
+        expect.fail("Should have thrown MatchPayerMismatch error");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("MatchPayerMismatch");
+      }
+
+      const matchRent = await provider.connection.getBalance(matchPda);
+      const charlieBefore = await provider.connection.getBalance(charlie.publicKey);
+      await unmatch(bob, charlie.publicKey, charlie.publicKey);
+
+      expect(await program.account.match.fetchNullable(matchPda)).to.be.null;
+      expect(
+        await program.account.like.fetchNullable(getLikePda(bob.publicKey, charlie.publicKey)[0])
+      ).to.be.null;
+      expect(await provider.connection.getBalance(charlie.publicKey)).to.equal(
+        charlieBefore + matchRent
+      );
+
+      // Charlie's like outlives the match until withdrawn
+      const [charlieLike] = getLikePda(charlie.publicKey, bob.publicKey);
+      expect(await program.account.like.fetchNullable(charlieLike)).to.not.be.null;
+      await unmatch(charlie, bob.publicKey, charlie.publicKey);
+      expect(await program.account.like.fetchNullable(charlieLike)).to.be.null;
+    });
+    const superLike = (liker: Keypair, likerTokenAccount: PublicKey, likee: PublicKey) => {
+      const [superLikePda] = getSuperLikePda(liker.publicKey, likee);
+      return program.methods
+        .superLikeProfile(new BN(ONE_USDC))
+        .accountsStrict({
+          liker: liker.publicKey,
+          likerProfile: getProfilePda(liker.publicKey)[0],
+          likeeProfile: getProfilePda(likee)[0],
+          like: getLikePda(liker.publicKey, likee)[0],
+          reverseLike: getLikePda(likee, liker.publicKey)[0],
+          mint: usdcMint,
+          likerTokenAccount,
+          superLike: superLikePda,
+          superLikeVault: getAssociatedTokenAddressSync(usdcMint, superLikePda, true),
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([liker])
+        .rpc();
+    };
+
+    it("a super-like pays the likee once they like back", async () => {
+      const [superLikePda] = getSuperLikePda(alice.publicKey, bob.publicKey);
+      await superLike(alice, aliceTokenAccount, bob.publicKey);
+
+      const escrow = await program.account.superLike.fetch(superLikePda);
+      expect(escrow.amount.toNumber()).to.equal(ONE_USDC);
+      expect(escrow.sender.toString()).to.equal(alice.publicKey.toString());
+
+      const claim = () =>
+        program.methods
+          .claimSuperLike()
+          .accountsStrict({
+            likee: bob.publicKey,
+            likeeProfile: getProfilePda(bob.publicKey)[0],
+            sender: alice.publicKey,
+            mint: usdcMint,
+            superLike: superLikePda,
+            likeBack: getLikePda(bob.publicKey, alice.publicKey)[0],
+            superLikeVault: getAssociatedTokenAddressSync(usdcMint, superLikePda, true),
+            likeeTokenAccount: bobTokenAccount,
+            treasury: TREASURY,
+            treasuryTokenAccount: getAssociatedTokenAddressSync(usdcMint, TREASURY),
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([bob])
+          .rpc();
+
+      // No like back yet, so nothing to claim
+      try {
+        await claim();
+        expect.fail("Should have thrown AccountNotInitialized error");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("AccountNotInitialized");
+      }
+
+      await like(bob, alice.publicKey, getMatchPda(alice.publicKey, bob.publicKey)[0]);
+      const bobBefore = Number((await getAccount(provider.connection, bobTokenAccount)).amount);
+      await claim();
+
+      const bobAfter = Number((await getAccount(provider.connection, bobTokenAccount)).amount);
+      expect(bobAfter - bobBefore).to.equal(ONE_USDC - ONE_USDC / 100);
+      expect(await program.account.superLike.fetchNullable(superLikePda)).to.be.null;
+      // The emptied vault is closed too
+      const vault = getAssociatedTokenAddressSync(usdcMint, superLikePda, true);
+      expect(await provider.connection.getAccountInfo(vault)).to.be.null;
+    });
+
+    it("an unanswered super-like can't be refunded before its window closes", async () => {
+      const [superLikePda] = getSuperLikePda(charlie.publicKey, alice.publicKey);
+      await superLike(charlie, charlieTokenAccount, alice.publicKey);
+
+      try {
+        await program.methods
+          .refundSuperLike()
+          .accountsStrict({
+            sender: charlie.publicKey,
+            mint: usdcMint,
+            superLike: superLikePda,
+            likeBack: getLikePda(alice.publicKey, charlie.publicKey)[0],
+            superLikeVault: getAssociatedTokenAddressSync(usdcMint, superLikePda, true),
+            senderTokenAccount: charlieTokenAccount,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .rpc();
+        expect.fail("Should have thrown SuperLikeNotExpired error");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("SuperLikeNotExpired");
+      }
+
+      // Liking back before a super-like is pointless, so it's refused
+      try {
+        await superLike(alice, aliceTokenAccount, charlie.publicKey);
+        expect.fail("Should have thrown AlreadyLikedBack error");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("AlreadyLikedBack");
+      }
+    });
+  });
+
+  // ============================================
+  // DATE TESTS
+  // ============================================
+  describe("Date Bookings", () => {
+    // Alice and bob matched through the super-like
+    const pairMatch = () => getMatchPda(alice.publicKey, bob.publicKey)[0];
+    const booking = () => getDateBookingPda(pairMatch())[0];
+    const bookingVault = () => getAssociatedTokenAddressSync(usdcMint, booking(), true);
+    const balanceOf = async (account: PublicKey) =>
+      Number((await getAccount(provider.connection, account)).amount);
+
+    const book = (scheduledAt: number) =>
+      program.methods
+        .bookDate(new BN(ONE_USDC), new BN(scheduledAt))
+        .accountsStrict({
+          booker: alice.publicKey,
+          bookerProfile: getProfilePda(alice.publicKey)[0],
+          pairMatch: pairMatch(),
+          mint: usdcMint,
+          bookerTokenAccount: aliceTokenAccount,
+          booking: booking(),
+          bookingVault: bookingVault(),
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([alice])
+        .rpc();
+    const confirm = (attendee: Keypair) =>
+      program.methods
+        .confirmAttendance()
+        .accountsStrict({
+          attendee: attendee.publicKey,
+          attendeeProfile: getProfilePda(attendee.publicKey)[0],
+          booking: booking(),
+        })
+        .signers([attendee])
+        .rpc();
+
+    it("both sides get their stake back when both show up", async () => {
+      const aliceBefore = await balanceOf(aliceTokenAccount);
+      const bobBefore = await balanceOf(bobTokenAccount);
+      const scheduledAt = Math.floor(Date.now() / 1000) + 3;
+      await book(scheduledAt);
+
+      // Nothing to confirm until bob stakes too
+      try {
+        await confirm(alice);
+        expect.fail("Should have thrown DateNotStaked error");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("DateNotStaked");
+      }
+
+      await program.methods
+        .stakeDate()
+        .accountsStrict({
+          staker: bob.publicKey,
+          stakerProfile: getProfilePda(bob.publicKey)[0],
+          pairMatch: pairMatch(),
+          mint: usdcMint,
+          stakerTokenAccount: bobTokenAccount,
+          booking: booking(),
+          bookingVault: bookingVault(),
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([bob])
+        .rpc();
+      expect(await balanceOf(bookingVault())).to.equal(2 * ONE_USDC);
+
+      // Attendance opens at the scheduled time
+      while (
+        (await provider.connection.getBlockTime(await provider.connection.getSlot())) <=
+        scheduledAt
+      ) {
+        await new Promise((resolve) => setTimeout(resolve, 500));
+      }
+      await confirm(alice);
+      await confirm(bob);
+
+      // Party order follows the match's sorted identities
+      const aliceFirst = Buffer.compare(alice.publicKey.toBuffer(), bob.publicKey.toBuffer()) <= 0;
+      const [stakerA, stakerB] = aliceFirst
+        ? [alice.publicKey, bob.publicKey]
+        : [bob.publicKey, alice.publicKey];
+      await program.methods
+        .settleDate()
+        .accountsStrict({
+          payer: alice.publicKey,
+          mint: usdcMint,
+          booking: booking(),
+          bookingVault: bookingVault(),
+          stakerA,
+          stakerATokenAccount: getAssociatedTokenAddressSync(usdcMint, stakerA),
+          stakerB,
+          stakerBTokenAccount: getAssociatedTokenAddressSync(usdcMint, stakerB),
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+      expect(await balanceOf(aliceTokenAccount)).to.equal(aliceBefore);
+      expect(await balanceOf(bobTokenAccount)).to.equal(bobBefore);
+      expect(await program.account.dateBooking.fetchNullable(booking())).to.be.null;
+    });
+
+    it("a date can be called off until both sides stake", async () => {
+      const aliceBefore = await balanceOf(aliceTokenAccount);
+      await book(Math.floor(Date.now() / 1000) + 3600);
+      expect(await balanceOf(aliceTokenAccount)).to.equal(aliceBefore - ONE_USDC);
+
+      // The match can't be dissolved out from under a booking
+      try {
+        await program.methods
+          .unmatch()
+          .accountsStrict({
+            liker: bob.publicKey,
+            likerProfile: getProfilePda(bob.publicKey)[0],
+            like: getLikePda(bob.publicKey, alice.publicKey)[0],
+            pairMatch: pairMatch(),
+            dateBooking: booking(),
+            matchPayer: (await program.account.match.fetch(pairMatch())).payer,
+          })
+          .signers([bob])
+          .rpc();
+
+        expect.fail("Should have thrown DateBookingActive error");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("DateBookingActive");
+      }
+
+      // Bob backs out, and alice's stake is refunded
+      await program.methods
+        .cancelDate()
+        .accountsStrict({
+          canceller: bob.publicKey,
+          cancellerProfile: getProfilePda(bob.publicKey)[0],
+          payer: alice.publicKey,
+          staker: alice.publicKey,
+          mint: usdcMint,
+          booking: booking(),
+          bookingVault: bookingVault(),
+          stakerTokenAccount: aliceTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([bob])
+        .rpc();
+
+      expect(await balanceOf(aliceTokenAccount)).to.equal(aliceBefore);
+      expect(await program.account.dateBooking.fetchNullable(booking())).to.be.null;
+    });
+  });
+
+  // ============================================
+  // BOUNTY TESTS
+  // ============================================
+  describe("Matchmaker Bounty", () => {
+    it("bob creates a bounty", async () => {
+      const [bountyPda] = getBountyPda(bob.publicKey);
+      const bountyVault = getAssociatedTokenAddressSync(usdcMint, bountyPda, true);
+
+      const rewardAmount = new BN(50 * ONE_USDC); // 50 USDC
+
+      const initialBalance = (
+        await getAccount(provider.connection, bobTokenAccount)
+      ).amount;
+
+      const tx = await program.methods
+        .createBounty(rewardAmount, { issuerOnly: {} }, null, [], null)
+        .accountsStrict({
+          issuer: bob.publicKey,
+          issuerProfile: getProfilePda(bob.publicKey)[0],
+          mint: usdcMint,
+          bounty: bountyPda,
+          bountyVault: bountyVault,
+          issuerTokenAccount: bobTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([bob])
+        .rpc();
+
+      console.log("Create bounty tx:", tx);
+
+      // Verify bounty was created
+      const bounty = await program.account.bountyVault.fetch(bountyPda);
+      expect(bounty.issuer.toString()).to.equal(bob.publicKey.toString());
+      expect(bounty.rewardAmount.toNumber()).to.equal(50 * ONE_USDC);
+      expect(bounty.status).to.deep.equal({ open: {} });
+
+      // Verify USDC was transferred
+      const finalBalance = (
+        await getAccount(provider.connection, bobTokenAccount)
+      ).amount;
+      expect(Number(initialBalance) - Number(finalBalance)).to.equal(50 * ONE_USDC);
+    });
+
+    it("bob increases his bounty", async () => {
+      const [bountyPda] = getBountyPda(bob.publicKey);
+      const bountyVault = getAssociatedTokenAddressSync(usdcMint, bountyPda, true);
+
+      const newAmount = new BN(75 * ONE_USDC); // Increase to 75 USDC
+
+      const initialVaultBalance = (
+        await getAccount(provider.connection, bountyVault)
+      ).amount;
+
+      const tx = await program.methods
+        .updateBounty(newAmount, null)
+        .accountsStrict({
+          issuer: bob.publicKey,
+          issuerProfile: getProfilePda(bob.publicKey)[0],
+          mint: usdcMint,
+          bounty: bountyPda,
+          bountyVault: bountyVault,
+          issuerTokenAccount: bobTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([bob])
+        .rpc();
+
+      console.log("Increase bounty tx:", tx);
+
+      // Verify bounty was updated
+      const bounty = await program.account.bountyVault.fetch(bountyPda);
+      expect(bounty.rewardAmount.toNumber()).to.equal(75 * ONE_USDC);
+
+      // Verify additional USDC was deposited
+      const finalVaultBalance = (
+        await getAccount(provider.connection, bountyVault)
+      ).amount;
+      expect(Number(finalVaultBalance) - Number(initialVaultBalance)).to.equal(25 * ONE_USDC);
+    });
+
+    it("bob decreases his bounty (partial withdrawal)", async () => {
+      const [bountyPda] = getBountyPda(bob.publicKey);
+      const bountyVault = getAssociatedTokenAddressSync(usdcMint, bountyPda, true);
+
+      const newAmount = new BN(60 * ONE_USDC); // Decrease to 60 USDC
+
+      const initialBobBalance = (
+        await getAccount(provider.connection, bobTokenAccount)
+      ).amount;
+
+      const tx = await program.methods
+        .updateBounty(newAmount, null)
+        .accountsStrict({
+          issuer: bob.publicKey,
+          issuerProfile: getProfilePda(bob.publicKey)[0],
+          mint: usdcMint,
+          bounty: bountyPda,
+          bountyVault: bountyVault,
+          issuerTokenAccount: bobTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([bob])
+        .rpc();
+
+      console.log("Decrease bounty tx:", tx);
+
+      // Verify bounty was updated
+      const bounty = await program.account.bountyVault.fetch(bountyPda);
+      expect(bounty.rewardAmount.toNumber()).to.equal(60 * ONE_USDC);
+
+      // Verify USDC was returned to bob
+      const finalBobBalance = (
+        await getAccount(provider.connection, bobTokenAccount)
+      ).amount;
+      expect(Number(finalBobBalance) - Number(initialBobBalance)).to.equal(15 * ONE_USDC);
+    });
+
+    it("charlie chips in to bob's bounty", async () => {
+      const [bountyPda] = getBountyPda(bob.publicKey);
+      const [contributionPda] = getContributionPda(bountyPda, charlie.publicKey);
+      const before = await program.account.bountyVault.fetch(bountyPda);
+
+      await program.methods
+        .contributeToBounty(new BN(ONE_USDC))
+        .accountsStrict({
+          contributor: charlie.publicKey,
+          mint: usdcMint,
+          bounty: bountyPda,
+          bountyVault: getAssociatedTokenAddressSync(usdcMint, bountyPda, true),
+          contributorTokenAccount: charlieTokenAccount,
+          contribution: contributionPda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([charlie])
+        .rpc();
+
+      const bounty = await program.account.bountyVault.fetch(bountyPda);
+      expect(bounty.rewardAmount.toNumber()).to.equal(
+        before.rewardAmount.toNumber() + ONE_USDC
+      );
+      expect(bounty.contributedAmount.toNumber()).to.equal(ONE_USDC);
+      expect(bounty.contributorCount).to.equal(1);
+
+      const contribution = await program.account.contribution.fetch(contributionPda);
+      expect(contribution.amount.toNumber()).to.equal(ONE_USDC);
+    });
+
+    it("alice registers as a matchmaker", async () => {
+      const [recordPda] = getMatchmakerPda(alice.publicKey);
+
+      await program.methods
+        .registerMatchmaker(new BN(5 * ONE_USDC), Array.from(Buffer.alloc(32, 3)))
+        .accountsStrict({
+          matchmaker: alice.publicKey,
+          matchmakerRecord: recordPda,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([alice])
+        .rpc();
+
+      await program.methods
+        .updateMatchmaker(new BN(10 * ONE_USDC), null)
+        .accountsStrict({
+          matchmaker: alice.publicKey,
+          matchmakerRecord: recordPda,
+        })
+        .signers([alice])
+        .rpc();
+
+      const record = await program.account.matchmakerProfile.fetch(recordPda);
+      expect(record.matchmaker.toString()).to.equal(alice.publicKey.toString());
+      expect(record.minFee.toNumber()).to.equal(10 * ONE_USDC);
+      expect(record.specialtiesHash).to.deep.equal(Array.from(Buffer.alloc(32, 3)));
+      expect(record.successfulPayouts).to.equal(0);
+      expect(record.totalEarned.toNumber()).to.equal(0);
+    });
+
+    it("alice submits charlie as a match for bob", async () => {
+      const [bountyPda] = getBountyPda(bob.publicKey);
+      const [submissionPda] = getSubmissionPda(bountyPda, alice.publicKey);
+      const noteHash = Array.from(Buffer.alloc(32, 7));
+
+      await program.methods
+        .submitReferral(charlie.publicKey, noteHash)
+        .accountsStrict({
+          matchmaker: alice.publicKey,
+          bounty: bountyPda,
+          submission: submissionPda,
+          matchmakerRecord: getMatchmakerPda(alice.publicKey)[0],
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([alice])
+        .rpc();
+
+      const submission = await program.account.submission.fetch(submissionPda);
+      expect(submission.candidate.toString()).to.equal(charlie.publicKey.toString());
+      expect(submission.noteHash).to.deep.equal(noteHash);
+      expect(submission.status).to.deep.equal({ pending: {} });
+    });
+
+    it("a rejected submission can't be paid and is withdrawn", async () => {
+      const [bountyPda] = getBountyPda(bob.publicKey);
+      const [submissionPda] = getSubmissionPda(bountyPda, charlie.publicKey);
+
+      await program.methods
+        .submitReferral(alice.publicKey, Array.from(Buffer.alloc(32)))
+        .accountsStrict({
+          matchmaker: charlie.publicKey,
+          bounty: bountyPda,
+          submission: submissionPda,
+          matchmakerRecord: getMatchmakerPda(charlie.publicKey)[0],
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([charlie])
+        .rpc();
+
+      await program.methods
+        .rejectSubmission()
+        .accountsStrict({
+          issuer: bob.publicKey,
+          issuerProfile: getProfilePda(bob.publicKey)[0],
+          bounty: bountyPda,
+          submission: submissionPda,
+        })
+        .signers([bob])
+        .rpc();
+
+      try {
+        await program.methods
+          .payoutReferral()
+          .accountsStrict({
+            issuer: bob.publicKey,
+            issuerProfile: getProfilePda(bob.publicKey)[0],
+            matchmaker: charlie.publicKey,
+            matchmakerProfile: getProfilePda(charlie.publicKey)[0],
+            matchmakerRecord: getMatchmakerPda(charlie.publicKey)[0],
+            mint: usdcMint,
+            bounty: bountyPda,
+            submission: submissionPda,
+            bountyVault: getAssociatedTokenAddressSync(usdcMint, bountyPda, true),
+            matchmakerTokenAccount: charlieTokenAccount,
+            treasury: TREASURY,
+            treasuryTokenAccount: getAssociatedTokenAddressSync(usdcMint, TREASURY),
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([bob])
+          .rpc();
+
+        expect.fail("Should have thrown SubmissionNotLive error");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("SubmissionNotLive");
+      }
+
+      await program.methods
+        .withdrawSubmission()
+        .accountsStrict({
+          matchmaker: charlie.publicKey,
+          submission: submissionPda,
+        })
+        .signers([charlie])
+        .rpc();
+
+      const closed = await program.account.submission.fetchNullable(submissionPda);
+      expect(closed).to.be.null;
+    });
+
+    it("bob pays out bounty to alice (matchmaker)", async () => {
+      const [bountyPda] = getBountyPda(bob.publicKey);
+      const bountyVault = getAssociatedTokenAddressSync(usdcMint, bountyPda, true);
+
+      const initialAliceBalance = (
+        await getAccount(provider.connection, aliceTokenAccount)
+      ).amount;
+
+      const bountyAmount = (await program.account.bountyVault.fetch(bountyPda))
+        .rewardAmount.toNumber();
+
+      const treasuryTokenAccount = getAssociatedTokenAddressSync(usdcMint, TREASURY);
+
+      const tx = await program.methods
+        .payoutReferral()
+        .accountsStrict({
+          issuer: bob.publicKey,
+          issuerProfile: getProfilePda(bob.publicKey)[0],
+          matchmaker: alice.publicKey,
+          matchmakerProfile: getProfilePda(alice.publicKey)[0],
+          matchmakerRecord: getMatchmakerPda(alice.publicKey)[0],
+          mint: usdcMint,
+          bounty: bountyPda,
+          submission: getSubmissionPda(bountyPda, alice.publicKey)[0],
+          bountyVault: bountyVault,
+          matchmakerTokenAccount: aliceTokenAccount,
+          treasury: TREASURY,
+          treasuryTokenAccount: treasuryTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts([
+          {
+            pubkey: getContributionPda(bountyPda, charlie.publicKey)[0],
+            isSigner: false,
+            isWritable: false,
+          },
+        ])
+        .signers([bob])
+        .rpc();
+
+      console.log("Payout bounty tx:", tx);
+
+      // Verify alice received the bounty (minus 1% fee)
+      const finalAliceBalance = (
+        await getAccount(provider.connection, aliceTokenAccount)
+      ).amount;
+      const expectedAmount = bountyAmount - Math.floor(bountyAmount / 100); // 99% after 1% fee
+      expect(Number(finalAliceBalance) - Number(initialAliceBalance)).to.equal(expectedAmount);
+
+      // Verify alice's matchmaker reputation was credited
+      const aliceProfile = await program.account.userProfile.fetch(
+        getProfilePda(alice.publicKey)[0]
+      );
+      expect(aliceProfile.reputation.bountiesPaid).to.equal(1);
+      expect(aliceProfile.reputation.bountyEarned.toNumber()).to.equal(expectedAmount);
+
+      // ...and so was alice's matchmaker record
+      const aliceRecord = await program.account.matchmakerProfile.fetch(
+        getMatchmakerPda(alice.publicKey)[0]
+      );
+      expect(aliceRecord.successfulPayouts).to.equal(1);
+      expect(aliceRecord.totalEarned.toNumber()).to.equal(expectedAmount);
+
+      // Verify bounty vault is empty (tokens transferred out)
+      const vaultBalance = await getAccount(provider.connection, bountyVault);
+      expect(Number(vaultBalance.amount)).to.equal(0);
+
+      // The bounty is paid, so charlie can reclaim the contribution's rent
+      const [contributionPda] = getContributionPda(bountyPda, charlie.publicKey);
+      await program.methods
+        .closeContribution()
+        .accountsStrict({
+          contributor: charlie.publicKey,
+          bounty: bountyPda,
+          contribution: contributionPda,
+        })
+        .signers([charlie])
+        .rpc();
+      expect(await program.account.contribution.fetchNullable(contributionPda)).to.be.null;
+    });
+
+    it("a bounty can require a matchmaker track record", async () => {
+      const [bountyPda] = getBountyPda(bob.publicKey);
+      const bountyVault = getAssociatedTokenAddressSync(usdcMint, bountyPda, true);
+      const submit = (matchmaker: Keypair, candidate: PublicKey) =>
+        program.methods
+          .submitReferral(candidate, Array.from(Buffer.alloc(32)))
+          .accountsStrict({
+            matchmaker: matchmaker.publicKey,
+            bounty: bountyPda,
+            submission: getSubmissionPda(bountyPda, matchmaker.publicKey)[0],
+            matchmakerRecord: getMatchmakerPda(matchmaker.publicKey)[0],
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([matchmaker])
+          .rpc();
+
+      // Only matchmakers with at least one paid referral
+      await program.methods
+        .createBounty(new BN(ONE_USDC), { issuerOnly: {} }, null, [], 1)
+        .accountsStrict({
+          issuer: bob.publicKey,
+          issuerProfile: getProfilePda(bob.publicKey)[0],
+          mint: usdcMint,
+          bounty: bountyPda,
+          bountyVault,
+          issuerTokenAccount: bobTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([bob])
+        .rpc();
+
+      try {
+        await submit(charlie, alice.publicKey);
+        expect.fail("Should have thrown MatchmakerNotRegistered error");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("MatchmakerNotRegistered");
+      }
+
+      // Registered, but with no payouts yet
+      const [charlieRecord] = getMatchmakerPda(charlie.publicKey);
+      await program.methods
+        .registerMatchmaker(new BN(0), Array.from(Buffer.alloc(32)))
+        .accountsStrict({
+          matchmaker: charlie.publicKey,
+          matchmakerRecord: charlieRecord,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([charlie])
+        .rpc();
+      try {
+        await submit(charlie, alice.publicKey);
+        expect.fail("Should have thrown InsufficientTrackRecord error");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("InsufficientTrackRecord");
+      }
 
-      // Verify charlie's bid was taken
-      const charlieFinalBalance = (
-        await getAccount(provider.connection, charlieTokenAccount)
-      ).amount;
-      expect(Number(charlieInitialBalance) - Number(charlieFinalBalance)).to.equal(25 * ONE_USDC);
+      // Alice has been paid once, which is enough - but not for under 10 USDC
+      try {
+        await submit(alice, charlie.publicKey);
+        expect.fail("Should have thrown RewardBelowMatchmakerFee error");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("RewardBelowMatchmakerFee");
+      }
 
-      // Verify auction state
-      const auction = await program.account.dateAuction.fetch(auctionPda);
-      expect(auction.highestBidder.toString()).to.equal(charlie.publicKey.toString());
-      expect(auction.highestBid.toNumber()).to.equal(25 * ONE_USDC);
+      const setReward = (amount: number) =>
+        program.methods
+          .updateBounty(new BN(amount), null)
+          .accountsStrict({
+            issuer: bob.publicKey,
+            issuerProfile: getProfilePda(bob.publicKey)[0],
+            mint: usdcMint,
+            bounty: bountyPda,
+            bountyVault,
+            issuerTokenAccount: bobTokenAccount,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([bob])
+          .rpc();
+      await setReward(10 * ONE_USDC);
+      await submit(alice, charlie.publicKey);
+      const [aliceSubmission] = getSubmissionPda(bountyPda, alice.publicKey);
+      expect(
+        (await program.account.submission.fetch(aliceSubmission)).minFee.toNumber()
+      ).to.equal(10 * ONE_USDC);
+
+      // Lowering the reward after the submission doesn't get under alice's fee
+      await setReward(ONE_USDC);
+      try {
+        await program.methods
+          .payoutReferral()
+          .accountsStrict({
+            issuer: bob.publicKey,
+            issuerProfile: getProfilePda(bob.publicKey)[0],
+            matchmaker: alice.publicKey,
+            matchmakerProfile: getProfilePda(alice.publicKey)[0],
+            matchmakerRecord: getMatchmakerPda(alice.publicKey)[0],
+            mint: usdcMint,
+            bounty: bountyPda,
+            submission: aliceSubmission,
+            bountyVault,
+            matchmakerTokenAccount: aliceTokenAccount,
+            treasury: TREASURY,
+            treasuryTokenAccount: getAssociatedTokenAddressSync(usdcMint, TREASURY),
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([bob])
+          .rpc();
+        expect.fail("Should have thrown RewardBelowMatchmakerFee error");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("RewardBelowMatchmakerFee");
+      }
+
+      await program.methods
+        .cancelBounty()
+        .accountsStrict({
+          issuer: bob.publicKey,
+          issuerProfile: getProfilePda(bob.publicKey)[0],
+          mint: usdcMint,
+          bounty: bountyPda,
+          bountyVault,
+          issuerTokenAccount: bobTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([bob])
+        .rpc();
+      await program.methods
+        .withdrawSubmission()
+        .accountsStrict({
+          matchmaker: alice.publicKey,
+          submission: aliceSubmission,
+        })
+        .signers([alice])
+        .rpc();
     });
-  });
 
-  // ============================================
-  // BOUNTY TESTS
-  // ============================================
-  describe("Matchmaker Bounty", () => {
-    it("bob creates a bounty", async () => {
-      const [bountyPda] = getBountyPda(bob.publicKey);
+    it("alice creates and cancels a bounty", async () => {
+      const [bountyPda] = getBountyPda(alice.publicKey);
       const bountyVault = getAssociatedTokenAddressSync(usdcMint, bountyPda, true);
 
-      const rewardAmount = new BN(50 * ONE_USDC); // 50 USDC
+      // First create a bounty
+      const rewardAmount = new BN(20 * ONE_USDC);
 
-      const initialBalance = (
-        await getAccount(provider.connection, bobTokenAccount)
+      await program.methods
+        .createBounty(rewardAmount, { issuerOnly: {} }, null, [], null)
+        .accountsStrict({
+          issuer: alice.publicKey,
+          issuerProfile: getProfilePda(alice.publicKey)[0],
+          mint: usdcMint,
+          bounty: bountyPda,
+          bountyVault: bountyVault,
+          issuerTokenAccount: aliceTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([alice])
+        .rpc();
+
+      const initialAliceBalance = (
+        await getAccount(provider.connection, aliceTokenAccount)
       ).amount;
 
+      // Now cancel it
       const tx = await program.methods
-        .createBounty(rewardAmount)
+        .cancelBounty()
         .accountsStrict({
-          issuer: bob.publicKey,
+          issuer: alice.publicKey,
+          issuerProfile: getProfilePda(alice.publicKey)[0],
           mint: usdcMint,
           bounty: bountyPda,
           bountyVault: bountyVault,
-          issuerTokenAccount: bobTokenAccount,
+          issuerTokenAccount: aliceTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([alice])
+        .rpc();
+
+      console.log("Cancel bounty tx:", tx);
+
+      // Verify alice got her USDC back
+      const finalAliceBalance = (
+        await getAccount(provider.connection, aliceTokenAccount)
+      ).amount;
+      expect(Number(finalAliceBalance) - Number(initialAliceBalance)).to.equal(20 * ONE_USDC);
+    });
+
+    it("a consent-mode bounty releases once the issuer and candidate confirm", async () => {
+      const [bountyPda] = getBountyPda(alice.publicKey);
+      const bountyVault = getAssociatedTokenAddressSync(usdcMint, bountyPda, true);
+      const [submissionPda] = getSubmissionPda(bountyPda, charlie.publicKey);
+      const issuerProfile = getProfilePda(alice.publicKey)[0];
+      const treasuryTokenAccount = getAssociatedTokenAddressSync(usdcMint, TREASURY);
+
+      await program.methods
+        .createBounty(new BN(5 * ONE_USDC), { consent: {} }, null, [], null)
+        .accountsStrict({
+          issuer: alice.publicKey,
+          issuerProfile,
+          mint: usdcMint,
+          bounty: bountyPda,
+          bountyVault,
+          issuerTokenAccount: aliceTokenAccount,
           tokenProgram: TOKEN_PROGRAM_ID,
           associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
         })
-        .signers([bob])
+        .signers([alice])
         .rpc();
 
-      console.log("Create bounty tx:", tx);
+      // Charlie introduces bob and claims the reward
+      await program.methods
+        .submitReferral(bob.publicKey, Array.from(Buffer.alloc(32, 1)))
+        .accountsStrict({
+          matchmaker: charlie.publicKey,
+          bounty: bountyPda,
+          submission: submissionPda,
+          matchmakerRecord: getMatchmakerPda(charlie.publicKey)[0],
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([charlie])
+        .rpc();
+      await program.methods
+        .claimReferral()
+        .accountsStrict({
+          matchmaker: charlie.publicKey,
+          bounty: bountyPda,
+          submission: submissionPda,
+        })
+        .signers([charlie])
+        .rpc();
 
-      // Verify bounty was created
-      const bounty = await program.account.bountyVault.fetch(bountyPda);
-      expect(bounty.issuer.toString()).to.equal(bob.publicKey.toString());
-      expect(bounty.rewardAmount.toNumber()).to.equal(50 * ONE_USDC);
-      expect(bounty.status).to.deep.equal({ open: {} });
+      // The claim locks the reward - alice can't pull it back
+      try {
+        await program.methods
+          .cancelBounty()
+          .accountsStrict({
+            issuer: alice.publicKey,
+            issuerProfile,
+            mint: usdcMint,
+            bounty: bountyPda,
+            bountyVault,
+            issuerTokenAccount: aliceTokenAccount,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([alice])
+          .rpc();
 
-      // Verify USDC was transferred
-      const finalBalance = (
-        await getAccount(provider.connection, bobTokenAccount)
+        expect.fail("Should have thrown BountyClaimPending error");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("BountyClaimPending");
+      }
+
+      const confirm = (signer: Keypair) =>
+        program.methods
+          .confirmReferral()
+          .accountsStrict({
+            signer: signer.publicKey,
+            issuerProfile,
+            bounty: bountyPda,
+            submission: submissionPda,
+          })
+          .signers([signer])
+          .rpc();
+      const release = () =>
+        program.methods
+          .releaseReferral()
+          .accountsStrict({
+            payer: charlie.publicKey,
+            issuer: alice.publicKey,
+            issuerProfile,
+            matchmaker: charlie.publicKey,
+            matchmakerProfile: getProfilePda(charlie.publicKey)[0],
+            matchmakerRecord: getMatchmakerPda(charlie.publicKey)[0],
+            mint: usdcMint,
+            bounty: bountyPda,
+            submission: submissionPda,
+            bountyVault,
+            matchmakerTokenAccount: charlieTokenAccount,
+            treasury: TREASURY,
+            treasuryTokenAccount,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([charlie])
+          .rpc();
+
+      await confirm(alice);
+      try {
+        await release();
+        expect.fail("Should have thrown ConsentNotReached error");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("ConsentNotReached");
+      }
+
+      const initialCharlieBalance = (
+        await getAccount(provider.connection, charlieTokenAccount)
       ).amount;
-      expect(Number(initialBalance) - Number(finalBalance)).to.equal(50 * ONE_USDC);
+
+      await confirm(bob);
+      await release();
+
+      const finalCharlieBalance = (
+        await getAccount(provider.connection, charlieTokenAccount)
+      ).amount;
+      const expectedAmount = 5 * ONE_USDC - Math.floor((5 * ONE_USDC) / 100);
+      expect(Number(finalCharlieBalance) - Number(initialCharlieBalance)).to.equal(expectedAmount);
+      expect(await program.account.bountyVault.fetchNullable(bountyPda)).to.be.null;
     });
 
-    it("bob increases his bounty", async () => {
-      const [bountyPda] = getBountyPda(bob.publicKey);
-      const bountyVault = getAssociatedTokenAddressSync(usdcMint, bountyPda, true);
+    it("an expiring bounty can't be refunded before its deadline", async () => {
+      const [bountyPda] = getBountyPda(alice.publicKey);
+      const bountyAccounts = {
+        issuer: alice.publicKey,
+        issuerProfile: getProfilePda(alice.publicKey)[0],
+        mint: usdcMint,
+        bounty: bountyPda,
+        bountyVault: getAssociatedTokenAddressSync(usdcMint, bountyPda, true),
+        issuerTokenAccount: aliceTokenAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      };
+      const now = Math.floor(Date.now() / 1000);
 
-      const newAmount = new BN(75 * ONE_USDC); // Increase to 75 USDC
+      try {
+        await program.methods
+          .createBounty(new BN(ONE_USDC), { issuerOnly: {} }, new BN(now - 60), [], null)
+          .accountsStrict({
+            ...bountyAccounts,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([alice])
+          .rpc();
 
-      const initialVaultBalance = (
-        await getAccount(provider.connection, bountyVault)
-      ).amount;
+        expect.fail("Should have thrown InvalidBountyExpiry error");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("InvalidBountyExpiry");
+      }
 
-      const tx = await program.methods
-        .updateBounty(newAmount)
+      await program.methods
+        .createBounty(new BN(ONE_USDC), { issuerOnly: {} }, new BN(now + 3600), [], null)
         .accountsStrict({
-          issuer: bob.publicKey,
+          ...bountyAccounts,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([alice])
+        .rpc();
+
+      const bounty = await program.account.bountyVault.fetch(bountyPda);
+      expect(bounty.expiresAt.toNumber()).to.equal(now + 3600);
+
+      // Anyone may crank the refund, but only once the deadline has passed
+      try {
+        await program.methods
+          .refundExpiredBounty()
+          .accountsStrict(bountyAccounts)
+          .rpc();
+
+        expect.fail("Should have thrown BountyNotExpired error");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("BountyNotExpired");
+      }
+
+      await program.methods
+        .cancelBounty()
+        .accountsStrict(bountyAccounts)
+        .signers([alice])
+        .rpc();
+    });
+
+    it("an expired bounty's deadline can't be moved", async () => {
+      const [bountyPda] = getBountyPda(alice.publicKey);
+      const bountyAccounts = {
+        issuer: alice.publicKey,
+        issuerProfile: getProfilePda(alice.publicKey)[0],
+        mint: usdcMint,
+        bounty: bountyPda,
+        bountyVault: getAssociatedTokenAddressSync(usdcMint, bountyPda, true),
+        issuerTokenAccount: aliceTokenAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      };
+      const now = Math.floor(Date.now() / 1000);
+
+      await program.methods
+        .createBounty(new BN(ONE_USDC), { issuerOnly: {} }, new BN(now + 2), [], null)
+        .accountsStrict({
+          ...bountyAccounts,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([alice])
+        .rpc();
+      await new Promise((resolve) => setTimeout(resolve, 4000));
+
+      try {
+        await program.methods
+          .updateBounty(new BN(ONE_USDC), new BN(now + 3600))
+          .accountsStrict(bountyAccounts)
+          .signers([alice])
+          .rpc();
+
+        expect.fail("Should have thrown BountyExpired error");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("BountyExpired");
+      }
+
+      await program.methods
+        .refundExpiredBounty()
+        .accountsStrict(bountyAccounts)
+        .rpc();
+      expect(await program.account.bountyVault.fetchNullable(bountyPda)).to.be.null;
+    });
+
+    it("cancelling a crowdfunded bounty refunds every contributor", async () => {
+      const [bountyPda] = getBountyPda(alice.publicKey);
+      const bountyVault = getAssociatedTokenAddressSync(usdcMint, bountyPda, true);
+      const [contributionPda] = getContributionPda(bountyPda, charlie.publicKey);
+      const issuerProfile = getProfilePda(alice.publicKey)[0];
+      const balanceOf = async (account: PublicKey) =>
+        Number((await getAccount(provider.connection, account)).amount);
+
+      await program.methods
+        .createBounty(new BN(4 * ONE_USDC), { issuerOnly: {} }, null, [], null)
+        .accountsStrict({
+          issuer: alice.publicKey,
+          issuerProfile,
+          mint: usdcMint,
+          bounty: bountyPda,
+          bountyVault,
+          issuerTokenAccount: aliceTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([alice])
+        .rpc();
+      await program.methods
+        .contributeToBounty(new BN(ONE_USDC))
+        .accountsStrict({
+          contributor: charlie.publicKey,
+          mint: usdcMint,
+          bounty: bountyPda,
+          bountyVault,
+          contributorTokenAccount: charlieTokenAccount,
+          contribution: contributionPda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([charlie])
+        .rpc();
+
+      // Alice only gets her own share back - charlie's stays for the contributor refund
+      const aliceBefore = await balanceOf(aliceTokenAccount);
+      await program.methods
+        .cancelBounty()
+        .accountsStrict({
+          issuer: alice.publicKey,
+          issuerProfile,
           mint: usdcMint,
           bounty: bountyPda,
-          bountyVault: bountyVault,
-          issuerTokenAccount: bobTokenAccount,
+          bountyVault,
+          issuerTokenAccount: aliceTokenAccount,
           tokenProgram: TOKEN_PROGRAM_ID,
         })
-        .signers([bob])
+        .signers([alice])
         .rpc();
+      expect((await balanceOf(aliceTokenAccount)) - aliceBefore).to.equal(4 * ONE_USDC);
 
-      console.log("Increase bounty tx:", tx);
-
-      // Verify bounty was updated
       const bounty = await program.account.bountyVault.fetch(bountyPda);
-      expect(bounty.rewardAmount.toNumber()).to.equal(75 * ONE_USDC);
-
-      // Verify additional USDC was deposited
-      const finalVaultBalance = (
-        await getAccount(provider.connection, bountyVault)
-      ).amount;
-      expect(Number(finalVaultBalance) - Number(initialVaultBalance)).to.equal(25 * ONE_USDC);
-    });
+      expect(bounty.status).to.deep.equal({ refunding: {} });
 
-    it("bob decreases his bounty (partial withdrawal)", async () => {
-      const [bountyPda] = getBountyPda(bob.publicKey);
-      const bountyVault = getAssociatedTokenAddressSync(usdcMint, bountyPda, true);
-
-      const newAmount = new BN(60 * ONE_USDC); // Decrease to 60 USDC
-
-      const initialBobBalance = (
-        await getAccount(provider.connection, bobTokenAccount)
-      ).amount;
-
-      const tx = await program.methods
-        .updateBounty(newAmount)
+      // Anyone can crank the contributor's refund; the last one closes the bounty
+      const charlieBefore = await balanceOf(charlieTokenAccount);
+      await program.methods
+        .refundContribution()
         .accountsStrict({
-          issuer: bob.publicKey,
+          contributor: charlie.publicKey,
+          issuer: alice.publicKey,
+          issuerProfile,
           mint: usdcMint,
           bounty: bountyPda,
-          bountyVault: bountyVault,
-          issuerTokenAccount: bobTokenAccount,
+          contribution: contributionPda,
+          bountyVault,
+          contributorTokenAccount: charlieTokenAccount,
           tokenProgram: TOKEN_PROGRAM_ID,
         })
-        .signers([bob])
         .rpc();
-
-      console.log("Decrease bounty tx:", tx);
-
-      // Verify bounty was updated
-      const bounty = await program.account.bountyVault.fetch(bountyPda);
-      expect(bounty.rewardAmount.toNumber()).to.equal(60 * ONE_USDC);
-
-      // Verify USDC was returned to bob
-      const finalBobBalance = (
-        await getAccount(provider.connection, bobTokenAccount)
-      ).amount;
-      expect(Number(finalBobBalance) - Number(initialBobBalance)).to.equal(15 * ONE_USDC);
+      expect((await balanceOf(charlieTokenAccount)) - charlieBefore).to.equal(ONE_USDC);
+      expect(await program.account.bountyVault.fetchNullable(bountyPda)).to.be.null;
     });
 
-    it("bob pays out bounty to alice (matchmaker)", async () => {
-      const [bountyPda] = getBountyPda(bob.publicKey);
+    it("alice splits a bounty between two matchmakers", async () => {
+      const [bountyPda] = getBountyPda(alice.publicKey);
       const bountyVault = getAssociatedTokenAddressSync(usdcMint, bountyPda, true);
+      const issuerProfile = getProfilePda(alice.publicKey)[0];
+      const balanceOf = async (account: PublicKey) =>
+        Number((await getAccount(provider.connection, account)).amount);
 
-      const initialAliceBalance = (
-        await getAccount(provider.connection, aliceTokenAccount)
-      ).amount;
-
-      const bountyAmount = (await program.account.bountyVault.fetch(bountyPda))
-        .rewardAmount.toNumber();
-
-      const treasuryTokenAccount = getAssociatedTokenAddressSync(usdcMint, TREASURY);
-
-      const tx = await program.methods
-        .payoutReferral()
+      await program.methods
+        .createBounty(new BN(3 * ONE_USDC), { issuerOnly: {} }, null, [], null)
         .accountsStrict({
-          issuer: bob.publicKey,
-          matchmaker: alice.publicKey,
+          issuer: alice.publicKey,
+          issuerProfile,
           mint: usdcMint,
           bounty: bountyPda,
-          bountyVault: bountyVault,
-          matchmakerTokenAccount: aliceTokenAccount,
-          treasury: TREASURY,
-          treasuryTokenAccount: treasuryTokenAccount,
+          bountyVault,
+          issuerTokenAccount: aliceTokenAccount,
           tokenProgram: TOKEN_PROGRAM_ID,
           associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
         })
-        .signers([bob])
+        .signers([alice])
         .rpc();
 
-      console.log("Payout bounty tx:", tx);
+      // Bob and charlie each put forward the other
+      for (const [matchmaker, candidate] of [
+        [bob, charlie],
+        [charlie, bob],
+      ]) {
+        await program.methods
+          .submitReferral(candidate.publicKey, Array.from(Buffer.alloc(32)))
+          .accountsStrict({
+            matchmaker: matchmaker.publicKey,
+            bounty: bountyPda,
+            submission: getSubmissionPda(bountyPda, matchmaker.publicKey)[0],
+            matchmakerRecord: getMatchmakerPda(matchmaker.publicKey)[0],
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([matchmaker])
+          .rpc();
+      }
 
-      // Verify alice received the bounty (minus 1% fee)
-      const finalAliceBalance = (
-        await getAccount(provider.connection, aliceTokenAccount)
-      ).amount;
-      const expectedAmount = bountyAmount - Math.floor(bountyAmount / 100); // 99% after 1% fee
-      expect(Number(finalAliceBalance) - Number(initialAliceBalance)).to.equal(expectedAmount);
+      const recipient = (
+        matchmaker: Keypair,
+        tokenAccount: PublicKey,
+        profile = getProfilePda(matchmaker.publicKey)[0]
+      ) => [
+        { pubkey: matchmaker.publicKey, isSigner: false, isWritable: true },
+        {
+          pubkey: getSubmissionPda(bountyPda, matchmaker.publicKey)[0],
+          isSigner: false,
+          isWritable: true,
+        },
+        { pubkey: tokenAccount, isSigner: false, isWritable: true },
+        { pubkey: profile, isSigner: false, isWritable: true },
+        {
+          pubkey: getMatchmakerPda(matchmaker.publicKey)[0],
+          isSigner: false,
+          isWritable: true,
+        },
+      ];
+      const split = (shares: number[], charlieProfile?: PublicKey) =>
+        program.methods
+          .payoutReferralSplit(shares)
+          .accountsStrict({
+            issuer: alice.publicKey,
+            issuerProfile,
+            mint: usdcMint,
+            bounty: bountyPda,
+            bountyVault,
+            treasury: TREASURY,
+            treasuryTokenAccount: getAssociatedTokenAddressSync(usdcMint, TREASURY),
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .remainingAccounts([
+            ...recipient(bob, bobTokenAccount),
+            ...recipient(charlie, charlieTokenAccount, charlieProfile),
+          ])
+          .signers([alice])
+          .rpc();
 
-      // Verify bounty vault is empty (tokens transferred out)
-      const vaultBalance = await getAccount(provider.connection, bountyVault);
-      expect(Number(vaultBalance.amount)).to.equal(0);
+      try {
+        await split([7000, 2000]);
+        expect.fail("Should have thrown InvalidSplitShares error");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("InvalidSplitShares");
+      }
+
+      // Leaving a matchmaker's profile out can't skip their reputation credit
+      try {
+        await split([7000, 3000], program.programId);
+        expect.fail("Should have thrown InvalidSplitAccounts error");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("InvalidSplitAccounts");
+      }
+
+      const bobBefore = await balanceOf(bobTokenAccount);
+      const charlieBefore = await balanceOf(charlieTokenAccount);
+      const charliePaidBefore = (
+        await program.account.userProfile.fetch(getProfilePda(charlie.publicKey)[0])
+      ).reputation.bountiesPaid;
+      await split([7000, 3000]);
+
+      // 1% fee comes off each recipient's gross share
+      const net = (gross: number) => gross - Math.floor(gross / 100);
+      expect((await balanceOf(bobTokenAccount)) - bobBefore).to.equal(net(2_100_000));
+      expect((await balanceOf(charlieTokenAccount)) - charlieBefore).to.equal(net(900_000));
+      expect(await program.account.bountyVault.fetchNullable(bountyPda)).to.be.null;
+      const charlieProfile = await program.account.userProfile.fetch(
+        getProfilePda(charlie.publicKey)[0]
+      );
+      expect(charlieProfile.reputation.bountiesPaid).to.equal(charliePaidBefore + 1);
     });
 
-    it("alice creates and cancels a bounty", async () => {
+    it("alice pays a staged bounty milestone by milestone", async () => {
       const [bountyPda] = getBountyPda(alice.publicKey);
       const bountyVault = getAssociatedTokenAddressSync(usdcMint, bountyPda, true);
+      const [submissionPda] = getSubmissionPda(bountyPda, bob.publicKey);
+      const issuerProfile = getProfilePda(alice.publicKey)[0];
+      const balanceOf = async (account: PublicKey) =>
+        Number((await getAccount(provider.connection, account)).amount);
 
-      // First create a bounty
-      const rewardAmount = new BN(20 * ONE_USDC);
-
+      // Half for the introduction, half once an hour has passed
       await program.methods
-        .createBounty(rewardAmount)
+        .createBounty(new BN(2 * ONE_USDC), { issuerOnly: {} }, null, [
+          { amount: new BN(ONE_USDC), unlockAt: new BN(0), released: false },
+          {
+            amount: new BN(ONE_USDC),
+            unlockAt: new BN(Math.floor(Date.now() / 1000) + 3600),
+            released: false,
+          },
+        ], null)
         .accountsStrict({
           issuer: alice.publicKey,
+          issuerProfile,
           mint: usdcMint,
           bounty: bountyPda,
-          bountyVault: bountyVault,
+          bountyVault,
           issuerTokenAccount: aliceTokenAccount,
           tokenProgram: TOKEN_PROGRAM_ID,
           associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
@@ -735,32 +3273,330 @@ describe("solmates", () => {
         })
         .signers([alice])
         .rpc();
+      await program.methods
+        .submitReferral(charlie.publicKey, Array.from(Buffer.alloc(32)))
+        .accountsStrict({
+          matchmaker: bob.publicKey,
+          bounty: bountyPda,
+          submission: submissionPda,
+          matchmakerRecord: getMatchmakerPda(bob.publicKey)[0],
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([bob])
+        .rpc();
 
-      const initialAliceBalance = (
-        await getAccount(provider.connection, aliceTokenAccount)
-      ).amount;
+      const release = (index: number) =>
+        program.methods
+          .releaseMilestone(index)
+          .accountsStrict({
+            issuer: alice.publicKey,
+            issuerProfile,
+            matchmaker: bob.publicKey,
+            matchmakerProfile: getProfilePda(bob.publicKey)[0],
+            matchmakerRecord: getMatchmakerPda(bob.publicKey)[0],
+            mint: usdcMint,
+            bounty: bountyPda,
+            submission: submissionPda,
+            bountyVault,
+            matchmakerTokenAccount: bobTokenAccount,
+            treasury: TREASURY,
+            treasuryTokenAccount: getAssociatedTokenAddressSync(usdcMint, TREASURY),
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([alice])
+          .rpc();
 
-      // Now cancel it
-      const tx = await program.methods
+      try {
+        await release(1);
+        expect.fail("Should have thrown InvalidMilestoneIndex error");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("InvalidMilestoneIndex");
+      }
+
+      const bobBefore = await balanceOf(bobTokenAccount);
+      await release(0);
+      expect((await balanceOf(bobTokenAccount)) - bobBefore).to.equal(
+        ONE_USDC - Math.floor(ONE_USDC / 100)
+      );
+
+      try {
+        await release(1);
+        expect.fail("Should have thrown MilestoneLocked error");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("MilestoneLocked");
+      }
+
+      // Cancelling returns the unreached milestone to alice
+      const aliceBefore = await balanceOf(aliceTokenAccount);
+      await program.methods
         .cancelBounty()
         .accountsStrict({
           issuer: alice.publicKey,
+          issuerProfile,
           mint: usdcMint,
           bounty: bountyPda,
-          bountyVault: bountyVault,
+          bountyVault,
           issuerTokenAccount: aliceTokenAccount,
           tokenProgram: TOKEN_PROGRAM_ID,
         })
         .signers([alice])
         .rpc();
+      expect((await balanceOf(aliceTokenAccount)) - aliceBefore).to.equal(ONE_USDC);
+      expect(await program.account.bountyVault.fetchNullable(bountyPda)).to.be.null;
 
-      console.log("Cancel bounty tx:", tx);
+      await program.methods
+        .withdrawSubmission()
+        .accountsStrict({ matchmaker: bob.publicKey, submission: submissionPda })
+        .signers([bob])
+        .rpc();
+    });
+  });
 
-      // Verify alice got her USDC back
-      const finalAliceBalance = (
-        await getAccount(provider.connection, aliceTokenAccount)
-      ).amount;
-      expect(Number(finalAliceBalance) - Number(initialAliceBalance)).to.equal(20 * ONE_USDC);
+  // ============================================
+  // LEGACY MIGRATION TESTS
+  // ============================================
+  // The legacy accounts are loaded from tests/fixtures by the test validator
+  describe("Legacy Migration", () => {
+    const key = (address: string) => new PublicKey(address);
+
+    const v0Authority = key("ESqaHVXLwMwNdAuERjJCt6KEwFdxR3JoRz3rbKVtrWAC");
+    const v0DmSender = key("Aqi33zwu8kWqpr81J2XvXQ9sfXffkJXecfhvK3oa86Qs");
+    const gateMint = key("6S4UUXByJH7hTYaoRSkFqw3Kbf4xncZDXmKjwFoNWnqv");
+    const gateCollection = key("7PjLh6MciVHY8RMhAH8CvgPZkNqEa8A93hjUApMrRWMP");
+    const auctionMint = key("8rF3NdmPf5yHyCwsPNMzF5TY1rRwHfeGkfV4ANsbbdR8");
+    const auctionBidder = key("Avt2XpZxW4JvcsgAvmnAaAdM2z25hxkuU59KpBBRwaWQ");
+    const bountyMint = key("6USnSde7kGK56hZgCtVyR55hBER2cp7baBkF9yVSYR9g");
+    const escrowMint = key("4Aubx68DXgvh5YCwJEcyf36A7JqnvSjhaGp2Er4N7yfi");
+    const v0ZeroMinAuthority = key("FfLF3mE6yQHCVVUCMPQLR11GMmcgsyaaMCg1DbUCn55H");
+    const v1Authority = key("964TbVVvE7LAQmqTQnF5jHcPGj4Gsjd26yCHXxrGSWma");
+    const v2Identity = key("3gv1btcRPUHb8CcSYFh3nP9JxGXHpDsyXus4KqDK9cDR");
+    const v2Authority = key("VVi62Pzt91jT72KFhHyeDEzr2TJn6MwcvEMYb4M38qq");
+
+    const readonly = (pubkey: PublicKey) => ({
+      pubkey,
+      isWritable: false,
+      isSigner: false,
+    });
+
+    async function migrateProfile(profile: PublicKey, obligations: PublicKey[] = []) {
+      await program.methods
+        .migrateProfile()
+        .accountsStrict({
+          payer: provider.wallet.publicKey,
+          profile,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(obligations.map(readonly))
+        .rpc();
+    }
+
+    function expectFreshFields(profile: any) {
+      expect(profile.version).to.equal(3);
+      expect(profile.encryptionKey).to.be.null;
+      expect(profile.encryptionKeyVersion).to.equal(0);
+      expect(profile.availability).to.deep.equal({ available: {} });
+      expect(profile.pricing).to.be.null;
+      expect(profile.recentAccepts).to.equal(0);
+      expect(profile.recentAcceptsSince.toNumber()).to.equal(0);
+    }
+
+    it("migrates a pre-versioning profile, counting its obligations", async () => {
+      const [profilePda, bump] = getProfilePda(v0Authority);
+      const [bountyPda] = getBountyPda(v0Authority);
+      const [openAuction] = getAuctionPda(v0Authority, 0);
+      const [settledAuction] = getAuctionPda(v0Authority, 1);
+      const [escrowPda] = getEscrowPda(v0DmSender, v0Authority);
+
+      // Every auction the profile ever hosted must be listed
+      try {
+        await migrateProfile(profilePda, [bountyPda, openAuction, escrowPda]);
+        expect.fail("Should have thrown ObligationAccountsMismatch error");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("ObligationAccountsMismatch");
+      }
+
+      await migrateProfile(profilePda, [
+        bountyPda,
+        openAuction,
+        settledAuction,
+        escrowPda,
+      ]);
+
+      const profile = await program.account.userProfile.fetch(profilePda);
+      expectFreshFields(profile);
+      expect(profile.identity.toString()).to.equal(v0Authority.toString());
+      expect(profile.authority.toString()).to.equal(v0Authority.toString());
+      expect(profile.dmPrice.toNumber()).to.equal(5 * ONE_USDC);
+      expect(profile.gate.mode).to.deep.equal({ anyOf: {} });
+      expect(profile.gate.rules).to.have.length(1);
+      expect(profile.gate.rules[0].token.mint.toString()).to.equal(gateMint.toString());
+      expect(profile.gate.rules[0].token.minAmount.toNumber()).to.equal(3);
+      expect(profile.auctionCount.toNumber()).to.equal(2);
+      // Creation time was never recorded
+      expect(profile.createdAt.toNumber()).to.equal(0);
+      // Only the first auction is still open
+      expect(profile.openAuctions.toNumber()).to.equal(1);
+      expect(profile.openBounties.toNumber()).to.equal(1);
+      expect(profile.pendingEscrows.toNumber()).to.equal(1);
+      expect(profile.bump).to.equal(bump);
+      expect(profile.handle).to.be.null;
+      expect(profile.reputation.dmsAccepted).to.equal(0);
+      expect(profile.reputation.dmEarned.toNumber()).to.equal(0);
+    });
+
+    it("migrates a pre-versioning gate with no minimum to a valid one", async () => {
+      const [profilePda] = getProfilePda(v0ZeroMinAuthority);
+      await migrateProfile(profilePda, [getBountyPda(v0ZeroMinAuthority)[0]]);
+
+      // Holding the mint at all is now a minimum of one token
+      const profile = await program.account.userProfile.fetch(profilePda);
+      expectFreshFields(profile);
+      expect(profile.gate.mode).to.deep.equal({ anyOf: {} });
+      expect(profile.gate.rules).to.have.length(1);
+      expect(profile.gate.rules[0].token.mint.toString()).to.equal(gateMint.toString());
+      expect(profile.gate.rules[0].token.minAmount.toNumber()).to.equal(1);
+      expect(profile.openAuctions.toNumber()).to.equal(0);
+      expect(profile.openBounties.toNumber()).to.equal(0);
+    });
+
+    it("migrates a version 1 profile", async () => {
+      const [profilePda, bump] = getProfilePda(v1Authority);
+      await migrateProfile(profilePda);
+
+      const profile = await program.account.userProfile.fetch(profilePda);
+      expectFreshFields(profile);
+      expect(profile.identity.toString()).to.equal(v1Authority.toString());
+      expect(profile.authority.toString()).to.equal(v1Authority.toString());
+      expect(profile.dmPrice.toNumber()).to.equal(4 * ONE_USDC);
+      expect(profile.gate.mode).to.deep.equal({ allOf: {} });
+      expect(profile.gate.rules).to.have.length(2);
+      expect(profile.gate.rules[0].collection.collection.toString()).to.equal(
+        gateCollection.toString()
+      );
+      expect(profile.gate.rules[1].token.mint.toString()).to.equal(gateMint.toString());
+      expect(profile.gate.rules[1].token.minAmount.toNumber()).to.equal(9);
+      expect(profile.auctionCount.toNumber()).to.equal(4);
+      expect(profile.createdAt.toNumber()).to.equal(1_690_000_000);
+      expect(profile.openAuctions.toNumber()).to.equal(1);
+      expect(profile.openBounties.toNumber()).to.equal(1);
+      expect(profile.pendingEscrows.toNumber()).to.equal(2);
+      expect(profile.bump).to.equal(bump);
+      expect(profile.handle).to.be.null;
+      expect(profile.reputation.dmsAccepted).to.equal(0);
+    });
+
+    it("migrates a version 2 profile", async () => {
+      const [profilePda, bump] = getProfilePda(v2Identity);
+      await migrateProfile(profilePda);
+
+      const profile = await program.account.userProfile.fetch(profilePda);
+      expectFreshFields(profile);
+      expect(profile.identity.toString()).to.equal(v2Identity.toString());
+      expect(profile.authority.toString()).to.equal(v2Authority.toString());
+      expect(profile.dmPrice.toNumber()).to.equal(6 * ONE_USDC);
+      expect(profile.gate).to.be.null;
+      expect(profile.auctionCount.toNumber()).to.equal(5);
+      expect(profile.createdAt.toNumber()).to.equal(1_695_000_000);
+      expect(profile.openAuctions.toNumber()).to.equal(0);
+      expect(profile.openBounties.toNumber()).to.equal(2);
+      expect(profile.pendingEscrows.toNumber()).to.equal(1);
+      expect(profile.bump).to.equal(bump);
+      expect(profile.handle).to.equal("legacy_v2");
+      expect(profile.reputation.dmsAccepted).to.equal(11);
+      expect(profile.reputation.dmsDeclined).to.equal(2);
+      expect(profile.reputation.dmsExpired).to.equal(3);
+      expect(profile.reputation.auctionsHosted).to.equal(4);
+      expect(profile.reputation.auctionsWon).to.equal(5);
+      expect(profile.reputation.bountiesPaid).to.equal(6);
+      expect(profile.reputation.dmEarned.toNumber()).to.equal(70 * ONE_USDC);
+      expect(profile.reputation.auctionEarned.toNumber()).to.equal(80 * ONE_USDC);
+      expect(profile.reputation.bountyEarned.toNumber()).to.equal(90 * ONE_USDC);
+    });
+
+    it("refuses to migrate a current profile", async () => {
+      try {
+        await migrateProfile(getProfilePda(v2Identity)[0]);
+        expect.fail("Should have thrown AccountAlreadyMigrated error");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("AccountAlreadyMigrated");
+      }
+    });
+
+    it("migrates a pre-versioning DM escrow", async () => {
+      const [escrowPda, bump] = getEscrowPda(v0DmSender, v0Authority);
+      await program.methods
+        .migrateEscrow()
+        .accountsStrict({
+          payer: provider.wallet.publicKey,
+          escrow: escrowPda,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const escrow = await program.account.messageEscrow.fetch(escrowPda);
+      expect(escrow.version).to.equal(1);
+      expect(escrow.sender.toString()).to.equal(v0DmSender.toString());
+      expect(escrow.recipient.toString()).to.equal(v0Authority.toString());
+      expect(escrow.mint.toString()).to.equal(escrowMint.toString());
+      expect(escrow.amount.toNumber()).to.equal(2 * ONE_USDC);
+      expect(escrow.expiry.toNumber()).to.equal(1_700_003_600);
+      expect(escrow.status).to.deep.equal({ pending: {} });
+      expect(escrow.bump).to.equal(bump);
+      expect(escrow.inboxPage).to.be.null;
+    });
+
+    it("migrates a pre-versioning date auction", async () => {
+      const [auctionPda, bump] = getAuctionPda(v0Authority, 0);
+      await program.methods
+        .migrateAuction()
+        .accountsStrict({
+          payer: provider.wallet.publicKey,
+          auction: auctionPda,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const auction = await program.account.dateAuction.fetch(auctionPda);
+      expect(auction.version).to.equal(1);
+      expect(auction.host.toString()).to.equal(v0Authority.toString());
+      expect(auction.auctionId.toNumber()).to.equal(0);
+      expect(auction.mint.toString()).to.equal(auctionMint.toString());
+      expect(auction.highestBidder.toString()).to.equal(auctionBidder.toString());
+      expect(auction.highestBid.toNumber()).to.equal(7 * ONE_USDC);
+      expect(auction.endTime.toNumber()).to.equal(1_700_000_000);
+      expect(auction.totalExtended.toNumber()).to.equal(300);
+      expect(auction.bidderRequirements).to.be.null;
+      expect(auction.bump).to.equal(bump);
+    });
+
+    it("migrates a pre-versioning bounty", async () => {
+      const [bountyPda, bump] = getBountyPda(v0Authority);
+      await program.methods
+        .migrateBounty()
+        .accountsStrict({
+          payer: provider.wallet.publicKey,
+          bounty: bountyPda,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const bounty = await program.account.bountyVault.fetch(bountyPda);
+      expect(bounty.version).to.equal(1);
+      expect(bounty.issuer.toString()).to.equal(v0Authority.toString());
+      expect(bounty.mint.toString()).to.equal(bountyMint.toString());
+      expect(bounty.rewardAmount.toNumber()).to.equal(25 * ONE_USDC);
+      expect(bounty.status).to.deep.equal({ open: {} });
+      expect(bounty.bump).to.equal(bump);
+      expect(bounty.createdAt.toNumber()).to.equal(0);
+      expect(bounty.payoutMode).to.deep.equal({ issuerOnly: {} });
+      expect(bounty.activeClaim).to.be.null;
+      expect(bounty.expiresAt).to.be.null;
+      expect(bounty.contributedAmount.toNumber()).to.equal(0);
+      expect(bounty.contributorCount).to.equal(0);
+      expect(bounty.milestones).to.have.length(0);
+      expect(bounty.minMatchmakerPayouts).to.be.null;
     });
   });
 
@@ -783,7 +3619,7 @@ describe("solmates", () => {
 
       // Create auction with 10 USDC start price
       await program.methods
-        .createAuction(new BN(TEN_USDC), new BN(24 * 60 * 60))
+        .createAuction(new BN(TEN_USDC), new BN(24 * 60 * 60), null)
         .accountsStrict({
           host: alice.publicKey,
           hostProfile: aliceProfile,
@@ -803,8 +3639,12 @@ describe("solmates", () => {
           .placeBid(new BN(5 * ONE_USDC))
           .accountsStrict({
             bidder: bob.publicKey,
+            signer: bob.publicKey,
+            session: null,
+            bidderProfile: null,
             previousBidder: alice.publicKey,
             host: alice.publicKey,
+            hostProfile: getProfilePda(alice.publicKey)[0],
             mint: usdcMint,
             auction: auctionPda,
             auctionVault: auctionVault,
@@ -821,15 +3661,74 @@ describe("solmates", () => {
       }
     });
 
+    it("fails when a bidder is not on the auction allowlist", async () => {
+      const [aliceProfile] = getProfilePda(alice.publicKey);
+      const profile = await program.account.userProfile.fetch(aliceProfile);
+      const auctionId = profile.auctionCount.toNumber();
+
+      const [auctionPda] = getAuctionPda(alice.publicKey, auctionId);
+      const auctionVault = getAssociatedTokenAddressSync(
+        usdcMint,
+        auctionPda,
+        true
+      );
+
+      await program.methods
+        .createAuction(new BN(TEN_USDC), new BN(24 * 60 * 60), {
+          gate: null,
+          minProfileAge: new BN(0),
+          allowlist: [charlie.publicKey],
+        })
+        .accountsStrict({
+          host: alice.publicKey,
+          hostProfile: aliceProfile,
+          mint: usdcMint,
+          auction: auctionPda,
+          auctionVault: auctionVault,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([alice])
+        .rpc();
+
+      try {
+        await program.methods
+          .placeBid(new BN(20 * ONE_USDC))
+          .accountsStrict({
+            bidder: bob.publicKey,
+            signer: bob.publicKey,
+            session: null,
+            bidderProfile: null,
+            previousBidder: alice.publicKey,
+            host: alice.publicKey,
+            hostProfile: getProfilePda(alice.publicKey)[0],
+            mint: usdcMint,
+            auction: auctionPda,
+            auctionVault: auctionVault,
+            bidderTokenAccount: bobTokenAccount,
+            previousBidderTokenAccount: aliceTokenAccount,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([bob])
+          .rpc();
+
+        expect.fail("Should have thrown BidderNotAllowlisted error");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("BidderNotAllowlisted");
+      }
+    });
+
     it("fails when unauthorized user tries to update bounty", async () => {
       // Create bounty for charlie first
       const [bountyPda] = getBountyPda(charlie.publicKey);
       const bountyVault = getAssociatedTokenAddressSync(usdcMint, bountyPda, true);
 
       await program.methods
-        .createBounty(new BN(10 * ONE_USDC))
+        .createBounty(new BN(10 * ONE_USDC), { issuerOnly: {} }, null, [], null)
         .accountsStrict({
           issuer: charlie.publicKey,
+          issuerProfile: getProfilePda(charlie.publicKey)[0],
           mint: usdcMint,
           bounty: bountyPda,
           bountyVault: bountyVault,
@@ -845,9 +3744,10 @@ describe("solmates", () => {
       const [bobBountyPda] = getBountyPda(bob.publicKey);
       try {
         await program.methods
-          .updateBounty(new BN(20 * ONE_USDC))
+          .updateBounty(new BN(20 * ONE_USDC), null)
           .accountsStrict({
             issuer: bob.publicKey,
+            issuerProfile: getProfilePda(bob.publicKey)[0],
             mint: usdcMint,
             bounty: bobBountyPda, // Bob's bounty PDA (different from charlie's)
             bountyVault: getAssociatedTokenAddressSync(usdcMint, bobBountyPda, true),